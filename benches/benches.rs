@@ -1,57 +1,17 @@
 #![feature(test)]
 use crossbeam_skiplist::SkipMap;
+use skippy_rs::bench::{Comparisons, CountingKey};
 use skippy_rs::{SkipList, SyncSkipList};
-use std::sync::{atomic::AtomicUsize, Arc};
 
 extern crate test;
 
 use test::Bencher;
 
-struct CountOnCmp<K> {
-    key: K,
-    counter: Arc<AtomicUsize>,
-}
-
-impl<K> PartialEq for CountOnCmp<K>
-where
-    K: PartialEq,
-{
-    fn eq(&self, other: &Self) -> bool {
-        self.counter
-            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        self.key.eq(&other.key)
-    }
-}
-
-impl<K> Eq for CountOnCmp<K> where K: Eq {}
-
-impl<K> PartialOrd for CountOnCmp<K>
-where
-    K: Ord,
-{
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.counter
-            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        Some(self.key.cmp(&other.key))
-    }
-}
-
-impl<K> Ord for CountOnCmp<K>
-where
-    K: Ord,
-{
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.counter
-            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        self.key.cmp(&other.key)
-    }
-}
-
 #[bench]
 fn insert_skippy(b: &mut Bencher) {
     let upper = test::black_box(1_000);
     let mut seed: u16 = rand::random();
-    let counter = Arc::new(AtomicUsize::new(0));
+    let counter = Comparisons::new();
 
     b.iter(|| {
         let mut list = SkipList::new();
@@ -60,19 +20,13 @@ fn insert_skippy(b: &mut Bencher) {
             seed ^= seed << 6;
             seed ^= seed >> 11;
             seed ^= seed << 5;
-            list.insert(
-                CountOnCmp {
-                    key: seed,
-                    counter: counter.clone(),
-                },
-                "Hello There!",
-            );
+            list.insert(counter.key(seed), "Hello There!");
         }
     });
 
     println!(
         "cmp count for insert skippy: {}m",
-        counter.load(std::sync::atomic::Ordering::Acquire) / 1_000_000
+        counter.get() / 1_000_000
     );
 }
 
@@ -80,7 +34,7 @@ fn insert_skippy(b: &mut Bencher) {
 fn insert_sync_skippy(b: &mut Bencher) {
     let upper = test::black_box(1_000);
     let mut seed: u16 = rand::random();
-    let counter = Arc::new(AtomicUsize::new(0));
+    let counter = Comparisons::new();
 
     b.iter(|| {
         let list = SyncSkipList::new();
@@ -89,19 +43,13 @@ fn insert_sync_skippy(b: &mut Bencher) {
             seed ^= seed << 6;
             seed ^= seed >> 11;
             seed ^= seed << 5;
-            list.insert(
-                CountOnCmp {
-                    key: seed,
-                    counter: counter.clone(),
-                },
-                "Hello There!",
-            );
+            list.insert(counter.key(seed), "Hello There!");
         }
     });
 
     println!(
         "cmp count for insert skippy: {}m",
-        counter.load(std::sync::atomic::Ordering::Acquire) / 1_000_000
+        counter.get() / 1_000_000
     );
 }
 
@@ -109,7 +57,7 @@ fn insert_sync_skippy(b: &mut Bencher) {
 fn insert_crossbeam(b: &mut Bencher) {
     let upper = test::black_box(1_000);
     let mut seed: u16 = rand::random();
-    let counter = Arc::new(AtomicUsize::new(0));
+    let counter = Comparisons::new();
 
     b.iter(|| {
         let list = SkipMap::new();
@@ -118,19 +66,13 @@ fn insert_crossbeam(b: &mut Bencher) {
             seed ^= seed << 6;
             seed ^= seed >> 11;
             seed ^= seed << 5;
-            list.insert(
-                CountOnCmp {
-                    key: seed,
-                    counter: counter.clone(),
-                },
-                "Hello There!",
-            );
+            list.insert(counter.key(seed), "Hello There!");
         }
     });
 
     println!(
         "cmp count for insert crossbeam: {}m",
-        counter.load(std::sync::atomic::Ordering::Acquire) / 1_000_000
+        counter.get() / 1_000_000
     );
 }
 
@@ -138,21 +80,15 @@ fn insert_crossbeam(b: &mut Bencher) {
 fn get_skippy(b: &mut Bencher) {
     let upper = test::black_box(1_000);
     let mut seed: u16 = rand::random();
-    let mut list: SkipList<CountOnCmp<u16>, u8> = SkipList::new();
+    let mut list: SkipList<CountingKey<u16>, u8> = SkipList::new();
 
-    let counter = Arc::new(AtomicUsize::new(0));
+    let counter = Comparisons::new();
 
     for _ in 0..upper {
         seed ^= seed << 6;
         seed ^= seed >> 11;
         seed ^= seed << 5;
-        list.insert(
-            CountOnCmp {
-                key: seed,
-                counter: counter.clone(),
-            },
-            0,
-        );
+        list.insert(counter.key(seed), 0);
     }
 
     b.iter(|| {
@@ -160,17 +96,11 @@ fn get_skippy(b: &mut Bencher) {
             seed ^= seed << 6;
             seed ^= seed >> 11;
             seed ^= seed << 5;
-            list.get(&CountOnCmp {
-                key: seed,
-                counter: counter.clone(),
-            });
+            list.get(&counter.key(seed));
         }
     });
 
-    println!(
-        "cmp count for get skippy: {}m",
-        counter.load(std::sync::atomic::Ordering::Acquire) / 1_000_000
-    );
+    println!("cmp count for get skippy: {}m", counter.get() / 1_000_000);
 }
 
 #[bench]
@@ -179,19 +109,13 @@ fn get_skippy_sync(b: &mut Bencher) {
     let mut seed: u16 = rand::random();
     let list = SyncSkipList::new();
 
-    let counter = Arc::new(AtomicUsize::new(0));
+    let counter = Comparisons::new();
 
     for _ in 0..upper {
         seed ^= seed << 6;
         seed ^= seed >> 11;
         seed ^= seed << 5;
-        list.insert(
-            CountOnCmp {
-                key: seed,
-                counter: counter.clone(),
-            },
-            0,
-        );
+        list.insert(counter.key(seed), 0);
     }
 
     b.iter(|| {
@@ -199,16 +123,13 @@ fn get_skippy_sync(b: &mut Bencher) {
             seed ^= seed << 6;
             seed ^= seed >> 11;
             seed ^= seed << 5;
-            list.get(&CountOnCmp {
-                key: seed,
-                counter: counter.clone(),
-            });
+            list.get(&counter.key(seed));
         }
     });
 
     println!(
         "cmp count for get skippy_sync: {}m",
-        counter.load(std::sync::atomic::Ordering::Acquire) / 1_000_000
+        counter.get() / 1_000_000
     );
 }
 
@@ -218,19 +139,13 @@ fn get_crossbeam(b: &mut Bencher) {
     let mut seed: u16 = rand::random();
     let list = SkipMap::new();
 
-    let counter = Arc::new(AtomicUsize::new(0));
+    let counter = Comparisons::new();
 
     for _ in 0..upper {
         seed ^= seed << 6;
         seed ^= seed >> 11;
         seed ^= seed << 5;
-        list.insert(
-            CountOnCmp {
-                key: seed,
-                counter: counter.clone(),
-            },
-            "Hello There!",
-        );
+        list.insert(counter.key(seed), "Hello There!");
     }
 
     b.iter(|| {
@@ -238,16 +153,13 @@ fn get_crossbeam(b: &mut Bencher) {
             seed ^= seed << 6;
             seed ^= seed >> 11;
             seed ^= seed << 5;
-            list.get(&CountOnCmp {
-                key: seed,
-                counter: counter.clone(),
-            });
+            list.get(&counter.key(seed));
         }
     });
 
     println!(
         "cmp count for get crossbeam: {}m",
-        counter.load(std::sync::atomic::Ordering::Acquire) / 1_000_000
+        counter.get() / 1_000_000
     );
 }
 
@@ -255,38 +167,29 @@ fn get_crossbeam(b: &mut Bencher) {
 fn remove_skippy(b: &mut Bencher) {
     let upper = test::black_box(1_000);
     let mut seed: u16 = rand::random();
-    let mut list: SkipList<CountOnCmp<u16>, u8> = SkipList::new();
+    let mut list: SkipList<CountingKey<u16>, u8> = SkipList::new();
 
-    let counter = Arc::new(AtomicUsize::new(0));
+    let counter = Comparisons::new();
 
     b.iter(|| {
         for _ in 0..upper {
             seed ^= seed << 6;
             seed ^= seed >> 11;
             seed ^= seed << 5;
-            list.insert(
-                CountOnCmp {
-                    key: seed,
-                    counter: counter.clone(),
-                },
-                0,
-            );
+            list.insert(counter.key(seed), 0);
         }
 
         for _ in 0..upper {
             seed ^= seed << 6;
             seed ^= seed >> 11;
             seed ^= seed << 5;
-            list.remove(&CountOnCmp {
-                key: seed,
-                counter: counter.clone(),
-            });
+            list.remove(&counter.key(seed));
         }
     });
 
     println!(
         "cmp count for remove skippy: {}m",
-        counter.load(std::sync::atomic::Ordering::Acquire) / 1_000_000
+        counter.get() / 1_000_000
     );
 }
 
@@ -296,36 +199,27 @@ fn remove_skippy_sync(b: &mut Bencher) {
     let mut seed: u16 = rand::random();
     let list = SyncSkipList::new();
 
-    let counter = Arc::new(AtomicUsize::new(0));
+    let counter = Comparisons::new();
 
     b.iter(|| {
         for _ in 0..upper {
             seed ^= seed << 6;
             seed ^= seed >> 11;
             seed ^= seed << 5;
-            list.insert(
-                CountOnCmp {
-                    key: seed,
-                    counter: counter.clone(),
-                },
-                0,
-            );
+            list.insert(counter.key(seed), 0);
         }
 
         for _ in 0..upper {
             seed ^= seed << 6;
             seed ^= seed >> 11;
             seed ^= seed << 5;
-            list.remove(&CountOnCmp {
-                key: seed,
-                counter: counter.clone(),
-            });
+            list.remove(&counter.key(seed));
         }
     });
 
     println!(
         "cmp count for remove skippy_sync: {}m",
-        counter.load(std::sync::atomic::Ordering::Acquire) / 1_000_000
+        counter.get() / 1_000_000
     );
 }
 
@@ -335,36 +229,27 @@ fn remove_crossbeam(b: &mut Bencher) {
     let mut seed: u16 = rand::random();
     let list = SkipMap::new();
 
-    let counter = Arc::new(AtomicUsize::new(0));
+    let counter = Comparisons::new();
 
     b.iter(|| {
         for _ in 0..upper {
             seed ^= seed << 6;
             seed ^= seed >> 11;
             seed ^= seed << 5;
-            list.insert(
-                CountOnCmp {
-                    key: seed,
-                    counter: counter.clone(),
-                },
-                "Hello There!",
-            );
+            list.insert(counter.key(seed), "Hello There!");
         }
 
         for _ in 0..upper {
             seed ^= seed << 6;
             seed ^= seed >> 11;
             seed ^= seed << 5;
-            list.remove(&CountOnCmp {
-                key: seed,
-                counter: counter.clone(),
-            });
+            list.remove(&counter.key(seed));
         }
     });
 
     println!(
         "cmp count for remove crossbeam: {}m",
-        counter.load(std::sync::atomic::Ordering::Acquire) / 1_000_000
+        counter.get() / 1_000_000
     );
 }
 
@@ -373,9 +258,9 @@ fn inmove_skippy(b: &mut Bencher) {
     let upper = test::black_box(1_000);
     let mut seed: u16 = rand::random();
     let mut seed2: u8 = rand::random();
-    let mut list: SkipList<CountOnCmp<u16>, u8> = SkipList::new();
+    let mut list: SkipList<CountingKey<u16>, u8> = SkipList::new();
 
-    let counter = Arc::new(AtomicUsize::new(0));
+    let counter = Comparisons::new();
 
     b.iter(|| {
         for _ in 0..upper {
@@ -386,25 +271,16 @@ fn inmove_skippy(b: &mut Bencher) {
             seed2 ^= seed2 >> 5;
             seed2 ^= seed2 << 2;
             if seed2 % 5 == 0 {
-                list.remove(&CountOnCmp {
-                    key: seed,
-                    counter: counter.clone(),
-                });
+                list.remove(&counter.key(seed));
             } else {
-                list.insert(
-                    CountOnCmp {
-                        key: seed,
-                        counter: counter.clone(),
-                    },
-                    0,
-                );
+                list.insert(counter.key(seed), 0);
             }
         }
     });
 
     println!(
         "cmp count for inmove skippy: {}m; len: {}",
-        counter.load(std::sync::atomic::Ordering::Acquire) / 1_000_000,
+        counter.get() / 1_000_000,
         list.len()
     );
 }
@@ -416,7 +292,7 @@ fn inmove_skippy_sync(b: &mut Bencher) {
     let mut seed2: u8 = rand::random();
     let list = SyncSkipList::new();
 
-    let counter = Arc::new(AtomicUsize::new(0));
+    let counter = Comparisons::new();
 
     b.iter(|| {
         for _ in 0..upper {
@@ -427,25 +303,16 @@ fn inmove_skippy_sync(b: &mut Bencher) {
             seed2 ^= seed2 >> 5;
             seed2 ^= seed2 << 2;
             if seed2 % 5 == 0 {
-                list.remove(&CountOnCmp {
-                    key: seed,
-                    counter: counter.clone(),
-                });
+                list.remove(&counter.key(seed));
             } else {
-                list.insert(
-                    CountOnCmp {
-                        key: seed,
-                        counter: counter.clone(),
-                    },
-                    0,
-                );
+                list.insert(counter.key(seed), 0);
             }
         }
     });
 
     println!(
         "cmp count for inmove skippy_sync: {}m; len: {}",
-        counter.load(std::sync::atomic::Ordering::Acquire) / 1_000_000,
+        counter.get() / 1_000_000,
         list.len()
     );
 }
@@ -457,7 +324,7 @@ fn inmove_crossbeam(b: &mut Bencher) {
     let mut seed2: u8 = rand::random();
     let list = SkipMap::new();
 
-    let counter = Arc::new(AtomicUsize::new(0));
+    let counter = Comparisons::new();
 
     b.iter(|| {
         for _ in 0..upper {
@@ -468,25 +335,16 @@ fn inmove_crossbeam(b: &mut Bencher) {
             seed2 ^= seed2 >> 5;
             seed2 ^= seed2 << 2;
             if seed2 % 5 == 0 {
-                list.remove(&CountOnCmp {
-                    key: seed,
-                    counter: counter.clone(),
-                });
+                list.remove(&counter.key(seed));
             } else {
-                list.insert(
-                    CountOnCmp {
-                        key: seed,
-                        counter: counter.clone(),
-                    },
-                    0,
-                );
+                list.insert(counter.key(seed), 0);
             }
         }
     });
 
     println!(
         "cmp count for inmove crossbeam: {}m; len: {}",
-        counter.load(std::sync::atomic::Ordering::Acquire) / 1_000_000,
+        counter.get() / 1_000_000,
         list.len()
     );
 }
@@ -0,0 +1,82 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use skippy_rs::SyncSkipList;
+use std::sync::Arc;
+
+const fn randomize(mut seed: usize) -> usize {
+    seed ^= seed << 13;
+    seed ^= seed >> 17;
+    seed ^ seed << 5
+}
+
+/// Reads the list in every way it can be read, checking that what comes back is ordered.
+fn read(list: &SyncSkipList<'_, u8, ()>, mut seed: usize) {
+    for _ in 0..500 {
+        seed = randomize(seed);
+
+        match seed % 4 {
+            0 => {
+                let keys = list.iter().map(|e| *e.key()).collect::<Vec<_>>();
+                assert!(keys.windows(2).all(|w| w[0] < w[1]), "{:?}", keys);
+            }
+            1 => {
+                let keys = list.iter().rev().map(|e| *e.key()).collect::<Vec<_>>();
+                assert!(keys.windows(2).all(|w| w[0] > w[1]), "{:?}", keys);
+            }
+            2 => {
+                // The ends may change between the two calls, so there is nothing to compare them
+                // against, but both walk past the nodes being removed.
+                drop((list.get_first(), list.get_last()));
+            }
+            _ => {
+                let key = (seed >> 8) as u8;
+
+                if let Some(entry) = list.get(&key) {
+                    assert_eq!(*entry.key(), key);
+                }
+            }
+        }
+    }
+}
+
+fn write(list: &SyncSkipList<'_, u8, ()>, mut seed: usize) {
+    for _ in 0..5_000 {
+        seed = randomize(seed);
+        let key = (seed >> 8) as u8;
+
+        if seed % 5 == 0 {
+            list.remove(&key);
+        } else {
+            list.insert(key, ());
+        }
+    }
+}
+
+// Each seed spawns a thread, which reads the list if the seed is odd and writes it otherwise.
+fuzz_target!(|seeds: Vec<usize>| {
+    let list = Arc::new(SyncSkipList::new());
+
+    let threads = seeds
+        .into_iter()
+        .take(16)
+        .map(|seed| {
+            let list = list.clone();
+
+            std::thread::spawn(move || {
+                if seed % 2 == 1 {
+                    read(&list, seed);
+                } else {
+                    write(&list, seed);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for thread in threads {
+        thread.join().unwrap()
+    }
+
+    let keys = list.iter().map(|e| *e.key()).collect::<Vec<_>>();
+    assert!(keys.windows(2).all(|w| w[0] < w[1]), "{:?}", keys);
+});
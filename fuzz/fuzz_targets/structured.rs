@@ -0,0 +1,6 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use skippy_rs::fuzz::{run, Op};
+
+fuzz_target!(|ops: Vec<Op<u8, u16>>| run(ops));
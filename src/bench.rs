@@ -0,0 +1,135 @@
+//! Keys that count how often they are compared, to measure how many comparisons the operations of
+//! a list take on a given workload.
+//!
+//! ```
+//! use skippy_rs::{bench::Comparisons, SyncSkipList};
+//!
+//! let comparisons = Comparisons::new();
+//! let list = SyncSkipList::new();
+//!
+//! for i in 0..1000 {
+//!     list.insert(comparisons.key(i), ());
+//! }
+//!
+//! comparisons.reset();
+//! list.get(&comparisons.key(500));
+//!
+//! println!("a get took {} comparisons", comparisons.get());
+//! ```
+
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use std::sync::{atomic, atomic::AtomicUsize, Arc};
+
+/// A counter shared by all the [CountingKey](CountingKey)s created from it.
+#[derive(Debug, Clone, Default)]
+pub struct Comparisons(Arc<AtomicUsize>);
+
+impl Comparisons {
+    pub fn new() -> Self {
+        Comparisons::default()
+    }
+
+    /// Wraps `key` so that comparing it counts towards this counter.
+    pub fn key<K>(&self, key: K) -> CountingKey<K> {
+        CountingKey {
+            key,
+            comparisons: self.clone(),
+        }
+    }
+
+    /// The number of comparisons counted since the counter was created or last reset.
+    pub fn get(&self) -> usize {
+        self.0.load(atomic::Ordering::Acquire)
+    }
+
+    /// Resets the count to 0, returning what it was.
+    pub fn reset(&self) -> usize {
+        self.0.swap(0, atomic::Ordering::AcqRel)
+    }
+
+    fn count(&self) {
+        self.0.fetch_add(1, atomic::Ordering::Relaxed);
+    }
+}
+
+/// A key that increments its [Comparisons](Comparisons) every time it is compared to another.
+#[derive(Clone)]
+pub struct CountingKey<K> {
+    key: K,
+    comparisons: Comparisons,
+}
+
+impl<K> CountingKey<K> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_inner(self) -> K {
+        self.key
+    }
+}
+
+impl<K> PartialEq for CountingKey<K>
+where
+    K: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.comparisons.count();
+        self.key.eq(&other.key)
+    }
+}
+
+impl<K> Eq for CountingKey<K> where K: Eq {}
+
+impl<K> PartialOrd for CountingKey<K>
+where
+    K: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K> Ord for CountingKey<K>
+where
+    K: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.comparisons.count();
+        self.key.cmp(&other.key)
+    }
+}
+
+impl<K> Debug for CountingKey<K>
+where
+    K: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.key.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod bench_test {
+    use super::*;
+
+    use crate::SkipList;
+
+    #[test]
+    fn test_counts_comparisons() {
+        let comparisons = Comparisons::new();
+        let mut list = SkipList::new();
+
+        for i in 0..100 {
+            list.insert(comparisons.key(i), ());
+        }
+
+        assert!(comparisons.reset() > 0);
+        assert_eq!(comparisons.get(), 0);
+
+        assert_eq!(*list.get(&comparisons.key(50)).unwrap().key().key(), 50);
+        assert!(comparisons.get() > 0);
+        assert!(comparisons.get() < 100);
+    }
+}
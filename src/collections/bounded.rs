@@ -0,0 +1,329 @@
+use core::fmt::Debug;
+use core::hint::spin_loop;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::internal::sync;
+use crate::SyncSkipList;
+
+/// What a [BoundedSkipList](BoundedSkipList) does with an insert of a new key once it is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Hands the key and value back to the caller.
+    Reject,
+    /// Removes the entry with the smallest key to make room.
+    EvictFirst,
+    /// Removes the entry with the greatest key to make room.
+    EvictLast,
+}
+
+/// The entry of the value an insert replaced and the entry it evicted to make room, if any.
+pub(crate) type Pushed<'a, K, V> = (Option<sync::Entry<'a, K, V>>, Option<sync::Entry<'a, K, V>>);
+
+/// A concurrent list holding at most a fixed number of entries. Every insert of a new key claims
+/// room for its entry before linking it, rejecting the key or evicting another entry as its
+/// [Overflow](Overflow) policy says when there is none left. Replacing the value of a key claims
+/// no room. No entry is ever linked without room claimed for it, so the bound holds at all times.
+///
+/// Removing through an [Entry](sync::Entry) bypasses the bound and leaves its room claimed, use
+/// [remove](Self::remove) instead.
+pub struct BoundedSkipList<K, V> {
+    list: SyncSkipList<'static, K, V>,
+    capacity: usize,
+    overflow: Overflow,
+    /// The entries in the list, along with the room inserts claimed for entries they are linking.
+    occupied: AtomicUsize,
+}
+
+impl<K, V> BoundedSkipList<K, V> {
+    /// Creates a list holding at most `capacity` entries.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is 0.
+    pub fn new(capacity: usize, overflow: Overflow) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+
+        BoundedSkipList {
+            list: SyncSkipList::new(),
+            capacity,
+            overflow,
+            occupied: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+}
+
+impl<K, V> BoundedSkipList<K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Inserts `val` under `key`, returning the entry of the value it replaced, if any. If the
+    /// list is full and `key` is new, the list either makes room first or, with
+    /// [Overflow::Reject](Overflow::Reject), hands `key` and `val` back. Should another thread
+    /// insert `key` while we make room for it, `val` replaces theirs after all, and the room goes
+    /// unused.
+    pub fn insert(&self, key: K, val: V) -> Result<Option<sync::Entry<'_, K, V>>, (K, V)> {
+        self.insert_evicting(key, val).map(|(replaced, _)| replaced)
+    }
+
+    /// Inserts just as [insert](Self::insert) does, returning the entry evicted to make room
+    /// along with the entry of the value it replaced.
+    pub(crate) fn insert_evicting(
+        &self,
+        key: K,
+        val: V,
+    ) -> Result<Pushed<'_, K, V>, (K, V)> {
+        // Replacing a value keeps the number of entries as it is, so it needs no room.
+        let val = match self.list.replace(&key, val) {
+            Ok(replaced) => return Ok((Some(replaced), None)),
+            Err(val) => val,
+        };
+
+        let Ok(evicted) = self.claim() else {
+            return Err((key, val));
+        };
+
+        Ok((self.unclaimed(self.list.insert(key, val)), evicted))
+    }
+
+    /// Frees the room an insert claimed if it replaced a value rather than linking an entry.
+    fn unclaimed<'a>(
+        &self,
+        replaced: Option<sync::Entry<'a, K, V>>,
+    ) -> Option<sync::Entry<'a, K, V>> {
+        if replaced.is_some() {
+            self.occupied.fetch_sub(1, Ordering::AcqRel);
+        }
+
+        replaced
+    }
+
+    /// Claims room for a new entry, evicting another if the policy allows it. Returns the evicted
+    /// entry, if any, or an error if there is no room.
+    fn claim(&self) -> Result<Option<sync::Entry<'_, K, V>>, ()> {
+        let mut occupied = self.occupied.load(Ordering::Acquire);
+
+        loop {
+            if occupied < self.capacity {
+                match self.occupied.compare_exchange_weak(
+                    occupied,
+                    occupied + 1,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => return Ok(None),
+                    Err(other) => occupied = other,
+                }
+
+                continue;
+            }
+
+            let evicted = match self.overflow {
+                Overflow::Reject => return Err(()),
+                Overflow::EvictFirst => self.list.pop_front(),
+                Overflow::EvictLast => self.list.pop_back(),
+            };
+
+            // The room of the evicted entry passes over to ours.
+            if evicted.is_some() {
+                return Ok(evicted);
+            }
+
+            // Every bit of room is claimed by inserts that did not link their entries yet.
+            spin_loop();
+            occupied = self.occupied.load(Ordering::Acquire);
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<sync::Entry<'_, K, V>> {
+        self.list.get(key)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.list.get(key).is_some()
+    }
+
+    /// Removes `key` and returns its entry, if it was in the list, freeing its room.
+    pub fn remove(&self, key: &K) -> Option<sync::Entry<'_, K, V>> {
+        self.released(self.list.remove(key))
+    }
+
+    pub fn pop_front(&self) -> Option<sync::Entry<'_, K, V>> {
+        self.released(self.list.pop_front())
+    }
+
+    pub fn pop_back(&self) -> Option<sync::Entry<'_, K, V>> {
+        self.released(self.list.pop_back())
+    }
+
+    /// Frees the room of an entry that was removed, if any.
+    fn released<'a>(
+        &self,
+        removed: Option<sync::Entry<'a, K, V>>,
+    ) -> Option<sync::Entry<'a, K, V>> {
+        if removed.is_some() {
+            self.occupied.fetch_sub(1, Ordering::AcqRel);
+        }
+
+        removed
+    }
+
+    pub fn get_first(&self) -> Option<sync::Entry<'_, K, V>> {
+        self.list.get_first()
+    }
+
+    pub fn get_last(&self) -> Option<sync::Entry<'_, K, V>> {
+        self.list.get_last()
+    }
+
+    /// Returns an iterator over the entries, in ascending order.
+    pub fn iter(&self) -> sync::Iter<'_, K, V> {
+        self.list.iter()
+    }
+}
+
+impl<K, V> Debug for BoundedSkipList<K, V>
+where
+    K: Ord + Send + Debug,
+    V: Send + Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.list.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod bounded_test {
+    use super::*;
+
+    #[test]
+    fn test_overflow() {
+        let reject = BoundedSkipList::new(3, Overflow::Reject);
+        let first = BoundedSkipList::new(3, Overflow::EvictFirst);
+        let last = BoundedSkipList::new(3, Overflow::EvictLast);
+
+        for list in [&reject, &first, &last] {
+            for i in 1..4 {
+                assert!(list.insert(i, i).unwrap().is_none());
+            }
+
+            // Replacing a value needs no room.
+            assert_eq!(*list.insert(2, 20).unwrap().unwrap().val(), 2);
+        }
+
+        assert_eq!(reject.insert(0, 0).unwrap_err(), (0, 0));
+        assert!(first.insert(0, 0).is_ok());
+        assert!(last.insert(0, 0).is_ok());
+
+        assert!(reject.iter().map(|e| *e.key()).eq([1, 2, 3]));
+        assert!(first.iter().map(|e| *e.key()).eq([0, 2, 3]));
+        assert!(last.iter().map(|e| *e.key()).eq([0, 1, 2]));
+
+        // Removing an entry makes room for another.
+        reject.remove(&1);
+        assert!(reject.insert(4, 4).is_ok());
+        assert_eq!(reject.len(), 3);
+    }
+
+    #[test]
+    fn test_concurrent_reject() {
+        let list = BoundedSkipList::new(64, Overflow::Reject);
+        let accepted = AtomicUsize::new(0);
+
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                let (list, accepted) = (&list, &accepted);
+
+                s.spawn(move || {
+                    for i in 0..250 {
+                        if list.insert(i * 4 + t, ()).is_ok() {
+                            accepted.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+
+        // Inserts of distinct keys never overshoot the bound.
+        assert_eq!(accepted.into_inner(), 64);
+        assert!(list.iter().count() <= 64);
+    }
+
+    #[test]
+    fn test_concurrent_overlapping() {
+        let list = BoundedSkipList::new(8, Overflow::EvictFirst);
+
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                let list = &list;
+
+                s.spawn(move || {
+                    for i in 0..2_000usize {
+                        let key = (i * 7 + t * 13) % 32;
+
+                        if i % 3 == 0 {
+                            list.remove(&key);
+                        } else {
+                            assert!(list.insert(key, i).is_ok());
+                        }
+
+                        // Not even replacing the value of a key removed in the meantime links
+                        // an entry without room for it.
+                        assert!(list.len() <= 8);
+                    }
+                });
+            }
+        });
+
+        assert!(list.iter().count() <= 8);
+    }
+
+    #[test]
+    fn test_concurrent_same_keys() {
+        let list = BoundedSkipList::new(64, Overflow::Reject);
+        let gone = AtomicUsize::new(0);
+
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                let (list, gone) = (&list, &gone);
+
+                s.spawn(move || {
+                    for i in 0..4_000usize {
+                        let key = i / 2 % 4;
+
+                        // Removing the keys over and over has inserts race to link them anew.
+                        let out = if i % 2 == 0 {
+                            list.remove(&key)
+                        } else {
+                            list.insert(key, t * 4_000 + i).unwrap()
+                        };
+
+                        if out.is_some() {
+                            gone.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+
+        // Every value was replaced, removed or is in the list, none was dropped on the way.
+        assert_eq!(gone.into_inner() + list.len(), 8_000);
+        assert_eq!(list.occupied.load(Ordering::Relaxed), list.len());
+    }
+}
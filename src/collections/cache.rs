@@ -0,0 +1,197 @@
+use core::fmt::Debug;
+
+use crate::internal::sync;
+use crate::{BoundedSkipList, Overflow};
+
+/// The entry an insert into a [SkipCache](SkipCache) pushed out of it, if any.
+#[derive(Debug)]
+pub enum Displaced<'a, K, V> {
+    /// The key was new and the cache had room for it.
+    Nothing,
+    /// The key was in the cache, this is the entry of its previous value.
+    Replaced(sync::Entry<'a, K, V>),
+    /// The key was new and the cache full, this is the entry evicted to make room.
+    Evicted(sync::Entry<'a, K, V>),
+}
+
+/// A concurrent cache holding at most a fixed number of entries, which evicts the entry with the
+/// smallest key to make room for a new one. The order of the keys is the eviction order, so keys
+/// that lead with a timestamp or a sequence number make for a cache evicting its oldest entries.
+///
+/// The cache is a [BoundedSkipList](BoundedSkipList) evicting with
+/// [Overflow::EvictFirst](Overflow::EvictFirst), which also tells which entry an insert pushed
+/// out. Should two threads insert the same new key at once, both may evict an entry for it, though
+/// only one links it and the other replaces its value. Removing through an [Entry](sync::Entry)
+/// leaves its room claimed, use [remove](Self::remove) instead.
+pub struct SkipCache<K, V> {
+    list: BoundedSkipList<K, V>,
+}
+
+impl<K, V> SkipCache<K, V> {
+    /// Creates a cache holding at most `capacity` entries.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        SkipCache {
+            list: BoundedSkipList::new(capacity, Overflow::EvictFirst),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.list.capacity()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+}
+
+impl<K, V> SkipCache<K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Inserts `val` under `key`, returning the entry this pushed out of the cache: the previous
+    /// value of `key`, or the entry with the smallest key if `key` is new and the cache full.
+    /// Should another thread insert `key` while we make room for it, `val` replaces theirs after
+    /// all, and this returns the entry of their value rather than the one evicted for nothing.
+    pub fn insert(&self, key: K, val: V) -> Displaced<'_, K, V> {
+        // A cache evicts rather than rejecting, so there is always room in the end.
+        let Ok((replaced, evicted)) = self.list.insert_evicting(key, val) else {
+            unreachable!("a cache never rejects an insert");
+        };
+
+        match (replaced, evicted) {
+            (Some(replaced), _) => Displaced::Replaced(replaced),
+            (None, Some(evicted)) => Displaced::Evicted(evicted),
+            (None, None) => Displaced::Nothing,
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<sync::Entry<'_, K, V>> {
+        self.list.get(key)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.list.contains_key(key)
+    }
+
+    /// Removes `key` and returns its entry, if it was in the cache, freeing its room.
+    pub fn remove(&self, key: &K) -> Option<sync::Entry<'_, K, V>> {
+        self.list.remove(key)
+    }
+
+    /// Evicts the entry with the smallest key, the one the next insert into a full cache would
+    /// evict, and returns it.
+    pub fn evict(&self) -> Option<sync::Entry<'_, K, V>> {
+        self.list.pop_front()
+    }
+
+    /// Returns an iterator over the entries, in eviction order.
+    pub fn iter(&self) -> sync::Iter<'_, K, V> {
+        self.list.iter()
+    }
+}
+
+impl<K, V> Debug for SkipCache<K, V>
+where
+    K: Ord + Send + Debug,
+    V: Send + Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.list.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod cache_test {
+    use super::*;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_cache() {
+        let cache = SkipCache::new(3);
+
+        for i in 1..4 {
+            assert!(matches!(cache.insert(i, i), Displaced::Nothing));
+        }
+
+        // Replacing a value evicts nothing.
+        match cache.insert(2, 20) {
+            Displaced::Replaced(replaced) => assert_eq!(*replaced.val(), 2),
+            other => panic!("expected a replaced entry, got {:?}", other),
+        }
+
+        match cache.insert(4, 4) {
+            Displaced::Evicted(evicted) => assert_eq!(*evicted.key(), 1),
+            other => panic!("expected an evicted entry, got {:?}", other),
+        }
+
+        assert!(cache.iter().map(|e| *e.key()).eq([2, 3, 4]));
+
+        // Removing an entry makes room for another.
+        assert_eq!(cache.remove(&3).map(|e| *e.val()), Some(3));
+        assert!(matches!(cache.insert(5, 5), Displaced::Nothing));
+        assert_eq!(cache.evict().map(|e| *e.key()), Some(2));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_insert_concurrent() {
+        let cache = SkipCache::new(64);
+        let evicted = AtomicUsize::new(0);
+
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                let (cache, evicted) = (&cache, &evicted);
+
+                s.spawn(move || {
+                    for i in 0..250 {
+                        if let Displaced::Evicted(_) = cache.insert(i * 4 + t, ()) {
+                            evicted.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+
+        // Every insert beyond the capacity evicted exactly one entry.
+        assert_eq!(evicted.into_inner(), 1000 - 64);
+        assert_eq!(cache.iter().count(), 64);
+    }
+
+    #[test]
+    fn test_insert_same_keys() {
+        let cache = SkipCache::new(64);
+        let gone = AtomicUsize::new(0);
+
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                let (cache, gone) = (&cache, &gone);
+
+                s.spawn(move || {
+                    for i in 0..4_000usize {
+                        let key = i / 2 % 4;
+
+                        if i % 2 == 0 {
+                            if cache.remove(&key).is_some() {
+                                gone.fetch_add(1, Ordering::Relaxed);
+                            }
+                        } else if let Displaced::Replaced(_) = cache.insert(key, t * 4_000 + i) {
+                            gone.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+
+        // An insert that lost the race to link its key still replaced the value of the winner.
+        assert_eq!(gone.into_inner() + cache.len(), 8_000);
+    }
+}
@@ -0,0 +1,232 @@
+use core::cmp::Reverse;
+use core::fmt::Debug;
+use core::ops::{Bound, RangeBounds};
+
+use crate::internal::sync;
+use crate::SyncSkipList;
+
+/// A concurrent list keeping its keys in descending order, so [get_first](Self::get_first) and
+/// [pop_front](Self::pop_front) hand out the greatest key. The keys are stored as [Reverse]
+/// internally, yet taken and handed out as they are.
+pub struct DescendingSkipList<K, V> {
+    list: SyncSkipList<'static, Reverse<K>, V>,
+}
+
+/// Borrows a key as the [Reverse] the list stores.
+fn reversed<K>(key: &K) -> &Reverse<K> {
+    // # Safety
+    //
+    // `Reverse` is `repr(transparent)`, so it shares the layout of the key it wraps.
+    unsafe { &*(key as *const K).cast::<Reverse<K>>() }
+}
+
+/// The bounds of a range over the stored keys.
+type ReversedRange<K> = (Bound<Reverse<K>>, Bound<Reverse<K>>);
+
+/// Turns a bound on the keys into one on the stored keys, which are ordered the other way.
+fn reversed_bound<K: Clone>(bound: Bound<&K>) -> Bound<Reverse<K>> {
+    bound.map(|key| Reverse(key.clone()))
+}
+
+impl<K, V> DescendingSkipList<K, V> {
+    pub fn new() -> Self {
+        DescendingSkipList {
+            list: SyncSkipList::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+}
+
+impl<K, V> DescendingSkipList<K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Inserts `val` under `key`, returning the entry of the value it replaced, if any.
+    pub fn insert(&self, key: K, val: V) -> Option<Entry<'_, K, V>> {
+        self.list.insert(Reverse(key), val).map(Entry)
+    }
+
+    pub fn get(&self, key: &K) -> Option<Entry<'_, K, V>> {
+        self.list.get(reversed(key)).map(Entry)
+    }
+
+    pub fn remove(&self, key: &K) -> Option<Entry<'_, K, V>> {
+        self.list.remove(reversed(key)).map(Entry)
+    }
+
+    /// Returns the entry with the greatest key.
+    pub fn get_first(&self) -> Option<Entry<'_, K, V>> {
+        self.list.get_first().map(Entry)
+    }
+
+    /// Returns the entry with the smallest key.
+    pub fn get_last(&self) -> Option<Entry<'_, K, V>> {
+        self.list.get_last().map(Entry)
+    }
+
+    /// Removes and returns the entry with the greatest key.
+    pub fn pop_front(&self) -> Option<Entry<'_, K, V>> {
+        self.list.pop_front().map(Entry)
+    }
+
+    /// Removes and returns the entry with the smallest key.
+    pub fn pop_back(&self) -> Option<Entry<'_, K, V>> {
+        self.list.pop_back().map(Entry)
+    }
+
+    /// Returns an iterator over the entries, in descending order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter(self.list.iter())
+    }
+
+    /// Returns an iterator over the entries whose keys lie within `range`, in descending order.
+    pub fn range<R>(&self, range: R) -> Range<'_, K, V>
+    where
+        K: Clone,
+        R: RangeBounds<K>,
+    {
+        Range(self.list.range((
+            reversed_bound(range.end_bound()),
+            reversed_bound(range.start_bound()),
+        )))
+    }
+}
+
+impl<K, V> Default for DescendingSkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Prints the entries as a map, in descending order, eliding all but the first
+/// [DEBUG_ENTRIES](crate::internal::utils::DEBUG_ENTRIES).
+impl<K, V> Debug for DescendingSkipList<K, V>
+where
+    K: Ord + Send + Debug,
+    V: Send + Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut map = f.debug_map();
+        let mut entries = self.iter();
+
+        for entry in entries.by_ref().take(crate::internal::utils::DEBUG_ENTRIES) {
+            map.entry(entry.key(), entry.val());
+        }
+
+        if entries.next().is_some() {
+            map.finish_non_exhaustive()
+        } else {
+            map.finish()
+        }
+    }
+}
+
+/// An entry of a [DescendingSkipList](DescendingSkipList), which hands out its key as it was
+/// inserted.
+pub struct Entry<'a, K, V>(sync::Entry<'a, Reverse<K>, V>);
+
+impl<'a, K, V> Entry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.0.key().0
+    }
+
+    pub fn val(&self) -> &V {
+        self.0.val()
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.0.is_removed()
+    }
+}
+
+impl<'a, K, V> Debug for Entry<'a, K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Entry")
+            .field("key", self.key())
+            .field("val", self.val())
+            .finish()
+    }
+}
+
+/// An iterator over the entries of a [DescendingSkipList](DescendingSkipList), from the greatest
+/// key down.
+pub struct Iter<'a, K, V>(sync::Iter<'a, Reverse<K>, V>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    type Item = Entry<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Entry)
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(Entry)
+    }
+}
+
+/// An iterator over the entries of a [DescendingSkipList](DescendingSkipList) within a range, see
+/// [range](DescendingSkipList::range).
+pub struct Range<'a, K, V>(sync::Range<'a, Reverse<K>, V, ReversedRange<K>>);
+
+impl<'a, K, V> Iterator for Range<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    type Item = Entry<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Entry)
+    }
+}
+
+#[cfg(test)]
+mod descending_test {
+    use super::*;
+
+    #[test]
+    fn test_descending() {
+        let list = DescendingSkipList::new();
+
+        for i in 0..10 {
+            list.insert(i, i * 10);
+        }
+
+        assert_eq!(*list.get_first().unwrap().key(), 9);
+        assert_eq!(*list.get_last().unwrap().key(), 0);
+        assert_eq!(*list.get(&4).unwrap().val(), 40);
+        assert!(list.iter().map(|e| *e.key()).eq((0..10).rev()));
+        assert!(list.iter().rev().map(|e| *e.key()).eq(0..10));
+        assert!(list.range(3..6).map(|e| *e.key()).eq([5, 4, 3]));
+        assert!(list.range(..=2).map(|e| *e.key()).eq([2, 1, 0]));
+
+        assert_eq!(*list.pop_front().unwrap().key(), 9);
+        assert_eq!(*list.pop_back().unwrap().key(), 0);
+        assert_eq!(*list.remove(&5).unwrap().key(), 5);
+        assert!(list.get(&5).is_none());
+        assert_eq!(list.len(), 7);
+        assert_eq!(format!("{:?}", list).find("8: 80"), Some(1));
+    }
+}
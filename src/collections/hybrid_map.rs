@@ -0,0 +1,227 @@
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::ops::RangeBounds;
+
+use crate::internal::sync::{self, index::Index};
+use crate::SyncSkipList;
+
+/// The number of slots the index of a [HybridMap](HybridMap) starts out with by default.
+const DEFAULT_CAPACITY: usize = 1 << 10;
+
+/// A concurrent map pairing the [SyncSkipList](SyncSkipList) with a hash index from keys to their
+/// nodes. Looking up a key the index points to takes constant time, while the keys can still be
+/// walked in order. The index grows along with the list, a few keys that do not fit into their
+/// bucket are looked up in the list as usual, which then points the index to them.
+pub struct HybridMap<K, V>
+where
+    K: Hash + Ord + Send,
+    V: Send,
+{
+    list: SyncSkipList<'static, K, V>,
+    index: Index<K, V>,
+}
+
+impl<K, V> HybridMap<K, V>
+where
+    K: Hash + Ord + Send,
+    V: Send,
+{
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Creates a map whose index starts out with room for at least `capacity` keys.
+    pub fn with_capacity(capacity: usize) -> Self {
+        HybridMap {
+            list: SyncSkipList::new(),
+            index: Index::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the entry of `key`. A key missing from the index is searched for in the list and
+    /// indexed, so looking it up again takes constant time.
+    pub fn get(&self, key: &K) -> Option<sync::Entry<'_, K, V>> {
+        if let Some(entry) = self.index.get(&self.list, key) {
+            return Some(entry);
+        }
+
+        let entry = self.list.get(key)?;
+        self.index.insert(&self.list, &entry);
+
+        Some(entry)
+    }
+
+    /// Inserts `val` under `key`, returning the entry of the value it replaced, if any.
+    pub fn insert(&self, key: K, val: V) -> Option<sync::Entry<'_, K, V>> {
+        let (entry, replaced) = self.list.insert_entry(key, val);
+        self.index.insert(&self.list, &entry);
+
+        replaced
+    }
+
+    /// Removes `key` and returns its entry, if it was in the map.
+    pub fn remove(&self, key: &K) -> Option<sync::Entry<'_, K, V>> {
+        let removed = self.list.remove(key)?;
+        self.index.remove(&self.list, &removed);
+
+        Some(removed)
+    }
+
+    /// Returns an iterator over the entries, in ascending order.
+    pub fn iter(&self) -> sync::Iter<'_, K, V> {
+        self.list.iter()
+    }
+
+    /// Returns an iterator over the entries whose keys lie within `range`.
+    pub fn range<R>(&self, range: R) -> sync::Range<'_, K, V, R>
+    where
+        R: RangeBounds<K>,
+    {
+        self.list.range(range)
+    }
+}
+
+impl<K, V> Default for HybridMap<K, V>
+where
+    K: Hash + Ord + Send,
+    V: Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Debug for HybridMap<K, V>
+where
+    K: Hash + Ord + Send + Debug,
+    V: Send + Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.list.fmt(f)
+    }
+}
+
+/// The index keeps the list from retiring the nodes it points to, so it has to let go of them
+/// before the list is dropped.
+impl<K, V> Drop for HybridMap<K, V>
+where
+    K: Hash + Ord + Send,
+    V: Send,
+{
+    fn drop(&mut self) {
+        self.index.clear(&self.list);
+    }
+}
+
+#[cfg(test)]
+mod hybrid_map_test {
+    use super::*;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let map = HybridMap::new();
+
+        for i in 0..100 {
+            assert!(map.insert(i, i).is_none());
+        }
+
+        assert_eq!(*map.insert(7, 70).unwrap().val(), 7);
+
+        // The first lookup of a key pushed out of the index searches the list instead.
+        for _ in 0..2 {
+            assert!((0..100).all(|i| *map.get(&i).unwrap().val() == if i == 7 { 70 } else { i }));
+        }
+
+        assert_eq!(*map.remove(&3).unwrap().key(), 3);
+        assert!(map.remove(&3).is_none());
+        assert!(!map.contains_key(&3));
+
+        map.insert(3, 30);
+        assert_eq!(*map.get(&3).unwrap().val(), 30);
+
+        assert_eq!(map.len(), 100);
+        assert!(map.iter().map(|e| *e.key()).eq(0..100));
+        assert!(map.range(10..13).map(|e| *e.key()).eq(10..13));
+    }
+
+    #[test]
+    fn test_small_index() {
+        // Most keys do not fit into the index and are looked up in the list.
+        let map = HybridMap::with_capacity(4);
+
+        for i in 0..50 {
+            map.insert(i, i.to_string());
+        }
+
+        for i in (0..50).rev() {
+            assert_eq!(*map.get(&i).unwrap().val(), i.to_string());
+        }
+
+        for i in (0..50).step_by(2) {
+            map.remove(&i);
+        }
+
+        assert!((0..50).all(|i| map.contains_key(&i) == (i % 2 == 1)));
+    }
+
+    #[test]
+    fn test_index_grows() {
+        let map = HybridMap::with_capacity(16);
+
+        for i in 0..10_000 {
+            map.insert(i, i);
+        }
+
+        // Keys the growing index let go of are indexed again on their first lookup.
+        for i in 0..10_000 {
+            assert_eq!(*map.get(&i).unwrap().val(), i);
+        }
+
+        let indexed = (0..10_000)
+            .filter(|i| map.index.get(&map.list, i).is_some())
+            .count();
+
+        // Only a few keys do not fit into their bucket.
+        assert!(indexed > 9_000, "{}", indexed);
+    }
+
+    #[test]
+    fn test_concurrent() {
+        let map = HybridMap::with_capacity(64);
+
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                let map = &map;
+
+                s.spawn(move || {
+                    for i in 0..500 {
+                        let key = (i * 7 + t) % 200;
+
+                        match i % 3 {
+                            0 => drop(map.insert(key, t)),
+                            1 => drop(map.remove(&key)),
+                            _ => drop(map.get(&key)),
+                        }
+                    }
+                });
+            }
+        });
+
+        // Whatever the index points to agrees with the list.
+        for entry in map.iter() {
+            assert_eq!(map.get(entry.key()).unwrap().key(), entry.key());
+        }
+    }
+}
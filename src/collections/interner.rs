@@ -0,0 +1,151 @@
+use core::fmt::Debug;
+use core::hash::Hash;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use crate::internal::{skiplist, sync};
+
+/// Hands out a single shared allocation for all keys that are equal, so lists keyed by `Arc<K>`
+/// store each distinct key once, no matter how many lists or entries hold it. The interner keeps
+/// every key it handed out until [purge](Self::purge) drops the ones no one else holds anymore.
+pub struct Interner<K> {
+    keys: Mutex<HashSet<Arc<K>>>,
+}
+
+impl<K> Interner<K>
+where
+    K: Hash + Eq,
+{
+    pub fn new() -> Self {
+        Interner {
+            keys: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// The number of distinct keys the interner holds.
+    pub fn len(&self) -> usize {
+        self.keys.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the shared copy of `key`, making `key` the shared copy if there is none yet.
+    pub fn intern(&self, key: K) -> Arc<K> {
+        let mut keys = self.keys.lock().unwrap();
+
+        if let Some(shared) = keys.get(&key) {
+            return shared.clone();
+        }
+
+        let shared = Arc::new(key);
+        keys.insert(shared.clone());
+
+        shared
+    }
+
+    /// Returns the shared copy of `key`, cloning `key` only if there is none yet.
+    pub fn intern_ref(&self, key: &K) -> Arc<K>
+    where
+        K: Clone,
+    {
+        let mut keys = self.keys.lock().unwrap();
+
+        if let Some(shared) = keys.get(key) {
+            return shared.clone();
+        }
+
+        let shared = Arc::new(key.clone());
+        keys.insert(shared.clone());
+
+        shared
+    }
+
+    /// Drops the keys held by no one but the interner, returning how many it dropped.
+    pub fn purge(&self) -> usize {
+        let mut keys = self.keys.lock().unwrap();
+        let len = keys.len();
+
+        keys.retain(|key| Arc::strong_count(key) > 1);
+
+        len - keys.len()
+    }
+}
+
+impl<K> Default for Interner<K>
+where
+    K: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> Debug for Interner<K>
+where
+    K: Hash + Eq,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Interner")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<'domain, K, V> skiplist::SkipList<'domain, Arc<K>, V>
+where
+    K: Ord + Hash,
+{
+    /// Inserts `val` under the shared copy of `key` from `interner`, see
+    /// [insert](skiplist::SkipList::insert).
+    pub fn insert_interned(&mut self, interner: &Interner<K>, key: K, val: V) -> Option<V> {
+        self.insert(interner.intern(key), val)
+    }
+}
+
+impl<'domain, K, V> sync::SkipList<'domain, Arc<K>, V>
+where
+    K: Ord + Hash + Send + Sync,
+    V: Send,
+{
+    /// Inserts `val` under the shared copy of `key` from `interner`, see
+    /// [insert](sync::SkipList::insert).
+    pub fn insert_interned<'a>(
+        &'a self,
+        interner: &Interner<K>,
+        key: K,
+        val: V,
+    ) -> Option<sync::Entry<'a, Arc<K>, V>> {
+        self.insert(interner.intern(key), val)
+    }
+}
+
+#[cfg(test)]
+mod interner_test {
+    use super::*;
+
+    #[test]
+    fn test_interner() {
+        let interner = Interner::new();
+        let (mut a, b) = (skiplist::SkipList::new(), sync::SkipList::new());
+
+        for i in 0..10 {
+            a.insert_interned(&interner, i.to_string(), i);
+            b.insert_interned(&interner, i.to_string(), i);
+        }
+
+        assert_eq!(interner.len(), 10);
+
+        // Both lists share a single allocation for each key.
+        let first = b.get_first().unwrap();
+        assert!(Arc::ptr_eq(a.get_first().unwrap().key(), first.key()));
+        assert!(Arc::ptr_eq(&interner.intern_ref(&"0".into()), first.key()));
+        drop(first);
+
+        assert_eq!(interner.purge(), 0);
+        drop((a, b));
+        assert_eq!(interner.purge(), 10);
+        assert!(interner.is_empty());
+    }
+}
@@ -0,0 +1,282 @@
+use core::fmt::Debug;
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::SyncSkipList;
+
+/// When a write happened, as far as the replicas of an [LwwMap](LwwMap) can tell. Stamps order
+/// by their logical time first and the replica that wrote them second, so no two writes of
+/// different replicas ever tie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Stamp {
+    pub time: u64,
+    pub replica: u64,
+}
+
+/// A write to an [LwwMap](LwwMap), to be shipped to the other replicas. A `val` of `None` removes
+/// the key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Op<K, V> {
+    pub key: K,
+    pub val: Option<V>,
+    pub stamp: Stamp,
+}
+
+/// The latest write to a key. Removed keys keep their register, so an older write arriving late
+/// cannot bring them back.
+struct Register<V> {
+    stamp: Option<Stamp>,
+    val: Option<V>,
+}
+
+/// A concurrent map whose replicas converge by keeping, for every key, the write with the greatest
+/// [Stamp](Stamp). Every local write returns the [Op](Op) to send to the other replicas, which
+/// [merge](Self::merge) it. Applying the same ops in any order, any number of times, leaves every
+/// replica with the same entries.
+///
+/// Stamps come from a logical clock, which moves past the time of every op the replica applies,
+/// so a write always wins over the writes its replica has seen. Removed keys stay in the list as
+/// tombstones.
+pub struct LwwMap<K, V> {
+    list: SyncSkipList<'static, K, Mutex<Register<V>>>,
+    replica: u64,
+    clock: AtomicU64,
+    /// The keys that are not removed.
+    live: AtomicUsize,
+}
+
+impl<K, V> LwwMap<K, V> {
+    /// Creates an empty replica, which stamps its writes with `replica`. Every replica of a map
+    /// needs an id of its own.
+    pub fn new(replica: u64) -> Self {
+        LwwMap {
+            list: SyncSkipList::new(),
+            replica,
+            clock: AtomicU64::new(0),
+            live: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn replica(&self) -> u64 {
+        self.replica
+    }
+
+    /// The number of keys that are not removed.
+    pub fn len(&self) -> usize {
+        self.live.load(Ordering::Acquire)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn tick(&self) -> Stamp {
+        Stamp {
+            time: self.clock.fetch_add(1, Ordering::AcqRel) + 1,
+            replica: self.replica,
+        }
+    }
+}
+
+impl<K, V> LwwMap<K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Sets `key` to `val`, returning the op to send to the other replicas.
+    pub fn insert(&self, key: K, val: V) -> Op<K, V>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.write(key, Some(val))
+    }
+
+    /// Removes `key`, returning the op to send to the other replicas.
+    pub fn remove(&self, key: K) -> Op<K, V>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.write(key, None)
+    }
+
+    fn write(&self, key: K, val: Option<V>) -> Op<K, V>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let op = Op {
+            key,
+            val,
+            stamp: self.tick(),
+        };
+
+        self.apply(op.clone());
+
+        op
+    }
+
+    /// Applies `op`, unless the key holds a write with a greater stamp already. Returns whether
+    /// `op` took effect.
+    pub fn apply(&self, op: Op<K, V>) -> bool {
+        self.clock.fetch_max(op.stamp.time, Ordering::AcqRel);
+
+        let empty = Register {
+            stamp: None,
+            val: None,
+        };
+        let entry = self.list.get_or_insert(op.key, Mutex::new(empty));
+        let mut register = entry.val().lock().unwrap();
+
+        if register.stamp >= Some(op.stamp) {
+            return false;
+        }
+
+        match (register.val.is_some(), op.val.is_some()) {
+            (false, true) => {
+                self.live.fetch_add(1, Ordering::AcqRel);
+            }
+            (true, false) => {
+                self.live.fetch_sub(1, Ordering::AcqRel);
+            }
+            _ => {}
+        }
+
+        *register = Register {
+            stamp: Some(op.stamp),
+            val: op.val,
+        };
+
+        true
+    }
+
+    /// Applies every op of `ops`, returning how many took effect.
+    pub fn merge<I>(&self, ops: I) -> usize
+    where
+        I: IntoIterator<Item = Op<K, V>>,
+    {
+        ops.into_iter()
+            .map(|op| self.apply(op))
+            .filter(|&applied| applied)
+            .count()
+    }
+
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.list.get(key)?.val().lock().unwrap().val.clone()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.list
+            .get(key)
+            .is_some_and(|e| e.val().lock().unwrap().val.is_some())
+    }
+
+    /// The stamp of the latest write to `key`, be it a removal or not.
+    pub fn stamp(&self, key: &K) -> Option<Stamp> {
+        self.list.get(key)?.val().lock().unwrap().stamp
+    }
+
+    /// Returns the latest write to every key, removals included, in ascending key order. Merging
+    /// these into another replica brings it up to date with this one.
+    pub fn ops(&self) -> Vec<Op<K, V>>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.list
+            .iter()
+            .filter_map(|e| {
+                let register = e.val().lock().unwrap();
+
+                Some(Op {
+                    key: e.key().clone(),
+                    val: register.val.clone(),
+                    stamp: register.stamp?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Prints the keys that are not removed along with their values, as a map.
+impl<K, V> Debug for LwwMap<K, V>
+where
+    K: Ord + Send + Debug,
+    V: Send + Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut map = f.debug_map();
+
+        for entry in self.list.iter() {
+            if let Some(val) = &entry.val().lock().unwrap().val {
+                map.entry(entry.key(), val);
+            }
+        }
+
+        map.finish()
+    }
+}
+
+#[cfg(test)]
+mod lww_map_test {
+    use super::*;
+
+    #[test]
+    fn test_last_write_wins() {
+        let (a, b) = (LwwMap::new(1), LwwMap::new(2));
+
+        let first = a.insert("x", 1);
+        let second = b.insert("x", 2);
+
+        // Both writes happened at the same logical time, so the greater replica wins.
+        assert_eq!(first.stamp.time, second.stamp.time);
+        assert!(!b.apply(first.clone()));
+        assert!(a.apply(second.clone()));
+        assert_eq!(a.get(&"x"), Some(2));
+
+        // A replica's next write wins over everything it has seen.
+        let removed = a.remove("x");
+        assert!(removed.stamp > second.stamp);
+        assert_eq!(b.merge([removed.clone(), second, first]), 1);
+
+        assert!(a.is_empty() && b.is_empty());
+        assert_eq!(a.stamp(&"x"), Some(removed.stamp));
+    }
+
+    #[test]
+    fn test_converge() {
+        let replicas = [LwwMap::new(0), LwwMap::new(1), LwwMap::new(2)];
+        let mut ops = Vec::new();
+
+        for (i, replica) in replicas.iter().enumerate() {
+            for key in 0..20 {
+                ops.push(if (key + i) % 4 == 0 {
+                    replica.remove(key)
+                } else {
+                    replica.insert(key, key * 10 + i)
+                });
+            }
+        }
+
+        // Every replica applies all ops, in an order of its own and with duplicates.
+        replicas[0].merge(ops.iter().cloned());
+        replicas[1].merge(ops.iter().rev().cloned());
+        replicas[2].merge(ops.iter().step_by(2).chain(ops.iter()).cloned());
+
+        let states = replicas.each_ref().map(|r| r.ops());
+
+        assert_eq!(states[0], states[1]);
+        assert_eq!(states[0], states[2]);
+        assert_eq!(replicas[0].len(), replicas[2].len());
+
+        // Merging a replica's state into a new one brings it up to date.
+        let fresh = LwwMap::new(3);
+        fresh.merge(states[0].clone());
+        assert_eq!(fresh.ops(), states[0]);
+        assert_eq!(format!("{:?}", fresh), format!("{:?}", replicas[1]));
+    }
+}
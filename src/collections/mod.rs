@@ -1 +1,10 @@
+pub mod bounded;
+pub mod cache;
+pub mod descending;
+pub mod hybrid_map;
+pub mod interner;
+pub mod lww_map;
+pub mod multi_map;
 pub mod priority_queue;
+pub mod skip_map;
+pub mod skip_set;
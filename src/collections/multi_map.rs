@@ -0,0 +1,252 @@
+use core::fmt::Debug;
+use core::ops::Bound;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use crate::internal::sync;
+use crate::SyncSkipList;
+
+/// A concurrent map holding any number of values per key. Inserting never replaces a value, the
+/// values of a key are kept in the order they were inserted in.
+///
+/// Every value is stored under its key along with a sequence number, which tells it apart from
+/// the other values of the key.
+pub struct MultiMap<K, V> {
+    list: SyncSkipList<'static, Dup<K>, V>,
+    seq: AtomicU64,
+}
+
+/// A key as the list stores it, once for every value inserted under it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Dup<K> {
+    key: K,
+    seq: u64,
+}
+
+/// The bounds of a range over all values of a key.
+type DupRange<K> = (Bound<Dup<K>>, Bound<Dup<K>>);
+
+fn all_of<K: Clone>(key: &K) -> DupRange<K> {
+    let dup = |seq| Dup {
+        key: key.clone(),
+        seq,
+    };
+
+    (Bound::Included(dup(0)), Bound::Included(dup(u64::MAX)))
+}
+
+impl<K, V> MultiMap<K, V> {
+    pub fn new() -> Self {
+        MultiMap {
+            list: SyncSkipList::new(),
+            seq: AtomicU64::new(0),
+        }
+    }
+
+    /// The number of values, counting every value of a key.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+}
+
+impl<K, V> MultiMap<K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Adds `val` to the values of `key`, returning its entry.
+    pub fn insert(&self, key: K, val: V) -> Entry<'_, K, V> {
+        let seq = self.seq.fetch_add(1, Ordering::Relaxed);
+
+        Entry(self.list.get_or_insert(Dup { key, seq }, val))
+    }
+
+    /// Returns an iterator over the values of `key`, in the order they were inserted in.
+    pub fn get_all(&self, key: &K) -> GetAll<'_, K, V>
+    where
+        K: Clone,
+    {
+        GetAll(self.list.range(all_of(key)))
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool
+    where
+        K: Clone,
+    {
+        self.get_all(key).next().is_some()
+    }
+
+    /// Removes the value of `key` that was inserted first, returning its entry.
+    pub fn remove_one(&self, key: &K) -> Option<Entry<'_, K, V>>
+    where
+        K: Clone,
+    {
+        loop {
+            let first = self.list.range(all_of(key)).next()?;
+
+            // Someone else may remove the value before us, in which case we try the next one.
+            if let Some(removed) = self.list.remove(first.key()) {
+                return Some(Entry(removed));
+            }
+        }
+    }
+
+    /// Removes every value of `key`, returning how many this call removed.
+    pub fn remove_all(&self, key: &K) -> usize
+    where
+        K: Clone,
+    {
+        self.list
+            .range(all_of(key))
+            .filter(|entry| self.list.remove(entry.key()).is_some())
+            .count()
+    }
+
+    /// Returns an iterator over the entries, in ascending key order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter(self.list.iter())
+    }
+}
+
+impl<K, V> Default for MultiMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Prints the entries as a map, listing a key once for every value, eliding all but the first
+/// [DEBUG_ENTRIES](crate::internal::utils::DEBUG_ENTRIES).
+impl<K, V> Debug for MultiMap<K, V>
+where
+    K: Ord + Send + Debug,
+    V: Send + Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut map = f.debug_map();
+        let mut entries = self.iter();
+
+        for entry in entries.by_ref().take(crate::internal::utils::DEBUG_ENTRIES) {
+            map.entry(entry.key(), entry.val());
+        }
+
+        if entries.next().is_some() {
+            map.finish_non_exhaustive()
+        } else {
+            map.finish()
+        }
+    }
+}
+
+/// An entry of a [MultiMap](MultiMap), one of the values of its key.
+pub struct Entry<'a, K, V>(sync::Entry<'a, Dup<K>, V>);
+
+impl<'a, K, V> Entry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.0.key().key
+    }
+
+    pub fn val(&self) -> &V {
+        self.0.val()
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.0.is_removed()
+    }
+}
+
+impl<'a, K, V> Debug for Entry<'a, K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Entry")
+            .field("key", self.key())
+            .field("val", self.val())
+            .finish()
+    }
+}
+
+/// An iterator over the entries of a [MultiMap](MultiMap).
+pub struct Iter<'a, K, V>(sync::Iter<'a, Dup<K>, V>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    type Item = Entry<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Entry)
+    }
+}
+
+/// An iterator over the values of a single key, see [get_all](MultiMap::get_all).
+pub struct GetAll<'a, K, V>(sync::Range<'a, Dup<K>, V, DupRange<K>>);
+
+impl<'a, K, V> Iterator for GetAll<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    type Item = Entry<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Entry)
+    }
+}
+
+#[cfg(test)]
+mod multi_map_test {
+    use super::*;
+
+    #[test]
+    fn test_multi_map() {
+        let map = MultiMap::new();
+
+        for i in 0..10 {
+            map.insert(i % 3, i);
+        }
+
+        assert_eq!(map.len(), 10);
+        assert!(map.get_all(&1).map(|e| *e.val()).eq([1, 4, 7]));
+        assert!(map.iter().map(|e| *e.key()).is_sorted());
+        assert!(!map.contains_key(&3));
+
+        assert_eq!(*map.remove_one(&1).unwrap().val(), 1);
+        assert!(map.get_all(&1).map(|e| *e.val()).eq([4, 7]));
+
+        assert_eq!(map.remove_all(&0), 4);
+        assert!(map.remove_one(&0).is_none());
+        assert_eq!(map.len(), 5);
+        assert_eq!(format!("{:?}", map), "{1: 4, 1: 7, 2: 2, 2: 5, 2: 8}");
+    }
+
+    #[test]
+    fn test_insert_concurrent() {
+        let map = MultiMap::new();
+
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                let map = &map;
+                s.spawn(move || {
+                    for i in 0..250 {
+                        map.insert(i % 2, t * 250 + i);
+                    }
+                });
+            }
+        });
+
+        // No insert replaced another.
+        assert_eq!(map.len(), 1000);
+        assert_eq!(map.get_all(&0).count(), 500);
+
+        let mut vals = map.iter().map(|e| *e.val()).collect::<Vec<_>>();
+        vals.sort();
+        assert!(vals.into_iter().eq(0..1000));
+    }
+}
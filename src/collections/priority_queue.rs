@@ -1,3 +1,5 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
 use crate::internal::skiplist;
 use crate::internal::skiplist::SkipList;
 use crate::internal::sync;
@@ -10,21 +12,108 @@ pub struct PriorityQueue<L> {
 }
 
 impl<'domain> PriorityQueue<()> {
-    pub fn new<V: Sync>() -> PriorityQueue<SkipList<'domain, V, ()>> {
+    pub fn new<V>() -> PriorityQueue<SkipList<'domain, V, ()>> {
         PriorityQueue {
             queue: SkipList::new(),
         }
     }
-    pub fn new_sync<V: Sync>() -> PriorityQueue<SyncSkipList<'domain, V, ()>> {
+    pub fn new_sync<V>() -> PriorityQueue<SyncSkipList<'domain, V, ()>> {
         PriorityQueue {
             queue: SyncSkipList::new(),
         }
     }
+
+    /// Creates a thread-safe queue of items pushed at a priority, which hands out a
+    /// [Handle](Handle) for every item pushed. Items of equal priority are popped in the order
+    /// they were pushed in.
+    pub fn new_keyed_sync<P, T>() -> PriorityQueue<Keyed<SyncSkipList<'domain, Queued<P>, T>>> {
+        PriorityQueue {
+            queue: Keyed {
+                list: SyncSkipList::new(),
+                seq: AtomicUsize::new(0),
+            },
+        }
+    }
+}
+
+/// The list of a keyed queue, along with the sequence numbers it hands out.
+pub struct Keyed<L> {
+    list: L,
+    seq: AtomicUsize,
+}
+
+/// The key of an item in a keyed queue. The sequence number it was pushed with tells it apart from
+/// other items of the same priority.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Queued<P> {
+    pub priority: P,
+    seq: usize,
+}
+
+/// Cancels the very item it was handed out for, see
+/// [new_keyed_sync](PriorityQueue::new_keyed_sync).
+pub struct Handle<'a, P, T> {
+    list: &'a SyncSkipList<'a, Queued<P>, T>,
+    key: Queued<P>,
+}
+
+impl<'a, P, T> Handle<'a, P, T>
+where
+    P: Ord + Send,
+    T: Send,
+{
+    pub fn priority(&self) -> &P {
+        &self.key.priority
+    }
+
+    /// Removes the item from its queue. Returns its entry, unless it was popped or cancelled
+    /// before.
+    pub fn cancel(self) -> Option<sync::Entry<'a, Queued<P>, T>> {
+        self.list.remove(&self.key)
+    }
+}
+
+/// Prints the queued values in order, eliding all but the first
+/// [DEBUG_ENTRIES](crate::internal::utils::DEBUG_ENTRIES).
+impl<'a, V> core::fmt::Debug for PriorityQueue<SkipList<'a, V, ()>>
+where
+    V: Ord + core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut values = self.queue.iter();
+        let mut list = f.debug_list();
+
+        for entry in values.by_ref().take(crate::internal::utils::DEBUG_ENTRIES) {
+            list.entry(entry.key());
+        }
+
+        if values.next().is_some() {
+            list.finish_non_exhaustive()
+        } else {
+            list.finish()
+        }
+    }
 }
 
-unsafe impl<L> Send for PriorityQueue<L> where L: Send + Sync {}
+impl<'a, V> core::fmt::Debug for PriorityQueue<SyncSkipList<'a, V, ()>>
+where
+    V: Ord + Send + core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut values = self.queue.iter();
+        let mut list = f.debug_list();
+
+        for entry in values.by_ref().take(crate::internal::utils::DEBUG_ENTRIES) {
+            list.entry(entry.key());
+        }
 
-unsafe impl<L> Sync for PriorityQueue<L> where L: Send + Sync {}
+        if values.next().is_some() {
+            list.finish_non_exhaustive()
+        } else {
+            list.finish()
+        }
+    }
+}
 
 impl<'a, V> PriorityQueue<SkipList<'a, V, ()>>
 where
@@ -53,7 +142,7 @@ where
 
 impl<'a, V> PriorityQueue<SyncSkipList<'a, V, ()>>
 where
-    V: Ord + Send + Sync + 'a,
+    V: Ord + Send + 'a,
 {
     pub fn push(&self, value: V) {
         self.queue.insert(value, ());
@@ -69,6 +158,12 @@ where
         first.remove()
     }
 
+    /// Pops the first value once there is one, parking the task while the queue is empty.
+    #[cfg(feature = "async")]
+    pub fn wait_pop(&'a self) -> sync::WaitPopFront<'a, V, ()> {
+        self.queue.wait_pop_front()
+    }
+
     pub fn len(&self) -> usize {
         self.queue.len()
     }
@@ -78,6 +173,49 @@ where
     }
 }
 
+impl<'a, P, T> PriorityQueue<Keyed<SyncSkipList<'a, Queued<P>, T>>>
+where
+    P: Ord + Clone + Send + 'a,
+    T: Send + 'a,
+{
+    /// Queues `item` at `priority`, returning a [Handle](Handle) that cancels this very item.
+    pub fn push(&'a self, priority: P, item: T) -> Handle<'a, P, T> {
+        let key = Queued {
+            priority,
+            seq: self.queue.seq.fetch_add(1, Ordering::Relaxed),
+        };
+
+        self.queue.list.insert(key.clone(), item);
+
+        Handle {
+            list: &self.queue.list,
+            key,
+        }
+    }
+
+    pub fn peek(&'a self) -> Option<sync::Entry<'a, Queued<P>, T>> {
+        self.queue.list.get_first()
+    }
+
+    pub fn pop(&'a self) -> Option<sync::Entry<'a, Queued<P>, T>> {
+        self.queue.list.pop_front()
+    }
+
+    /// Pops the first item once there is one, parking the task while the queue is empty.
+    #[cfg(feature = "async")]
+    pub fn wait_pop(&'a self) -> sync::WaitPopFront<'a, Queued<P>, T> {
+        self.queue.list.wait_pop_front()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.list.is_empty()
+    }
+}
+
 mod iter {
     use super::*;
 
@@ -105,7 +243,7 @@ mod iter {
 
     impl<'a, V> PriorityQueue<SyncSkipList<'a, V, ()>>
     where
-        V: Ord + Send + Sync,
+        V: Ord + Send,
     {
         pub fn iter(&'a self) -> sync::iter::Iter<'a, V, ()> {
             self.queue.iter()
@@ -114,7 +252,7 @@ mod iter {
 
     impl<'a, V> IntoIterator for PriorityQueue<SyncSkipList<'a, V, ()>>
     where
-        V: Ord + Send + Sync,
+        V: Ord + Send,
     {
         type Item = <SyncSkipList<'a, V, ()> as IntoIterator>::Item;
         type IntoIter = <SyncSkipList<'a, V, ()> as IntoIterator>::IntoIter;
@@ -131,6 +269,26 @@ mod pq_test {
 
     use super::*;
 
+    #[test]
+    fn test_debug() {
+        let mut queue = PriorityQueue::new();
+        let sync_queue = PriorityQueue::new_sync();
+
+        for i in (0..3).rev() {
+            queue.push(i);
+            sync_queue.push(i);
+        }
+
+        assert_eq!(format!("{:?}", queue), "[0, 1, 2]");
+        assert_eq!(format!("{:?}", sync_queue), "[0, 1, 2]");
+
+        for i in 3..100 {
+            sync_queue.push(i);
+        }
+
+        assert!(format!("{:?}", sync_queue).ends_with(", 31, ..]"));
+    }
+
     #[test]
     fn test_push() {
         let mut queue = PriorityQueue::new();
@@ -228,4 +386,47 @@ mod pq_test {
 
         assert!(queue.len() > 0);
     }
+
+    #[test]
+    fn test_keyed_cancel() {
+        let queue = PriorityQueue::new_keyed_sync();
+
+        let handles: Vec<_> = (0..6).map(|i| queue.push(i % 2, i)).collect();
+        assert_eq!(queue.len(), 6);
+
+        let mut handles = handles.into_iter();
+        let first = handles.next().unwrap();
+
+        // Cancelling one of several items of equal priority leaves the others queued.
+        assert_eq!(*handles.nth(1).unwrap().cancel().unwrap().val(), 2);
+        assert_eq!(*queue.pop().unwrap().val(), 0);
+
+        // The item was popped already.
+        assert!(first.cancel().is_none());
+
+        assert_eq!(queue.peek().unwrap().key().priority, 0);
+
+        let popped: Vec<_> = core::iter::from_fn(|| queue.pop().map(|e| *e.val())).collect();
+        assert_eq!(popped, [4, 1, 3, 5]);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_wait_pop() {
+        use futures::executor::block_on;
+
+        let queue = PriorityQueue::new_keyed_sync();
+
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                std::thread::sleep(std::time::Duration::from_millis(5));
+                queue.push(1, "task");
+            });
+
+            // The consumer parks until the item is pushed.
+            assert_eq!(*block_on(queue.wait_pop()).val(), "task");
+        });
+
+        assert!(queue.is_empty());
+    }
 }
@@ -0,0 +1,8 @@
+//! A concurrent map with the interface of `crossbeam_skiplist::SkipMap`: `get`, `insert`
+//! returning the new entry, `range`, `front`, `back`, `pop_front` and `pop_back`, among others.
+//! Swapping it in for the crossbeam map, say to benchmark the two against each other, leaves the
+//! call sites as they are.
+//!
+//! This is the map of the [compat](crate::compat) shim, listed among the collections as well.
+
+pub use crate::compat::map::{Entry, Iter, Range, SkipMap};
@@ -0,0 +1,302 @@
+use core::fmt::Debug;
+use core::ops::RangeBounds;
+
+use crate::internal::sync;
+use crate::SyncSkipList;
+
+/// A concurrent ordered set with the semantics of [BTreeSet](std::collections::BTreeSet):
+/// inserting an element that is in the set already keeps the one in the set, and removing tells
+/// whether the element was there. Elements are handed out as [Entries](Entry), which keep them
+/// readable while other threads change the set.
+///
+/// [compat::SkipSet](crate::compat::SkipSet) offers the interface of `crossbeam_skiplist` instead.
+pub struct SkipSet<T> {
+    list: SyncSkipList<'static, T, ()>,
+}
+
+impl<T> SkipSet<T> {
+    pub fn new() -> Self {
+        SkipSet {
+            list: SyncSkipList::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+}
+
+impl<T> SkipSet<T>
+where
+    T: Ord + Send,
+{
+    /// Adds `value` to the set. Returns whether it was not in the set before, in which case the
+    /// element in the set stays and `value` is dropped.
+    pub fn insert(&self, value: T) -> bool {
+        self.list.insert_new(value, ()).is_ok()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.list.get(value).is_some()
+    }
+
+    pub fn get(&self, value: &T) -> Option<Entry<'_, T>> {
+        self.list.get(value).map(Entry)
+    }
+
+    /// Removes `value` from the set. Returns whether this call removed it.
+    pub fn remove(&self, value: &T) -> bool {
+        self.list.remove(value).is_some()
+    }
+
+    /// Removes `value` from the set, returning the entry of the element that was in the set.
+    pub fn take(&self, value: &T) -> Option<Entry<'_, T>> {
+        self.list.remove(value).map(Entry)
+    }
+
+    /// Returns the smallest element.
+    pub fn first(&self) -> Option<Entry<'_, T>> {
+        self.list.get_first().map(Entry)
+    }
+
+    /// Returns the largest element.
+    pub fn last(&self) -> Option<Entry<'_, T>> {
+        self.list.get_last().map(Entry)
+    }
+
+    /// Removes and returns the smallest element. Of all threads popping concurrently, exactly one
+    /// receives it.
+    pub fn pop_first(&self) -> Option<Entry<'_, T>> {
+        self.list.pop_front().map(Entry)
+    }
+
+    /// Removes and returns the largest element.
+    pub fn pop_last(&self) -> Option<Entry<'_, T>> {
+        self.list.pop_back().map(Entry)
+    }
+
+    /// Returns an iterator over the elements, in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.list.iter())
+    }
+
+    /// Returns an iterator over the elements that lie within `range`, in ascending order.
+    pub fn range<R>(&self, range: R) -> Range<'_, T, R>
+    where
+        R: RangeBounds<T>,
+    {
+        Range(self.list.range(range))
+    }
+}
+
+impl<T> Default for SkipSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Prints the elements as a set, eliding all but the first
+/// [DEBUG_ENTRIES](crate::internal::utils::DEBUG_ENTRIES).
+impl<T> Debug for SkipSet<T>
+where
+    T: Ord + Send + Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut set = f.debug_set();
+        let mut entries = self.iter();
+
+        for entry in entries.by_ref().take(crate::internal::utils::DEBUG_ENTRIES) {
+            set.entry(entry.value());
+        }
+
+        if entries.next().is_some() {
+            set.finish_non_exhaustive()
+        } else {
+            set.finish()
+        }
+    }
+}
+
+/// Keeps the first of equal elements, as inserting them one by one would.
+impl<T> FromIterator<T> for SkipSet<T>
+where
+    T: Ord + Send,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = SkipSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T> Extend<T> for SkipSet<T>
+where
+    T: Ord + Send,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        (&*self).extend(iter)
+    }
+}
+
+/// Extends a set that is shared with other threads.
+impl<T> Extend<T> for &SkipSet<T>
+where
+    T: Ord + Send,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T> IntoIterator for SkipSet<T>
+where
+    T: Ord + Send,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self.list.into_iter())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SkipSet<T>
+where
+    T: Ord + Send,
+{
+    type Item = Entry<'a, T>;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An element of a [SkipSet](SkipSet), which stays readable while it is held, even if it is
+/// removed in the meantime.
+pub struct Entry<'a, T>(sync::Entry<'a, T, ()>);
+
+impl<'a, T> Entry<'a, T> {
+    pub fn value(&self) -> &T {
+        self.0.key()
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.0.is_removed()
+    }
+}
+
+impl<'a, T> Debug for Entry<'a, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Entry").field(self.value()).finish()
+    }
+}
+
+/// An iterator over the elements of a [SkipSet](SkipSet).
+pub struct Iter<'a, T>(sync::Iter<'a, T, ()>);
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: Ord + Send,
+{
+    type Item = Entry<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Entry)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T>
+where
+    T: Ord + Send,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(Entry)
+    }
+}
+
+/// An iterator over the elements of a [SkipSet](SkipSet) within a range, see
+/// [range](SkipSet::range).
+pub struct Range<'a, T, R>(sync::Range<'a, T, (), R>);
+
+impl<'a, T, R> Iterator for Range<'a, T, R>
+where
+    T: Ord + Send,
+    R: RangeBounds<T>,
+{
+    type Item = Entry<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Entry)
+    }
+}
+
+/// An iterator moving the elements out of a [SkipSet](SkipSet), in ascending order.
+pub struct IntoIter<T>(sync::IntoIter<T, ()>);
+
+impl<T> Iterator for IntoIter<T>
+where
+    T: Ord + Send,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(value, ())| value)
+    }
+}
+
+#[cfg(test)]
+mod skip_set_test {
+    use super::*;
+
+    #[test]
+    fn test_skip_set() {
+        let set = [5, 1, 3, 1].into_iter().collect::<SkipSet<_>>();
+
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&3));
+        assert!(set.insert(4));
+        assert!(!set.insert(4));
+        assert!(set.iter().map(|e| *e.value()).eq([1, 3, 4, 5]));
+        assert!(set.iter().rev().map(|e| *e.value()).eq([5, 4, 3, 1]));
+        assert!(set.range(2..5).map(|e| *e.value()).eq([3, 4]));
+
+        assert_eq!(*set.pop_first().unwrap().value(), 1);
+        assert_eq!(*set.pop_last().unwrap().value(), 5);
+        assert!(set.remove(&4));
+        assert!(!set.remove(&4));
+        assert_eq!(format!("{:?}", set), "{3}");
+
+        (&set).extend([7, 3]);
+        assert!(set.into_iter().eq([3, 7]));
+    }
+
+    #[test]
+    fn test_insert_concurrent() {
+        let set = SkipSet::new();
+
+        // Every element is reported as new exactly once.
+        let inserted = std::thread::scope(|s| {
+            let threads = (0..4)
+                .map(|_| s.spawn(|| (0..100).filter(|&i| set.insert(i)).count()))
+                .collect::<Vec<_>>();
+
+            threads
+                .into_iter()
+                .map(|t| t.join().unwrap())
+                .sum::<usize>()
+        });
+
+        assert_eq!(inserted, 100);
+        assert_eq!(set.len(), 100);
+    }
+}
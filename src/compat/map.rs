@@ -0,0 +1,393 @@
+//! A map mirroring `crossbeam_skiplist::SkipMap`.
+
+use core::borrow::Borrow;
+use core::fmt::Debug;
+use core::ops::{Bound, RangeBounds};
+
+use crate::internal::sync;
+use crate::SyncSkipList;
+
+/// A concurrent map with the interface of `crossbeam_skiplist::SkipMap`.
+pub struct SkipMap<K, V> {
+    inner: SyncSkipList<'static, K, V>,
+}
+
+impl<K, V> SkipMap<K, V> {
+    pub fn new() -> Self {
+        SkipMap {
+            inner: SyncSkipList::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<K, V> SkipMap<K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Returns the entry with the smallest key.
+    pub fn front(&self) -> Option<Entry<'_, K, V>> {
+        self.wrap(self.inner.get_first())
+    }
+
+    /// Returns the entry with the largest key.
+    pub fn back(&self) -> Option<Entry<'_, K, V>> {
+        self.wrap(self.inner.get_last())
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.inner.get(key).is_some()
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<Entry<'_, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.wrap(self.inner.get(key))
+    }
+
+    /// Returns the first entry whose key lies above `bound`.
+    pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> Option<Entry<'_, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.wrap(self.inner.lower_bound(bound))
+    }
+
+    /// Returns the last entry whose key lies below `bound`.
+    pub fn upper_bound<Q>(&self, bound: Bound<&Q>) -> Option<Entry<'_, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.wrap(self.inner.upper_bound(bound))
+    }
+
+    /// Returns the entry of `key`, inserting `value` first if there is none.
+    pub fn get_or_insert(&self, key: K, value: V) -> Entry<'_, K, V> {
+        Entry {
+            map: self,
+            inner: self.inner.get_or_insert(key, value),
+        }
+    }
+
+    /// Returns the entry of `key`, inserting the value `f` returns first if there is none. `f` is
+    /// only called if the key is absent.
+    pub fn get_or_insert_with<F>(&self, key: K, f: F) -> Entry<'_, K, V>
+    where
+        F: FnOnce() -> V,
+    {
+        Entry {
+            map: self,
+            inner: self.inner.get_or_insert_with(key, f),
+        }
+    }
+
+    /// Inserts `value` under `key`, replacing any previous value, and returns the new entry.
+    pub fn insert(&self, key: K, value: V) -> Entry<'_, K, V> {
+        Entry {
+            map: self,
+            inner: self.inner.insert_entry(key, value).0,
+        }
+    }
+
+    /// Removes `key` and returns its entry, if it was in the map.
+    pub fn remove<Q>(&self, key: &Q) -> Option<Entry<'_, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.wrap(self.inner.remove(key))
+    }
+
+    /// Removes and returns the entry with the smallest key.
+    pub fn pop_front(&self) -> Option<Entry<'_, K, V>> {
+        self.wrap(self.inner.pop_front())
+    }
+
+    /// Removes and returns the entry with the largest key.
+    pub fn pop_back(&self) -> Option<Entry<'_, K, V>> {
+        self.wrap(self.inner.pop_back())
+    }
+
+    /// Removes every entry of the map.
+    pub fn clear(&self) {
+        while self.pop_front().is_some() {}
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            map: self,
+            inner: self.inner.iter(),
+        }
+    }
+
+    /// Returns an iterator over the entries whose keys lie within `range`.
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V, R, Q>
+    where
+        K: Borrow<Q>,
+        R: RangeBounds<Q>,
+        Q: Ord + ?Sized,
+    {
+        Range {
+            map: self,
+            inner: self.inner.range(range),
+        }
+    }
+
+    fn wrap<'a>(&'a self, inner: Option<sync::Entry<'a, K, V>>) -> Option<Entry<'a, K, V>> {
+        inner.map(|inner| Entry { map: self, inner })
+    }
+}
+
+impl<K, V> Default for SkipMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Debug for SkipMap<K, V>
+where
+    K: Ord + Send + Debug,
+    V: Send + Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut map = f.debug_map();
+
+        for entry in self.iter() {
+            map.entry(entry.key(), entry.value());
+        }
+
+        map.finish()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for SkipMap<K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        SkipMap {
+            inner: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<K, V> IntoIterator for SkipMap<K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    type Item = (K, V);
+    type IntoIter = sync::IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SkipMap<K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    type Item = Entry<'a, K, V>;
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A reference to an entry of a [SkipMap](SkipMap).
+pub struct Entry<'a, K, V> {
+    map: &'a SkipMap<K, V>,
+    inner: sync::Entry<'a, K, V>,
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    pub fn key(&self) -> &K {
+        self.inner.key()
+    }
+
+    pub fn value(&self) -> &V {
+        self.inner.val()
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.inner.is_removed()
+    }
+
+    /// Removes the entry's key from the map. Returns `true` if this call removed it.
+    pub fn remove(&self) -> bool {
+        !self.is_removed() && self.map.inner.remove(self.key()).is_some()
+    }
+
+    /// Returns the entry following this one.
+    pub fn next(&self) -> Option<Entry<'a, K, V>> {
+        self.map.wrap(self.map.inner.next_node(&self.inner))
+    }
+
+    /// Returns the entry preceding this one.
+    pub fn prev(&self) -> Option<Entry<'a, K, V>> {
+        self.map.upper_bound(Bound::Excluded(self.key()))
+    }
+}
+
+impl<'a, K, V> Debug for Entry<'a, K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Entry")
+            .field(&self.inner.key())
+            .field(&self.inner.val())
+            .finish()
+    }
+}
+
+/// An iterator over the entries of a [SkipMap](SkipMap).
+pub struct Iter<'a, K, V> {
+    map: &'a SkipMap<K, V>,
+    inner: sync::Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    type Item = Entry<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.map.wrap(self.inner.next())
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.map.wrap(self.inner.next_back())
+    }
+}
+
+/// An iterator over a range of the entries of a [SkipMap](SkipMap).
+pub struct Range<'a, K, V, R, Q: ?Sized = K> {
+    map: &'a SkipMap<K, V>,
+    inner: sync::Range<'a, K, V, R, Q>,
+}
+
+impl<'a, K, V, R, Q> Iterator for Range<'a, K, V, R, Q>
+where
+    K: Ord + Send + Borrow<Q>,
+    V: Send,
+    R: RangeBounds<Q>,
+    Q: Ord + ?Sized,
+{
+    type Item = Entry<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.map.wrap(self.inner.next())
+    }
+}
+
+#[cfg(test)]
+mod map_test {
+    use super::*;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let map = SkipMap::new();
+
+        assert_eq!(*map.insert(1, "a").value(), "a");
+        assert_eq!(*map.insert(1, "b").value(), "b");
+        assert_eq!(*map.get_or_insert(1, "c").value(), "b");
+        assert_eq!(*map.get_or_insert_with(1, || unreachable!()).value(), "b");
+        assert_eq!(map.len(), 1);
+
+        let entry = map.get(&1).unwrap();
+        assert!(entry.remove());
+        assert!(entry.is_removed());
+        assert!(!entry.remove());
+        assert!(!map.contains_key(&1));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_borrowed_keys() {
+        let map = ["a", "b", "c", "d"]
+            .map(|k| (k.to_string(), ()))
+            .into_iter()
+            .collect::<SkipMap<_, _>>();
+
+        assert_eq!(map.get("b").unwrap().key(), "b");
+        assert!(map.contains_key("c"));
+        assert_eq!(map.lower_bound(Bound::Excluded("b")).unwrap().key(), "c");
+        assert_eq!(map.upper_bound(Bound::Excluded("b")).unwrap().key(), "a");
+        let range = (Bound::Included("b"), Bound::Excluded("d"));
+        assert!(map.range::<str, _>(range).map(|e| e.key().clone()).eq(["b", "c"]));
+
+        assert!(map.remove("a").is_some());
+        assert!(!map.contains_key("a"));
+    }
+
+    #[test]
+    fn test_range_and_bounds() {
+        let map = (0..20).map(|i| (i * 2, i)).collect::<SkipMap<_, _>>();
+
+        assert!(map.range(5..=11).map(|e| *e.key()).eq([6, 8, 10]));
+        assert!(map.range(..4).map(|e| *e.key()).eq([0, 2]));
+        assert!(map.range(36..).map(|e| *e.key()).eq([36, 38]));
+        assert!(map.iter().rev().take(2).map(|e| *e.key()).eq([38, 36]));
+
+        assert_eq!(*map.lower_bound(Bound::Included(&4)).unwrap().key(), 4);
+        assert_eq!(*map.lower_bound(Bound::Excluded(&4)).unwrap().key(), 6);
+        assert_eq!(*map.upper_bound(Bound::Included(&5)).unwrap().key(), 4);
+        assert_eq!(*map.upper_bound(Bound::Excluded(&4)).unwrap().key(), 2);
+        assert!(map.upper_bound(Bound::Excluded(&0)).is_none());
+        assert!(map.lower_bound(Bound::Excluded(&38)).is_none());
+
+        let entry = map.get(&10).unwrap();
+        assert_eq!(*entry.next().unwrap().key(), 12);
+        assert_eq!(*entry.prev().unwrap().key(), 8);
+    }
+
+    #[test]
+    fn test_pop() {
+        let map = (0..10).map(|i| (i, ())).collect::<SkipMap<_, _>>();
+
+        assert_eq!(*map.pop_front().unwrap().key(), 0);
+        assert_eq!(*map.pop_back().unwrap().key(), 9);
+        assert_eq!(*map.front().unwrap().key(), 1);
+        assert_eq!(*map.back().unwrap().key(), 8);
+        assert_eq!(map.len(), 8);
+
+        map.clear();
+
+        assert!(map.is_empty());
+        assert!(map.pop_front().is_none());
+    }
+}
@@ -0,0 +1,13 @@
+//! A drop-in shim for the API of [crossbeam-skiplist](https://docs.rs/crossbeam-skiplist).
+//!
+//! [SkipMap](SkipMap) and [SkipSet](SkipSet) mirror the method names and signatures of their
+//! `crossbeam_skiplist` counterparts, so switching between the two crates is a matter of changing
+//! an import. Both are thin wrappers around the [SyncSkipList](crate::SyncSkipList).
+//!
+//! Unlike `crossbeam_skiplist`, lookups take a `&K` rather than any borrowed form of the key.
+
+pub mod map;
+pub mod set;
+
+pub use map::SkipMap;
+pub use set::SkipSet;
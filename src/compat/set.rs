@@ -0,0 +1,241 @@
+//! A set mirroring `crossbeam_skiplist::SkipSet`.
+
+use core::fmt::Debug;
+use core::ops::{Bound, RangeBounds};
+
+use super::map::{self, SkipMap};
+
+/// A concurrent set with the interface of `crossbeam_skiplist::SkipSet`.
+pub struct SkipSet<T> {
+    inner: SkipMap<T, ()>,
+}
+
+impl<T> SkipSet<T> {
+    pub fn new() -> Self {
+        SkipSet {
+            inner: SkipMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<T> SkipSet<T>
+where
+    T: Ord + Send,
+{
+    /// Returns the smallest element.
+    pub fn front(&self) -> Option<Entry<'_, T>> {
+        self.inner.front().map(Entry)
+    }
+
+    /// Returns the largest element.
+    pub fn back(&self) -> Option<Entry<'_, T>> {
+        self.inner.back().map(Entry)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.inner.contains_key(value)
+    }
+
+    pub fn get(&self, value: &T) -> Option<Entry<'_, T>> {
+        self.inner.get(value).map(Entry)
+    }
+
+    /// Returns the first element that lies above `bound`.
+    pub fn lower_bound(&self, bound: Bound<&T>) -> Option<Entry<'_, T>> {
+        self.inner.lower_bound(bound).map(Entry)
+    }
+
+    /// Returns the last element that lies below `bound`.
+    pub fn upper_bound(&self, bound: Bound<&T>) -> Option<Entry<'_, T>> {
+        self.inner.upper_bound(bound).map(Entry)
+    }
+
+    /// Returns the entry of `value`, inserting it first if it is not in the set.
+    pub fn get_or_insert(&self, value: T) -> Entry<'_, T> {
+        Entry(self.inner.get_or_insert(value, ()))
+    }
+
+    /// Inserts `value`, replacing an equal element, and returns its entry.
+    pub fn insert(&self, value: T) -> Entry<'_, T> {
+        Entry(self.inner.insert(value, ()))
+    }
+
+    /// Removes `value` and returns its entry, if it was in the set.
+    pub fn remove(&self, value: &T) -> Option<Entry<'_, T>> {
+        self.inner.remove(value).map(Entry)
+    }
+
+    /// Removes and returns the smallest element.
+    pub fn pop_front(&self) -> Option<Entry<'_, T>> {
+        self.inner.pop_front().map(Entry)
+    }
+
+    /// Removes and returns the largest element.
+    pub fn pop_back(&self) -> Option<Entry<'_, T>> {
+        self.inner.pop_back().map(Entry)
+    }
+
+    /// Removes every element of the set.
+    pub fn clear(&self) {
+        self.inner.clear()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.inner.iter())
+    }
+
+    /// Returns an iterator over the elements that lie within `range`.
+    pub fn range<R>(&self, range: R) -> Range<'_, T, R>
+    where
+        R: RangeBounds<T>,
+    {
+        Range(self.inner.range(range))
+    }
+}
+
+impl<T> Default for SkipSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for SkipSet<T>
+where
+    T: Ord + Send + Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T> FromIterator<T> for SkipSet<T>
+where
+    T: Ord + Send,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        SkipSet {
+            inner: iter.into_iter().map(|value| (value, ())).collect(),
+        }
+    }
+}
+
+impl<T> IntoIterator for SkipSet<T>
+where
+    T: Ord + Send,
+{
+    type Item = T;
+    type IntoIter = core::iter::Map<<SkipMap<T, ()> as IntoIterator>::IntoIter, fn((T, ())) -> T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter().map(|(value, ())| value)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SkipSet<T>
+where
+    T: Ord + Send,
+{
+    type Item = Entry<'a, T>;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A reference to an element of a [SkipSet](SkipSet).
+pub struct Entry<'a, T>(map::Entry<'a, T, ()>);
+
+impl<'a, T> Entry<'a, T>
+where
+    T: Ord + Send,
+{
+    pub fn value(&self) -> &T {
+        self.0.key()
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.0.is_removed()
+    }
+
+    /// Removes the element from the set. Returns `true` if this call removed it.
+    pub fn remove(&self) -> bool {
+        self.0.remove()
+    }
+
+    /// Returns the element following this one.
+    pub fn next(&self) -> Option<Entry<'a, T>> {
+        self.0.next().map(Entry)
+    }
+
+    /// Returns the element preceding this one.
+    pub fn prev(&self) -> Option<Entry<'a, T>> {
+        self.0.prev().map(Entry)
+    }
+}
+
+impl<'a, T> Debug for Entry<'a, T>
+where
+    T: Ord + Send + Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Entry").field(self.value()).finish()
+    }
+}
+
+/// An iterator over the elements of a [SkipSet](SkipSet).
+pub struct Iter<'a, T>(map::Iter<'a, T, ()>);
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: Ord + Send,
+{
+    type Item = Entry<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Entry)
+    }
+}
+
+/// An iterator over a range of the elements of a [SkipSet](SkipSet).
+pub struct Range<'a, T, R>(map::Range<'a, T, (), R>);
+
+impl<'a, T, R> Iterator for Range<'a, T, R>
+where
+    T: Ord + Send,
+    R: RangeBounds<T>,
+{
+    type Item = Entry<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Entry)
+    }
+}
+
+#[cfg(test)]
+mod set_test {
+    use super::*;
+
+    #[test]
+    fn test_set() {
+        let set = [5, 1, 3].into_iter().collect::<SkipSet<_>>();
+
+        assert!(set.contains(&3));
+        assert_eq!(*set.insert(4).value(), 4);
+        assert!(set.iter().map(|e| *e.value()).eq([1, 3, 4, 5]));
+        assert!(set.range(2..5).map(|e| *e.value()).eq([3, 4]));
+
+        assert_eq!(*set.pop_front().unwrap().value(), 1);
+        assert!(set.remove(&4).is_some());
+        assert!(set.remove(&4).is_none());
+        assert!(set.into_iter().eq([3, 5]));
+    }
+}
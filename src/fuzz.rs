@@ -0,0 +1,35 @@
+//! Structured fuzzing support.
+//!
+//! Instead of reinterpreting raw bytes as keys, fuzz targets can let [arbitrary](arbitrary)
+//! generate a sequence of [Op](Op)s and hand them to [run](run).
+
+use std::fmt::Debug;
+
+pub use crate::model::{apply, Op};
+
+/// Applies `ops` to a fresh list and checks every result against a `BTreeMap`, see
+/// [check_against_btreemap](crate::model::check_against_btreemap).
+pub fn run<K, V>(ops: impl IntoIterator<Item = Op<K, V>>)
+where
+    K: Ord + Clone + Debug + Send + Sync,
+    V: Clone + PartialEq + Debug + Send + Sync,
+{
+    crate::model::check_against_btreemap(ops)
+}
+
+#[cfg(test)]
+mod fuzz_test {
+    use super::*;
+
+    #[test]
+    fn test_run_arbitrary() {
+        let bytes = (0..4096u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect::<Vec<_>>();
+        let mut u = arbitrary::Unstructured::new(&bytes);
+
+        let ops: Vec<Op<u8, u16>> = u.arbitrary().unwrap();
+
+        run(ops);
+    }
+}
@@ -0,0 +1,132 @@
+//! A randomized concurrency test harness built on [shuttle](shuttle).
+//!
+//! Shuttle runs the threads of a test under schedules it picks itself, switching between them at
+//! every atomic access of the list. This explores far more interleavings than running the same test
+//! on OS threads would. Every operation is checked against a `Mutex<BTreeMap>` model.
+//!
+//! Compiling with `--cfg shuttle` swaps the atomics of the lists for shuttle's, so the lists can
+//! then only be used from within a shuttle test. Like loom, it is a cfg rather than a feature, as
+//! it would otherwise break every other user of the crate built alongside it. Forks can run the
+//! harness against their own changes with `RUSTFLAGS="--cfg shuttle" cargo test harness` and, for
+//! example:
+//!
+//! ```ignore
+//! #[test]
+//! fn shuttle_list() {
+//!     skippy_rs::harness::check_random(skippy_rs::harness::Config::default());
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use shuttle::{
+    rand::{thread_rng, Rng},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use crate::SyncSkipList;
+
+/// The shape of the workload every shuttle execution runs.
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// The number of threads operating on the list at the same time.
+    pub threads: usize,
+    /// The number of operations every thread performs.
+    pub ops_per_thread: usize,
+    /// Keys are drawn from `0..key_range`.
+    pub key_range: usize,
+    /// The number of executions to explore.
+    pub iterations: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            threads: 3,
+            ops_per_thread: 8,
+            key_range: 12,
+            iterations: 1_000,
+        }
+    }
+}
+
+/// Explores `config.iterations` uniformly random schedules.
+pub fn check_random(config: Config) {
+    shuttle::check_random(move || run(config), config.iterations);
+}
+
+/// Explores `config.iterations` schedules with the PCT algorithm, which is more likely to find
+/// bugs that need up to `depth` well placed preemptions.
+pub fn check_pct(config: Config, depth: usize) {
+    shuttle::check_pct(move || run(config), config.iterations, depth);
+}
+
+/// A single execution. Every thread owns the keys congruent to its index, so the result of each
+/// of its operations is known exactly, while the towers of the threads still interleave.
+fn run(config: Config) {
+    let list = Arc::new(SyncSkipList::new());
+    let model = Arc::new(Mutex::new(BTreeMap::new()));
+
+    let handles = (0..config.threads)
+        .map(|id| {
+            let (list, model) = (list.clone(), model.clone());
+
+            thread::spawn(move || {
+                let mut rng = thread_rng();
+
+                for op in 0..config.ops_per_thread {
+                    let key =
+                        rng.gen_range(0..config.key_range) / config.threads * config.threads + id;
+                    let val = id * config.ops_per_thread + op;
+
+                    match rng.gen_range(0..3) {
+                        0 => {
+                            let old = list.insert(key, val).map(|e| *e.val());
+                            assert_eq!(old, model.lock().unwrap().insert(key, val));
+                        }
+                        1 => {
+                            let old = list.remove(&key).map(|e| *e.val());
+                            assert_eq!(old, model.lock().unwrap().remove(&key));
+                        }
+                        _ => {
+                            let found = list.get(&key).map(|e| *e.val());
+                            assert_eq!(found, model.lock().unwrap().get(&key).copied());
+                        }
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    handles.into_iter().for_each(|h| h.join().unwrap());
+
+    let model = model.lock().unwrap();
+
+    assert_eq!(list.len(), model.len());
+    assert!(list
+        .iter()
+        .map(|e| (*e.key(), *e.val()))
+        .eq(model.iter().map(|(k, v)| (*k, *v))));
+}
+
+#[cfg(test)]
+mod harness_test {
+    use super::*;
+
+    #[test]
+    fn test_check_random() {
+        check_random(Config::default());
+    }
+
+    #[test]
+    fn test_check_pct() {
+        check_pct(
+            Config {
+                iterations: 200,
+                ..Config::default()
+            },
+            3,
+        );
+    }
+}
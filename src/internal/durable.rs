@@ -0,0 +1,418 @@
+//! A concurrent list whose modifications survive restarts.
+//!
+//! Every insert and remove is appended to a write-ahead log before it is applied to the list. A
+//! checkpoint writes a snapshot of the list and empties the log, so recovery only has to replay
+//! what happened since. A directory holds the two files:
+//!
+//! ```text
+//! snapshot    the list as of the last checkpoint, see `SyncSkipList::write_to`
+//! wal         records of `tag: u8` (0 = insert, 1 = remove), `len: u32` and `crc: u32`, followed
+//!             by `len` bytes of the bincode encoded key and, for inserts, value, whose CRC-32 is
+//!             `crc`. Both numbers are little endian.
+//! ```
+//!
+//! A record is encoded in full before it is written in one go, and a write that fails is cut off
+//! the log again, so records never interleave. Replaying stops at the first record whose length or
+//! checksum does not hold, which is where a crash in the middle of a write leaves the log.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::snapshot::into_io;
+use super::sync::{Entry, Iter, SkipList as SyncSkipList};
+
+const SNAPSHOT: &str = "snapshot";
+const WAL: &str = "wal";
+
+const INSERT: u8 = 0;
+const REMOVE: u8 = 1;
+
+/// The bytes of a record in front of its payload: the tag, the length and the checksum.
+const HEADER: usize = 9;
+
+/// The number of logged operations after which a checkpoint is taken by default.
+pub const CHECKPOINT_EVERY: usize = 1 << 16;
+
+struct Wal {
+    /// Every record is written in a single call, so there is nothing to buffer.
+    file: File,
+    /// The length of the log up to the end of the last record written in full.
+    len: u64,
+    since_checkpoint: usize,
+}
+
+/// The CRC-32 (IEEE) of `bytes`, computed bit by bit, as records are small.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+
+    for &byte in bytes {
+        crc ^= byte as u32;
+
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+
+    !crc
+}
+
+/// Syncs the directory `dir`, so that a rename within it is on disk.
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    // Other platforms can neither open nor sync a directory.
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    #[cfg(not(unix))]
+    let _ = dir;
+
+    Ok(())
+}
+
+/// A [SyncSkipList](crate::SyncSkipList) backed by a write-ahead log.
+///
+/// Reads are as lock free as on the plain list. Writes are serialized through the log, so that
+/// replaying it applies them in the same order. Logged records are flushed to the OS right away,
+/// but only forced to disk by [sync](DurableSkipList::sync) and by checkpoints.
+pub struct DurableSkipList<K, V> {
+    list: SyncSkipList<'static, K, V>,
+    wal: Mutex<Wal>,
+    dir: PathBuf,
+    checkpoint_every: usize,
+}
+
+impl<K, V> DurableSkipList<K, V>
+where
+    K: Ord + Send + Sync + Serialize + DeserializeOwned,
+    V: Send + Sync + Serialize + DeserializeOwned,
+{
+    /// Opens the list stored in the directory `path`, creating it if it does not exist yet. The
+    /// last snapshot is loaded and the log replayed on top of it. A record torn by a crash at the
+    /// end of the log is discarded.
+    pub fn recover(path: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = path.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let list = match File::open(dir.join(SNAPSHOT)) {
+            Ok(file) => SyncSkipList::read_from(BufReader::new(file))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => SyncSkipList::new(),
+            Err(err) => return Err(err),
+        };
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(dir.join(WAL))?;
+
+        let mut log = Vec::new();
+        file.read_to_end(&mut log)?;
+
+        let (valid, replayed) = Self::replay(&list, &log)?;
+
+        if valid < log.len() {
+            file.set_len(valid as u64)?;
+        }
+
+        Ok(DurableSkipList {
+            list,
+            wal: Mutex::new(Wal {
+                file,
+                len: valid as u64,
+                since_checkpoint: replayed,
+            }),
+            dir,
+            checkpoint_every: CHECKPOINT_EVERY,
+        })
+    }
+
+    /// Sets the number of logged operations after which a checkpoint is taken.
+    pub fn checkpoint_every(mut self, ops: usize) -> Self {
+        self.checkpoint_every = ops.max(1);
+        self
+    }
+
+    /// Applies the records of `log` to `list`. Returns the length of the valid prefix of the log
+    /// and the number of records in it.
+    fn replay(list: &SyncSkipList<'static, K, V>, log: &[u8]) -> io::Result<(usize, usize)> {
+        let mut rest = log;
+        let mut replayed = 0;
+
+        loop {
+            let valid = log.len() - rest.len();
+
+            let Some((header, tail)) = rest.split_first_chunk::<HEADER>() else {
+                return Ok((valid, replayed));
+            };
+
+            let [tag, l0, l1, l2, l3, c0, c1, c2, c3] = *header;
+            let len = u32::from_le_bytes([l0, l1, l2, l3]) as usize;
+
+            let Some(record) = tail.get(..len) else {
+                return Ok((valid, replayed));
+            };
+
+            if crc32(record) != u32::from_le_bytes([c0, c1, c2, c3]) {
+                return Ok((valid, replayed));
+            }
+
+            // A record that was written in full and still fails to decode is not torn, it belongs
+            // to a different format.
+            let applied = match tag {
+                INSERT => bincode::deserialize(record).map(|(key, val)| {
+                    list.insert(key, val);
+                }),
+                REMOVE => bincode::deserialize(record).map(|key: K| {
+                    list.remove(&key);
+                }),
+                _ => return Ok((valid, replayed)),
+            };
+
+            applied.map_err(|e| into_io(*e))?;
+
+            rest = &tail[len..];
+            replayed += 1;
+        }
+    }
+
+    /// Writes a record to the log. The log stays locked until the returned guard is dropped, so
+    /// the caller can apply the record before anyone else logs theirs. Checkpoints are taken
+    /// before logging, so that a failing one leaves the operation undone.
+    fn log(&self, tag: u8, record: &impl Serialize) -> io::Result<MutexGuard<'_, Wal>> {
+        let mut wal = self.wal.lock().unwrap();
+
+        if wal.since_checkpoint >= self.checkpoint_every {
+            self.checkpoint_locked(&mut wal)?;
+        }
+
+        let payload = bincode::serialize(record).map_err(|e| into_io(*e))?;
+        let len = u32::try_from(payload.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "record too large"))?;
+
+        let mut bytes = Vec::with_capacity(HEADER + payload.len());
+        bytes.push(tag);
+        bytes.extend_from_slice(&len.to_le_bytes());
+        bytes.extend_from_slice(&crc32(&payload).to_le_bytes());
+        bytes.extend_from_slice(&payload);
+
+        if let Err(err) = wal.file.write_all(&bytes) {
+            // Whatever made it into the log of the record must not precede the next one.
+            wal.file.set_len(wal.len)?;
+            return Err(err);
+        }
+
+        wal.len += bytes.len() as u64;
+        wal.since_checkpoint += 1;
+
+        Ok(wal)
+    }
+
+    pub fn insert(&self, key: K, val: V) -> io::Result<Option<Entry<'_, K, V>>> {
+        let _wal = self.log(INSERT, &(&key, &val))?;
+
+        Ok(self.list.insert(key, val))
+    }
+
+    pub fn remove(&self, key: &K) -> io::Result<Option<Entry<'_, K, V>>> {
+        let _wal = self.log(REMOVE, key)?;
+
+        Ok(self.list.remove(key))
+    }
+
+    pub fn get(&self, key: &K) -> Option<Entry<'_, K, V>> {
+        self.list.get(key)
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.list.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Forces all logged records to disk.
+    pub fn sync(&self) -> io::Result<()> {
+        self.wal.lock().unwrap().file.sync_data()
+    }
+
+    /// Writes a snapshot of the list and empties the log.
+    pub fn checkpoint(&self) -> io::Result<()> {
+        self.checkpoint_locked(&mut self.wal.lock().unwrap())
+    }
+
+    fn checkpoint_locked(&self, wal: &mut Wal) -> io::Result<()> {
+        let tmp = self.dir.join(format!("{}.tmp", SNAPSHOT));
+
+        let mut file = BufWriter::new(File::create(&tmp)?);
+        self.list.write_to(&mut file)?;
+        file.into_inner()?.sync_all()?;
+
+        // Once the rename is through, the snapshot covers everything in the log. It has to be on
+        // disk before the log is emptied, or a crash could keep the empty log and lose the rename.
+        fs::rename(&tmp, self.dir.join(SNAPSHOT))?;
+        sync_dir(&self.dir)?;
+
+        wal.file.set_len(0)?;
+        wal.file.sync_all()?;
+        wal.len = 0;
+        wal.since_checkpoint = 0;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod durable_test {
+    use super::*;
+
+    fn dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("skippy-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_recover() {
+        let dir = dir("recover");
+
+        {
+            let list = DurableSkipList::recover(&dir).unwrap();
+
+            for i in 0..100u32 {
+                list.insert(i, i.to_string()).unwrap();
+            }
+
+            for i in (0..100).step_by(3) {
+                list.remove(&i).unwrap();
+            }
+        }
+
+        let list = DurableSkipList::<u32, String>::recover(&dir).unwrap();
+
+        assert_eq!(list.len(), 66);
+        assert_eq!(list.get(&4).map(|e| e.val().clone()), Some("4".to_string()));
+        assert!(list.get(&3).is_none());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let dir = dir("checkpoint");
+
+        {
+            let list = DurableSkipList::recover(&dir).unwrap().checkpoint_every(10);
+
+            for i in 0..25u32 {
+                list.insert(i, ()).unwrap();
+            }
+
+            // Checkpoints were taken before the 11th and 21st insert, so only the last five are
+            // still logged.
+            let logged = fs::metadata(dir.join(WAL)).unwrap().len();
+            assert_eq!(logged, 5 * (HEADER as u64 + 4));
+        }
+
+        let list = DurableSkipList::<u32, ()>::recover(&dir).unwrap();
+
+        assert!(list.iter().map(|e| *e.key()).eq(0..25));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_torn_record() {
+        let dir = dir("torn");
+
+        {
+            let list = DurableSkipList::recover(&dir).unwrap();
+
+            for i in 0..10u64 {
+                list.insert(i, i).unwrap();
+            }
+        }
+
+        // Cut the last record in half, as a crash in the middle of a write would.
+        let wal = OpenOptions::new().write(true).open(dir.join(WAL)).unwrap();
+        let len = wal.metadata().unwrap().len();
+        wal.set_len(len - 4).unwrap();
+
+        {
+            let list = DurableSkipList::<u64, u64>::recover(&dir).unwrap();
+
+            assert!(list.iter().map(|e| *e.key()).eq(0..9));
+
+            list.insert(42, 42).unwrap();
+        }
+
+        let list = DurableSkipList::<u64, u64>::recover(&dir).unwrap();
+
+        assert!(list.iter().map(|e| *e.key()).eq((0..9).chain([42])));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_record() {
+        let dir = dir("corrupt");
+
+        {
+            let list = DurableSkipList::recover(&dir).unwrap();
+
+            for i in 0..10u64 {
+                list.insert(i, i).unwrap();
+            }
+        }
+
+        // Flip a bit in the value of the last record, which still has its full length.
+        let mut log = fs::read(dir.join(WAL)).unwrap();
+        *log.last_mut().unwrap() ^= 1;
+        fs::write(dir.join(WAL), log).unwrap();
+
+        let list = DurableSkipList::<u64, u64>::recover(&dir).unwrap();
+
+        assert!(list.iter().map(|e| *e.key()).eq(0..9));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    /// Fails to serialize after writing part of itself.
+    struct Failing;
+
+    impl Serialize for Failing {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::{Error, SerializeTuple};
+
+            let mut tuple = serializer.serialize_tuple(2)?;
+            tuple.serialize_element(&u64::MAX)?;
+
+            Err(S::Error::custom("failed halfway"))
+        }
+    }
+
+    #[test]
+    fn test_failed_record() {
+        let dir = dir("failed");
+
+        {
+            let list = DurableSkipList::<u64, u64>::recover(&dir).unwrap();
+
+            list.insert(1, 1).unwrap();
+            assert!(list.log(INSERT, &Failing).is_err());
+            list.insert(2, 2).unwrap();
+        }
+
+        let list = DurableSkipList::<u64, u64>::recover(&dir).unwrap();
+
+        assert!(list.iter().map(|e| *e.key()).eq([1, 2]));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
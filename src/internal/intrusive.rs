@@ -0,0 +1,357 @@
+//! A skip list over nodes its users own. Embedding [Links](Links) in a struct and implementing
+//! [Intrusive](Intrusive) for it lets an [IntrusiveList](IntrusiveList) link the struct in place,
+//! the list never allocates.
+//!
+//! The list borrows every item it links for its whole lifetime, so items can neither move nor be
+//! dropped while linked. Dropping the list unlinks them, after which they can be linked again.
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+use core::ptr;
+
+use crate::internal::utils::{draw_height, DEFAULT_PROMOTION};
+
+/// The highest tower [Links](Links) leave room for.
+pub const LINKS_HEIGHT: usize = 16;
+
+/// The key of an item and the tower linking it into an [IntrusiveList](IntrusiveList).
+pub struct Links<K> {
+    key: K,
+    /// The item these links are embedded in, while it is linked.
+    owner: Cell<*const ()>,
+    height: Cell<usize>,
+    tower: [Cell<*const Links<K>>; LINKS_HEIGHT],
+}
+
+impl<K> Links<K> {
+    pub fn new(key: K) -> Self {
+        Links {
+            key,
+            owner: Cell::new(ptr::null()),
+            height: Cell::new(0),
+            tower: core::array::from_fn(|_| Cell::new(ptr::null())),
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Whether the item is linked into a list.
+    pub fn is_linked(&self) -> bool {
+        !self.owner.get().is_null()
+    }
+
+    /// The links following these on `level`.
+    fn next<'a>(&self, level: usize) -> Option<&'a Links<K>> {
+        // # Safety
+        //
+        // A list only links items it borrows for longer than it lives.
+        unsafe { self.tower[level].get().as_ref() }
+    }
+}
+
+impl<K: core::fmt::Debug> core::fmt::Debug for Links<K> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Links")
+            .field("key", &self.key)
+            .field("linked", &self.is_linked())
+            .finish()
+    }
+}
+
+/// Implemented by the items of an [IntrusiveList](IntrusiveList), to hand it the [Links](Links)
+/// embedded in them.
+pub trait Intrusive<K> {
+    fn links(&self) -> &Links<K>;
+}
+
+impl<K> Intrusive<K> for Links<K> {
+    fn links(&self) -> &Links<K> {
+        self
+    }
+}
+
+/// A skip list linking items of type `T` that are ordered by their keys of type `K`. Keys are
+/// unique, just as in the other lists.
+pub struct IntrusiveList<'a, K: 'a, T> {
+    /// The links of the head are never linked themselves, their key is never read.
+    head: [Cell<*const Links<K>>; LINKS_HEIGHT],
+    len: usize,
+    seed: usize,
+    _items: PhantomData<&'a T>,
+}
+
+impl<'a, K, T> IntrusiveList<'a, K, T> {
+    pub fn new() -> Self {
+        IntrusiveList {
+            head: core::array::from_fn(|_| Cell::new(ptr::null())),
+            len: 0,
+            seed: rand::random::<usize>() | 1,
+            _items: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The pointer on `level` of `prev`, or of the head if `prev` is `None`.
+    fn level<'b>(&'b self, prev: Option<&'b Links<K>>, level: usize) -> &'b Cell<*const Links<K>> {
+        match prev {
+            Some(prev) => &prev.tower[level],
+            None => &self.head[level],
+        }
+    }
+
+    fn gen_height(&mut self) -> usize {
+        draw_height(&mut self.seed, DEFAULT_PROMOTION, LINKS_HEIGHT)
+    }
+
+    /// Returns the item with the smallest key.
+    pub fn first(&self) -> Option<&'a T> {
+        // # Safety
+        //
+        // Every linked item is one of ours.
+        unsafe { self.head[0].get().as_ref().map(|links| Self::owner(links)) }
+    }
+
+    /// Returns an iterator over the items, in ascending order of their keys.
+    pub fn iter(&self) -> Iter<'a, '_, K, T> {
+        Iter {
+            next: unsafe { self.head[0].get().as_ref() },
+            _list: PhantomData,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `links` have to be linked into a list of items of type `T`.
+    unsafe fn owner(links: &Links<K>) -> &'a T {
+        &*links.owner.get().cast::<T>()
+    }
+}
+
+impl<'a, K, T> IntrusiveList<'a, K, T>
+where
+    K: Ord,
+    T: Intrusive<K>,
+{
+    /// Returns the links on each level after which `key` belongs, `None` standing for the head.
+    fn search(&self, key: &K) -> [Option<&'a Links<K>>; LINKS_HEIGHT] {
+        let mut prev = [None; LINKS_HEIGHT];
+        let mut curr = None;
+
+        for level in (0..LINKS_HEIGHT).rev() {
+            // # Safety
+            //
+            // A list only links items it borrows for longer than it lives.
+            while let Some(next) = unsafe { self.level(curr, level).get().as_ref::<'a>() } {
+                if next.key >= *key {
+                    break;
+                }
+
+                curr = Some(next);
+            }
+
+            prev[level] = curr;
+        }
+
+        prev
+    }
+
+    /// Links `item` into the list. Gives it back if it is linked already, into this list or
+    /// another, or if the list has an item of the same key.
+    pub fn insert(&mut self, item: &'a T) -> Result<(), &'a T> {
+        let links = item.links();
+
+        if links.is_linked() {
+            return Err(item);
+        }
+
+        let prev = self.search(&links.key);
+
+        // # Safety
+        //
+        // A list only links items it borrows for longer than it lives.
+        let next = unsafe { self.level(prev[0], 0).get().as_ref() };
+
+        if next.is_some_and(|next| next.key == links.key) {
+            return Err(item);
+        }
+
+        let height = self.gen_height();
+
+        links.owner.set((item as *const T).cast());
+        links.height.set(height);
+
+        for (level, prev) in prev.iter().enumerate().take(height) {
+            links.tower[level].set(self.level(*prev, level).get());
+            self.level(*prev, level).set(links);
+        }
+
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Returns the item of `key`.
+    pub fn get(&self, key: &K) -> Option<&'a T> {
+        let prev = self.search(key);
+        let links = self.level(prev[0], 0).get();
+
+        // # Safety
+        //
+        // Every linked item is one of ours.
+        unsafe {
+            links
+                .as_ref()
+                .filter(|links| links.key == *key)
+                .map(|links| Self::owner(links))
+        }
+    }
+
+    /// Unlinks and returns the item of `key`, which may then be linked again.
+    pub fn remove(&mut self, key: &K) -> Option<&'a T> {
+        let prev = self.search(key);
+        let links = unsafe { self.level(prev[0], 0).get().as_ref::<'a>() }
+            .filter(|links| links.key == *key)?;
+
+        for (level, prev) in prev.iter().enumerate().take(links.height.get()) {
+            self.level(*prev, level).set(links.tower[level].get());
+            links.tower[level].set(ptr::null());
+        }
+
+        self.len -= 1;
+
+        // # Safety
+        //
+        // The item was one of ours until now.
+        let item = unsafe { Self::owner(links) };
+        links.owner.set(ptr::null());
+
+        Some(item)
+    }
+}
+
+impl<'a, K, T> Default for IntrusiveList<'a, K, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Unlinks every item, so they can be linked into another list.
+impl<'a, K, T> Drop for IntrusiveList<'a, K, T> {
+    fn drop(&mut self) {
+        let mut next = unsafe { self.head[0].get().as_ref::<'a>() };
+
+        while let Some(links) = next {
+            next = links.next(0);
+
+            links.tower.iter().for_each(|level| level.set(ptr::null()));
+            links.owner.set(ptr::null());
+        }
+    }
+}
+
+/// An iterator over the items of an [IntrusiveList](IntrusiveList), see
+/// [iter](IntrusiveList::iter).
+pub struct Iter<'a, 'l, K, T> {
+    next: Option<&'a Links<K>>,
+    _list: PhantomData<&'l IntrusiveList<'a, K, T>>,
+}
+
+impl<'a, 'l, K, T> Iterator for Iter<'a, 'l, K, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let links = self.next?;
+        self.next = links.next(0);
+
+        // # Safety
+        //
+        // The iterator borrows the list, so every item it walks over is still one of its own.
+        Some(unsafe { IntrusiveList::<'a, K, T>::owner(links) })
+    }
+}
+
+#[cfg(test)]
+mod intrusive_test {
+    use super::*;
+
+    struct Task {
+        name: &'static str,
+        links: Links<u32>,
+    }
+
+    impl Intrusive<u32> for Task {
+        fn links(&self) -> &Links<u32> {
+            &self.links
+        }
+    }
+
+    fn task(name: &'static str, deadline: u32) -> Task {
+        Task {
+            name,
+            links: Links::new(deadline),
+        }
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let tasks: Vec<_> = (0..100).rev().map(|i| task("task", i)).collect();
+        let mut list = IntrusiveList::new();
+
+        for task in &tasks {
+            assert!(list.insert(task).is_ok());
+        }
+
+        assert_eq!(list.len(), 100);
+        assert!(list.iter().map(|t| *t.links.key()).eq(0..100));
+        assert_eq!(*list.first().unwrap().links.key(), 0);
+
+        let removed = list.remove(&42).unwrap();
+        assert!(!removed.links.is_linked());
+        assert!(list.get(&42).is_none());
+        assert!(list.remove(&42).is_none());
+
+        // An unlinked item can be linked again.
+        assert!(list.insert(removed).is_ok());
+        assert!(core::ptr::eq(list.get(&42).unwrap(), removed));
+    }
+
+    #[test]
+    fn test_rejects_linked_and_duplicates() {
+        let (a, b, c) = (task("a", 1), task("b", 1), task("c", 2));
+        let mut list = IntrusiveList::new();
+
+        assert!(list.insert(&a).is_ok());
+        assert_eq!(list.insert(&b).unwrap_err().name, "b");
+
+        {
+            let mut other = IntrusiveList::new();
+
+            assert!(other.insert(&c).is_ok());
+            assert_eq!(list.insert(&c).unwrap_err().name, "c");
+        }
+
+        // Dropping the other list unlinked the item.
+        assert!(!c.links.is_linked());
+        assert!(list.insert(&c).is_ok());
+        assert!(list.iter().map(|t| t.name).eq(["a", "c"]));
+    }
+
+    #[test]
+    fn test_bare_links() {
+        let links: Vec<_> = [3, 1, 2].into_iter().map(Links::new).collect();
+        let mut list = IntrusiveList::new();
+
+        links.iter().for_each(|l| list.insert(l).unwrap());
+
+        assert!(list.iter().map(|l| *l.key()).eq([1, 2, 3]));
+    }
+}
@@ -0,0 +1,353 @@
+//! A compact, sorted on-disk format for lists, and a read-only view of it through a memory map.
+//!
+//! Together with the in-memory lists, this covers the memtable and flushed-segment pair of a small
+//! LSM tree. Keys and values are stored as raw bytes. Lookups in a [MmapSkipList](MmapSkipList)
+//! compare those bytes, so the `Ord` of the list's keys has to agree with the order of their
+//! bytes, as it does for `Vec<u8>` and `String`.
+//!
+//! The layout of a file is:
+//!
+//! ```text
+//! entry*    key length: u32, value length: u32, key bytes, value bytes
+//! index     offset of every entry: u64
+//! footer    index offset: u64, entry count: u64, magic: b"SKPYSST1"
+//! ```
+//!
+//! All integers are little endian.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::ops::{Bound, RangeBounds};
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use super::skiplist::SkipList;
+use super::sync::SkipList as SyncSkipList;
+
+const MAGIC: &[u8; 8] = b"SKPYSST1";
+const FOOTER_LEN: usize = 8 + 8 + MAGIC.len();
+
+/// Writes `entries` to `path`, one at a time, reading the bytes of the key and value of each with
+/// `bytes`. Entries of the concurrent list hand out their bytes only for as long as they live.
+fn write_compact<I, F>(path: &Path, entries: I, bytes: F) -> io::Result<()>
+where
+    I: Iterator,
+    F: Fn(&I::Item) -> (&[u8], &[u8]),
+{
+    let mut out = BufWriter::new(File::create(path)?);
+    let mut index = Vec::new();
+    let mut offset = 0u64;
+
+    for entry in entries {
+        let (key, val) = bytes(&entry);
+        let too_long = || io::Error::new(io::ErrorKind::InvalidInput, "entry longer than 4GiB");
+        let key_len = u32::try_from(key.len()).map_err(|_| too_long())?;
+        let val_len = u32::try_from(val.len()).map_err(|_| too_long())?;
+
+        out.write_all(&key_len.to_le_bytes())?;
+        out.write_all(&val_len.to_le_bytes())?;
+        out.write_all(key)?;
+        out.write_all(val)?;
+
+        index.push(offset);
+        offset += 8 + key.len() as u64 + val.len() as u64;
+    }
+
+    for entry in index.iter() {
+        out.write_all(&entry.to_le_bytes())?;
+    }
+
+    out.write_all(&offset.to_le_bytes())?;
+    out.write_all(&(index.len() as u64).to_le_bytes())?;
+    out.write_all(MAGIC)?;
+
+    out.into_inner()?.sync_all()
+}
+
+impl<'domain, K, V> SkipList<'domain, K, V>
+where
+    K: Ord + AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    /// Writes the list to `path` in the compact format, ready to be opened with
+    /// [open_mmap](MmapSkipList::open_mmap).
+    pub fn write_compact(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        write_compact(path.as_ref(), self.iter(), |e| {
+            (e.key().as_ref(), e.val().as_ref())
+        })
+    }
+}
+
+impl<'domain, K, V> SyncSkipList<'domain, K, V>
+where
+    K: Ord + Send + AsRef<[u8]>,
+    V: Send + AsRef<[u8]>,
+{
+    /// Writes the list to `path` in the compact format, ready to be opened with
+    /// [open_mmap](MmapSkipList::open_mmap). Concurrent modifications may or may not be part of
+    /// the written file, but the file is always sorted.
+    pub fn write_compact(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        write_compact(path.as_ref(), self.iter(), |e| {
+            (e.key().as_ref(), e.val().as_ref())
+        })
+    }
+}
+
+/// A read-only list backed by a file written with `write_compact`.
+pub struct MmapSkipList {
+    map: Mmap,
+    index: usize,
+    len: usize,
+}
+
+impl MmapSkipList {
+    /// Maps the file at `path` into memory and checks that it is a well formed list.
+    ///
+    /// # Safety
+    ///
+    /// The file must be neither modified nor truncated, by this process or any other, for as long
+    /// as the list lives. The list hands out the mapped bytes as slices, and changing them from
+    /// under it is undefined behavior, or kills the process with `SIGBUS` once the file shrinks.
+    /// This is the contract of [Mmap::map](memmap2::Mmap::map).
+    pub unsafe fn open_mmap(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+
+        // # Safety
+        //
+        // The caller promises not to modify the file while it is mapped.
+        let map = Mmap::map(&file)?;
+
+        let invalid = |msg| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+        if map.len() < FOOTER_LEN || &map[map.len() - MAGIC.len()..] != MAGIC {
+            return Err(invalid("not a compact skip list"));
+        }
+
+        let footer = map.len() - FOOTER_LEN;
+
+        // On 32 bit targets, the numbers of a file that fits in memory always fit in a `usize`.
+        let (Ok(index), Ok(len)) = (
+            usize::try_from(read_u64(&map, footer)),
+            usize::try_from(read_u64(&map, footer + 8)),
+        ) else {
+            return Err(invalid("corrupt index"));
+        };
+
+        if len.checked_mul(8).and_then(|l| l.checked_add(index)) != Some(footer) {
+            return Err(invalid("corrupt index"));
+        }
+
+        let list = MmapSkipList { map, index, len };
+
+        // Validate every entry once, so lookups can slice without checking bounds again.
+        for i in 0..len {
+            let offset = usize::try_from(read_u64(&list.map, index + i * 8))
+                .map_err(|_| invalid("corrupt entry"))?;
+            let end = offset
+                .checked_add(8)
+                .filter(|end| *end <= index)
+                .ok_or_else(|| invalid("corrupt entry"))?;
+            let key_len = read_u32(&list.map, offset) as usize;
+            let val_len = read_u32(&list.map, offset + 4) as usize;
+
+            let end = end.checked_add(key_len).and_then(|end| end.checked_add(val_len));
+
+            if end.is_none_or(|end| end > index) {
+                return Err(invalid("corrupt entry"));
+            }
+        }
+
+        Ok(list)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        let i = self.lower_bound(key);
+
+        match self.entry(i) {
+            Some((k, v)) if k == key => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Iterates over all entries with keys in `range`, in ascending order.
+    pub fn range<'k, R>(&self, range: R) -> impl Iterator<Item = (&[u8], &[u8])>
+    where
+        R: RangeBounds<&'k [u8]>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(key) => self.lower_bound(key),
+            Bound::Excluded(key) => self.upper_bound(key),
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(key) => self.upper_bound(key),
+            Bound::Excluded(key) => self.lower_bound(key),
+            Bound::Unbounded => self.len,
+        };
+
+        (start..end.max(start)).filter_map(move |i| self.entry(i))
+    }
+
+    /// Iterates over all entries in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.range(..)
+    }
+
+    /// The position of the first entry with a key not less than `key`.
+    fn lower_bound(&self, key: &[u8]) -> usize {
+        self.partition_point(|k| k < key)
+    }
+
+    /// The position of the first entry with a key greater than `key`.
+    fn upper_bound(&self, key: &[u8]) -> usize {
+        self.partition_point(|k| k <= key)
+    }
+
+    fn partition_point(&self, pred: impl Fn(&[u8]) -> bool) -> usize {
+        let (mut lo, mut hi) = (0, self.len);
+
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+
+            if self.entry(mid).is_some_and(|(k, _)| pred(k)) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        lo
+    }
+
+    fn offset(&self, i: usize) -> usize {
+        read_u64(&self.map, self.index + i * 8) as usize
+    }
+
+    fn entry(&self, i: usize) -> Option<(&[u8], &[u8])> {
+        if i >= self.len {
+            return None;
+        }
+
+        let offset = self.offset(i);
+        let key_len = read_u32(&self.map, offset) as usize;
+        let val_len = read_u32(&self.map, offset + 4) as usize;
+        let key = offset + 8;
+
+        Some((
+            &self.map[key..key + key_len],
+            &self.map[key + key_len..key + key_len + val_len],
+        ))
+    }
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod mmap_test {
+    use super::*;
+
+    fn path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("skippy-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_write_open() {
+        let list = SyncSkipList::new();
+
+        for i in (0..100u32).rev() {
+            list.insert(format!("key-{:03}", i), i.to_string());
+        }
+
+        let path = path("write_open");
+        list.write_compact(&path).unwrap();
+        let map = unsafe { MmapSkipList::open_mmap(&path) }.unwrap();
+
+        assert_eq!(map.len(), 100);
+        assert_eq!(map.get(b"key-042"), Some(b"42".as_slice()));
+        assert_eq!(map.get(b"key-100"), None);
+        assert!(map
+            .iter()
+            .map(|(k, _)| k.to_vec())
+            .eq((0..100).map(|i| format!("key-{:03}", i).into_bytes())));
+
+        let range = map
+            .range(b"key-010".as_slice()..b"key-013".as_slice())
+            .map(|(_, v)| v)
+            .collect::<Vec<_>>();
+        assert_eq!(range, [b"10", b"11", b"12"]);
+
+        assert_eq!(map.range(b"key-090".as_slice()..).count(), 10);
+        assert_eq!(map.range(..=b"key-009".as_slice()).count(), 10);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut list = SkipList::<Vec<u8>, Vec<u8>>::new();
+        list.insert(vec![], vec![]);
+        list.remove(&vec![]);
+
+        let path = path("empty");
+        list.write_compact(&path).unwrap();
+        let map = unsafe { MmapSkipList::open_mmap(&path) }.unwrap();
+
+        assert!(map.is_empty());
+        assert_eq!(map.iter().count(), 0);
+        assert_eq!(map.get(b""), None);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt() {
+        let path = path("corrupt");
+        std::fs::write(&path, b"definitely not a list").unwrap();
+
+        assert_eq!(
+            unsafe { MmapSkipList::open_mmap(&path) }.err().map(|e| e.kind()),
+            Some(io::ErrorKind::InvalidData)
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_entry_past_index() {
+        let path = path("past_index");
+
+        // A single entry claiming the longest key and value there are, which would wrap around
+        // the end of the address space on 32 bit targets.
+        let mut file = Vec::new();
+        file.extend_from_slice(&u32::MAX.to_le_bytes());
+        file.extend_from_slice(&u32::MAX.to_le_bytes());
+        file.extend_from_slice(&0u64.to_le_bytes());
+        file.extend_from_slice(&8u64.to_le_bytes());
+        file.extend_from_slice(&1u64.to_le_bytes());
+        file.extend_from_slice(MAGIC);
+        std::fs::write(&path, file).unwrap();
+
+        assert_eq!(
+            unsafe { MmapSkipList::open_mmap(&path) }.err().map(|e| e.kind()),
+            Some(io::ErrorKind::InvalidData)
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
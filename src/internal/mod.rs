@@ -1,3 +1,12 @@
+#[cfg(feature = "persistence")]
+pub mod durable;
+pub mod intrusive;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "serde")]
+mod serialize;
 pub mod skiplist;
+#[cfg(feature = "snapshot")]
+mod snapshot;
 pub mod sync;
-mod utils;
+pub(crate) mod utils;
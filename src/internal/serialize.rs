@@ -0,0 +1,163 @@
+//! [Serialize](serde::Serialize) and [Deserialize](serde::Deserialize) support for both lists.
+//!
+//! Lists are serialized as maps in key order. Deserializing accepts maps as well as sequences of
+//! `(key, value)` pairs, sorted or not, and bulk-loads them via `from_sorted_iter`.
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use super::skiplist::SkipList;
+use super::sync::SkipList as SyncSkipList;
+
+impl<'domain, K, V> Serialize for SkipList<'domain, K, V>
+where
+    K: Ord + Serialize,
+    V: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+
+        for entry in self.iter() {
+            map.serialize_entry(entry.key(), entry.val())?;
+        }
+
+        map.end()
+    }
+}
+
+impl<'domain, K, V> Serialize for SyncSkipList<'domain, K, V>
+where
+    K: Ord + Send + Serialize,
+    V: Send + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // The length is only a hint under concurrent modification, so we leave it out.
+        let mut map = serializer.serialize_map(None)?;
+
+        for entry in self.iter() {
+            map.serialize_entry(entry.key(), entry.val())?;
+        }
+
+        map.end()
+    }
+}
+
+impl<'de, 'domain, K, V> Deserialize<'de> for SkipList<'domain, K, V>
+where
+    K: Ord + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pairs = deserializer.deserialize_map(PairsVisitor(PhantomData))?;
+
+        Ok(SkipList::from_sorted_iter(pairs))
+    }
+}
+
+impl<'de, 'domain, K, V> Deserialize<'de> for SyncSkipList<'domain, K, V>
+where
+    K: Ord + Send + Deserialize<'de>,
+    V: Send + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pairs = deserializer.deserialize_map(PairsVisitor(PhantomData))?;
+
+        Ok(SyncSkipList::from_sorted_iter(pairs))
+    }
+}
+
+/// Collects the pairs and sorts them by key. The sort is stable and so keeps the last value of a
+/// duplicate key last, and is linear for input that already is sorted.
+struct PairsVisitor<K, V>(PhantomData<(K, V)>);
+
+impl<'de, K, V> Visitor<'de> for PairsVisitor<K, V>
+where
+    K: Ord + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    type Value = Vec<(K, V)>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map or a sequence of key-value pairs")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut pairs: Self::Value = Vec::with_capacity(map.size_hint().unwrap_or(0));
+
+        while let Some(pair) = map.next_entry()? {
+            pairs.push(pair);
+        }
+
+        pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(pairs)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut pairs: Self::Value = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(pair) = seq.next_element()? {
+            pairs.push(pair);
+        }
+
+        pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(pairs)
+    }
+}
+
+#[cfg(test)]
+mod serialize_test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut list = SkipList::new();
+
+        for i in (0..100).rev() {
+            list.insert(i, i * 2);
+        }
+
+        let json = serde_json::to_string(&list).unwrap();
+        let other: SkipList<'_, u32, u32> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(other.len(), 100);
+        assert!(other
+            .iter()
+            .map(|e| (*e.key(), *e.val()))
+            .eq((0..100).map(|i| (i, i * 2))));
+    }
+
+    #[test]
+    fn test_round_trip_sync() {
+        let list = SyncSkipList::new();
+
+        for i in (0..100).rev() {
+            list.insert(i, i * 2);
+        }
+
+        let json = serde_json::to_string(&list).unwrap();
+        let other: SyncSkipList<'_, u32, u32> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(other.len(), 100);
+        assert!(other
+            .iter()
+            .map(|e| (*e.key(), *e.val()))
+            .eq((0..100).map(|i| (i, i * 2))));
+    }
+
+    #[test]
+    fn test_unsorted_pairs() {
+        let list: SkipList<'_, u32, &str> =
+            serde_json::from_str(r#"{"3": "c", "1": "a", "2": "x", "2": "b"}"#).unwrap();
+
+        assert_eq!(list.len(), 3);
+        assert!(list
+            .iter()
+            .map(|e| (*e.key(), *e.val()))
+            .eq([(1, "a"), (2, "b"), (3, "c")]));
+    }
+}
@@ -1,7 +1,8 @@
 use core::{ptr::NonNull, sync::atomic::Ordering};
 use std::marker::PhantomData;
 
-use crate::internal::utils::{skiplist_basics, GeneratesHeight, Levels, Node, HEIGHT};
+use crate::internal::sync::tagged::MaybeTagged;
+use crate::internal::utils::{metrics, skiplist_basics, GeneratesHeight, Node, HEIGHT};
 
 skiplist_basics!(SkipList);
 
@@ -10,15 +11,189 @@ where
     K: Ord,
 {
     /// Inserts a value in the list given a key.
+    ///
+    /// # Panics
+    ///
+    /// If comparing keys panics, the list is left as it was, as every comparison happens before the
+    /// list is modified. The same holds for [remove](SkipList::remove).
     pub fn insert(&mut self, key: K, val: V) -> Option<V> {
-        self.internal_insert(key, val, true)
+        self.internal_insert(key, val, true, None)
+    }
+
+    /// Inserts a value in the list given a key, building its tower `height` levels high instead of
+    /// a random height, so tests can construct lists of a specific shape. Replacing the value of a
+    /// key keeps its tower as it was.
+    ///
+    /// # Panics
+    ///
+    /// If `height` is 0 or above [max_height](Self::max_height).
+    #[cfg(any(test, feature = "testing"))]
+    pub fn insert_with_height(&mut self, key: K, val: V, height: usize) -> Option<V> {
+        self.internal_insert(key, val, true, Some(height))
+    }
+
+    /// Merges `sources` into a freshly built list, without inserting the keys one by one. If a key
+    /// is in more than one source, `resolve` combines its values, given in the order of
+    /// `sources`.
+    pub fn compact<F>(sources: Vec<Self>, resolve: F) -> Self
+    where
+        F: Fn(&K, Vec<V>) -> V,
+    {
+        let sources = sources.into_iter().map(|s| s.into_iter()).collect();
+
+        Self::from_sorted_iter(crate::internal::utils::merge::merge_sorted(
+            sources, resolve,
+        ))
+    }
+
+    /// Folds the entries of `other` into this list, calling `resolve` with the key, the value of
+    /// this list and the value of `other` for every key both hold. Both lists are sorted, so
+    /// rather than searching for each key from the top, every level carries on from where the
+    /// previous key left it, merging the lists in a single pass.
+    pub fn merge_from<F>(&mut self, other: &SkipList<'_, K, V>, resolve: F)
+    where
+        K: Clone,
+        V: Clone,
+        F: Fn(&K, &V, &V) -> V,
+    {
+        let head = self.head.as_ptr().cast::<Node<K, V>>();
+
+        // The last node before the current key on every level, along with its position.
+        let mut prev = [head; HEIGHT];
+        #[cfg(feature = "rank")]
+        let mut ranks = [0; HEIGHT];
+
+        for entry in other.iter() {
+            let key = entry.key();
+
+            metrics::inserted();
+
+            unsafe {
+                let mut curr = head;
+                #[cfg(feature = "rank")]
+                let mut rank = 0;
+
+                for level in (0..self.state.max_height.load(Ordering::Relaxed)).rev() {
+                    // Go on from whichever of the node we dropped down from and the one the
+                    // previous key left on this level is further along.
+                    if self.is_head(curr)
+                        || (!self.is_head(prev[level]) && (*curr).key < (*prev[level]).key)
+                    {
+                        curr = prev[level];
+                        #[cfg(feature = "rank")]
+                        {
+                            rank = ranks[level];
+                        }
+                    }
+
+                    loop {
+                        let mut next = (*curr).levels[level].load_ptr();
+
+                        if !next.is_null() && (*next).levels[level].load_tag() == 1 {
+                            next = self.unlink_level(curr, next, level);
+                        }
+
+                        if next.is_null() || (*next).key >= *key {
+                            break;
+                        }
+
+                        #[cfg(feature = "rank")]
+                        {
+                            rank += (*curr).span(level);
+                        }
+                        curr = next;
+                    }
+
+                    prev[level] = curr;
+                    #[cfg(feature = "rank")]
+                    {
+                        ranks[level] = rank;
+                    }
+                }
+
+                let next = (*prev[0]).levels[0].load_ptr();
+
+                if !next.is_null() && (*next).key == *key {
+                    let val = resolve(key, &(*next).val, entry.val());
+                    let old = core::mem::replace(&mut (*next).val, val);
+                    self.state.modified();
+
+                    self.callbacks.removed(&(*next).key, &old);
+                    self.callbacks.inserted(&(*next).key, &(*next).val);
+
+                    continue;
+                }
+
+                let new_node = self.new_node(key.clone(), entry.val().clone());
+
+                let insertion_point = SearchResult {
+                    prev,
+                    #[cfg(feature = "rank")]
+                    ranks,
+                    target: None,
+                };
+
+                self.link_nodes(new_node, &insertion_point);
+
+                self.state.grown((*new_node).height());
+                self.state.modified();
+
+                self.callbacks.inserted(&(*new_node).key, &(*new_node).val);
+            }
+        }
+    }
+
+    /// Rebuilds every tower with the current promotion probability, restoring the shape searches
+    /// expect of the list. Worth it once [needs_optimize](Self::needs_optimize) says so, or after
+    /// changing the probability of a list that already holds most of its entries. The entries
+    /// stay as they are, just in new nodes.
+    pub fn optimize(&mut self) {
+        unsafe {
+            let head = &*self.head.as_ptr().cast::<Node<K, V>>();
+            let mut node = head.levels[0].load_ptr();
+
+            // Detach the old nodes, the list is rebuilt from an empty head.
+            for level in 0..self.state.height_cap {
+                head.levels[level].store_ptr(core::ptr::null_mut());
+                #[cfg(feature = "rank")]
+                head.set_span(level, 1);
+            }
+            self.state.max_height.store(1, Ordering::Relaxed);
+
+            let mut tail = self.tail();
+
+            while !node.is_null() {
+                let next = (*node).levels[0].load_ptr();
+
+                let key = core::ptr::read(&(*node).key);
+                let val = core::ptr::read(&(*node).val);
+
+                self.state.shrunk((*node).height());
+                Node::<K, V>::dealloc(node);
+                self.state.allocations.freed(1);
+
+                self.append(&mut tail, key, val);
+
+                node = next;
+            }
+        }
+
+        self.state.tuning.rebuilt();
     }
 
     pub fn insert_conditionally(&mut self, key: K, val: V) -> Option<V> {
-        self.internal_insert(key, val, false)
+        self.internal_insert(key, val, false, None)
     }
 
-    fn internal_insert(&mut self, key: K, mut val: V, replace: bool) -> Option<V> {
+    fn internal_insert(
+        &mut self,
+        key: K,
+        mut val: V,
+        replace: bool,
+        height: Option<usize>,
+    ) -> Option<V> {
+        metrics::inserted();
+
         // After this check, whether we are holding the head or a regular Node will
         // not impact the operation.
         unsafe {
@@ -27,16 +202,24 @@ where
             if let Some(mut target) = insertion_point.target.take() {
                 if replace {
                     core::mem::swap(&mut target.as_mut().val, &mut val);
+                    self.state.modified();
+
+                    self.callbacks.removed(&target.as_ref().key, &val);
+                    self.callbacks
+                        .inserted(&target.as_ref().key, &target.as_ref().val);
                 }
 
                 return Some(val);
             }
 
-            let new_node = Node::new_rand_height(key, val, self);
+            let new_node = self.new_node_of_height(key, val, height);
+
+            self.link_nodes(new_node, &insertion_point);
 
-            self.link_nodes(new_node, insertion_point.prev);
+            self.state.grown((*new_node).height());
+            self.state.modified();
 
-            self.state.len.fetch_add(1, Ordering::Relaxed);
+            self.callbacks.inserted(&(*new_node).key, &(*new_node).val);
 
             None
         }
@@ -49,16 +232,41 @@ where
     /// - link_node cannot be null
     /// - no pointer tower along the path can have a null pointer pointing backwards
     /// - a tower of sufficient height must eventually be reached, the list head can be this tower
-    unsafe fn link_nodes(&self, new_node: *mut Node<K, V>, prev: [&Levels<K, V>; HEIGHT]) {
+    unsafe fn link_nodes(&self, new_node: *mut Node<K, V>, insertion_point: &SearchResult<K, V>) {
+        let height = (*new_node).height();
+
+        // The position of the new node, counting from 1 just past the head.
+        #[cfg(feature = "rank")]
+        let rank = insertion_point.ranks[0] + 1;
+
         // iterate over all the levels in the new nodes pointer tower
-        for (i, levels) in prev.iter().enumerate().take((*new_node).height()) {
+        for (i, &prev) in insertion_point.prev.iter().enumerate().take(height) {
             // move backwards until a pointer tower of sufficient hight is reached
             unsafe {
-                (*new_node).levels[i].store_ptr(levels[i].load_ptr());
-                levels[i].store_ptr(new_node);
+                (*new_node).levels[i].store_ptr((*prev).levels[i].load_ptr());
+                (*prev).levels[i].store_ptr(new_node);
                 (*new_node).add_ref();
+
+                #[cfg(feature = "rank")]
+                {
+                    let skipped = rank - insertion_point.ranks[i];
+                    (*new_node).set_span(i, (*prev).span(i) + 1 - skipped);
+                    (*prev).set_span(i, skipped);
+                }
             }
         }
+
+        // The links passing over the new node skip one more.
+        #[cfg(feature = "rank")]
+        for (i, &prev) in insertion_point
+            .prev
+            .iter()
+            .enumerate()
+            .take(self.state.height_cap)
+            .skip(height)
+        {
+            (*prev).set_span(i, (*prev).span(i) + 1);
+        }
     }
 
     pub fn remove(&mut self, key: &K) -> Option<(K, V)> {
@@ -86,14 +294,22 @@ where
                 SearchResult {
                     target: Some(target),
                     prev,
+                    ..
                 } => {
                     let target = target.as_ptr();
                     let key = core::ptr::read(&(*target).key);
                     let val = core::ptr::read(&(*target).val);
 
+                    let height = (*target).height();
+
                     self.unlink(target, prev);
                     Node::<K, V>::dealloc(target);
-                    self.state.len.fetch_sub(1, Ordering::Relaxed);
+                    self.state.allocations.freed(1);
+                    self.state.shrunk(height);
+                    self.state.modified();
+                    metrics::removed();
+
+                    self.callbacks.removed(&key, &val);
 
                     Some((key, val))
                 }
@@ -103,19 +319,36 @@ where
     }
 
     /// Logically removes the node from the list by linking its adjacent nodes to one-another.
-    fn unlink(&mut self, node: *mut Node<K, V>, prev: [&Levels<K, V>; HEIGHT]) {
+    fn unlink(&mut self, node: *mut Node<K, V>, prev: [*mut Node<K, V>; HEIGHT]) {
         // safety check against UB caused by unlinking the head
         if self.is_head(node) {
             panic!()
         }
         unsafe {
-            for (i, levels) in prev.iter().enumerate().take((*node).height()) {
-                levels[i].store_ptr((*node).levels[i].load_ptr());
+            let height = (*node).height();
+
+            for (i, &prev) in prev.iter().enumerate().take(height) {
+                (*prev).levels[i].store_ptr((*node).levels[i].load_ptr());
+
+                #[cfg(feature = "rank")]
+                (*prev).set_span(i, (*prev).span(i) + (*node).span(i) - 1);
+            }
+
+            // The links passing over the node skip one less.
+            #[cfg(feature = "rank")]
+            for (i, &prev) in prev
+                .iter()
+                .enumerate()
+                .take(self.state.height_cap)
+                .skip(height)
+            {
+                (*prev).set_span(i, (*prev).span(i) - 1);
             }
         }
     }
 
     unsafe fn unlink_level(
+        &self,
         prev: *mut Node<K, V>,
         curr: *mut Node<K, V>,
         level: usize,
@@ -124,6 +357,7 @@ where
 
         if (*curr).sub_ref() == 0 {
             Node::<K, V>::dealloc(curr);
+            self.state.allocations.freed(1);
         }
 
         (*prev).levels[level].store_ptr(next);
@@ -133,46 +367,80 @@ where
     /// This method is `unsafe` as it may return the head typecast as a Node, which can
     /// cause UB if not handled appropriately. If the return value is Ok(...) then it is a
     /// regular Node. If it is Err(...) then it is the head.
-    unsafe fn find<'a>(&self, key: &K) -> SearchResult<'a, K, V> {
+    unsafe fn find(&self, key: &K) -> SearchResult<K, V> {
         let mut level = self.state.max_height.load(Ordering::Relaxed);
         let head = unsafe { &(*self.head.as_ptr()) };
 
-        let mut prev = [&head.levels; HEIGHT];
+        let mut curr = self.head.as_ptr().cast::<Node<K, V>>();
+        let mut prev = [curr; HEIGHT];
+
+        // The position of `curr` and of every node in `prev`, counting from 1 just past the head.
+        #[cfg(feature = "rank")]
+        let (mut rank, mut ranks) = (0, [0; HEIGHT]);
 
         // find the first and highest node tower
         while level > 1 && head.levels[level - 1].load_ptr().is_null() {
             level -= 1;
         }
 
-        let mut curr = self.head.as_ptr().cast::<Node<K, V>>();
-        prev[level - 1] = &(*curr).levels;
+        // The last node we compared against and dropped down from. The next node on the level
+        // below is often the same one, in which case we reuse the result of the comparison.
+        let mut last = (core::ptr::null_mut(), core::cmp::Ordering::Greater);
+        let mut steps = 0;
 
         unsafe {
             while level > 0 {
+                steps += 1;
                 let mut next = (*curr).levels[level - 1].load_ptr();
 
                 if !next.is_null() && (*next).levels[level - 1].load_tag() == 1 {
-                    next = Self::unlink_level(curr, next, level - 1);
+                    next = self.unlink_level(curr, next, level - 1);
                 }
 
-                if next.is_null() || (*next).key >= *key {
-                    prev[level - 1] = &(*curr).levels;
+                let ordering = if next.is_null() {
+                    core::cmp::Ordering::Greater
+                } else if core::ptr::eq(next, last.0) {
+                    last.1
+                } else {
+                    (*next).key.cmp(key)
+                };
+
+                if ordering.is_ge() {
+                    last = (next, ordering);
+                    prev[level - 1] = curr;
+                    #[cfg(feature = "rank")]
+                    {
+                        ranks[level - 1] = rank;
+                    }
                     level -= 1;
                 } else {
+                    #[cfg(feature = "rank")]
+                    {
+                        rank += (*curr).span(level - 1);
+                    }
                     curr = next;
                 }
             }
         }
 
+        self.searched(steps);
+
         let next = (*curr).levels[level].load_ptr();
 
-        if !next.is_null() && &(*next).key == key {
+        if !next.is_null() && core::ptr::eq(next, last.0) && last.1.is_eq() {
             SearchResult {
                 prev,
+                #[cfg(feature = "rank")]
+                ranks,
                 target: unsafe { Some(NonNull::new_unchecked(next)) },
             }
         } else {
-            SearchResult { prev, target: None }
+            SearchResult {
+                prev,
+                #[cfg(feature = "rank")]
+                ranks,
+                target: None,
+            }
         }
     }
 
@@ -195,6 +463,14 @@ where
         }
     }
 
+    /// Returns the key stored in the list along with its value, as [BTreeMap::get_key_value]
+    /// does.
+    ///
+    /// [BTreeMap::get_key_value]: std::collections::BTreeMap::get_key_value
+    pub fn get_key_value<'a>(&'a self, key: &K) -> Option<(&'a K, &'a V)> {
+        self.get(key).map(|entry| entry.key_value())
+    }
+
     pub fn get_mut<'a>(&'a mut self, key: &K) -> Option<MutEntry<'a, K, V>> {
         if self.is_empty() {
             return None;
@@ -226,7 +502,7 @@ where
 
         unsafe {
             while !next.is_null() && (*next).levels[0].load_tag() == 1 {
-                next = Self::unlink_level(&(*node) as *const _ as *mut Node<K, V>, next, 0);
+                next = self.unlink_level(&(*node) as *const _ as *mut Node<K, V>, next, 0);
             }
         }
 
@@ -237,6 +513,44 @@ where
         unsafe { Some(E::from_raw(NonNull::new_unchecked(next))) }
     }
 
+    /// Returns the first entry from `from` on whose key is no less than `key`. Rather than
+    /// starting over from the head, this climbs onto the taller towers it passes and drops down
+    /// once they overshoot, so the cost grows with the distance skipped, not the length.
+    pub(crate) fn seek_from<'a>(
+        &'a self,
+        from: Entry<'a, K, V>,
+        key: &K,
+    ) -> Option<Entry<'a, K, V>> {
+        if from.key >= *key {
+            return Some(from);
+        }
+
+        let mut curr = from.node.as_ptr();
+
+        // # Safety
+        //
+        // We borrow the list, so no node can be unlinked or freed while we walk over them.
+        unsafe {
+            let mut level = (*curr).height() - 1;
+
+            loop {
+                let next = (*curr).levels[level].load_ptr();
+
+                if !next.is_null() && (*next).key < *key {
+                    curr = next;
+                    level = (*curr).height() - 1;
+                } else if level == 0 {
+                    return NonNull::new(next).map(|node| Entry {
+                        node,
+                        _lt: PhantomData,
+                    });
+                } else {
+                    level -= 1;
+                }
+            }
+        }
+    }
+
     pub fn get_first<'a>(&'a self) -> Option<Entry<'a, K, V>> {
         if self.is_empty() {
             return None;
@@ -311,8 +625,8 @@ where
         unsafe {
             match self.find(&key) {
                 SearchResult {
-                    prev: _,
                     target: Some(mut target),
+                    ..
                 } => Removable {
                     node: target.as_mut(),
                     list: self,
@@ -324,10 +638,231 @@ where
         }
     }
 
+    /// Returns the number of keys less than `key`, which is the index of `key` in key order if it
+    /// is in the list, or the index it would take once inserted. Every link counts the nodes it
+    /// skips, so this costs no more than a search.
+    ///
+    /// Only compiled with the `rank` feature, which makes the nodes of either list keep a count
+    /// for each of their levels, though only this list maintains them.
+    #[cfg(feature = "rank")]
+    pub fn rank(&self, key: &K) -> usize {
+        let mut curr = self.head.as_ptr().cast::<Node<K, V>>();
+        let mut rank = 0;
+
+        // # Safety
+        //
+        // We borrow the list, so no node can be unlinked or freed while we walk over them.
+        unsafe {
+            for level in (0..self.state.max_height.load(Ordering::Relaxed)).rev() {
+                loop {
+                    let next = (*curr).levels[level].load_ptr();
+
+                    if next.is_null() || (*next).key >= *key {
+                        break;
+                    }
+
+                    rank += (*curr).span(level);
+                    curr = next;
+                }
+            }
+        }
+
+        rank
+    }
+
+    /// Returns the entry at `index` in key order, the one `iter().nth(index)` would return,
+    /// without walking the entries before it.
+    #[cfg(feature = "rank")]
+    pub fn get_by_rank<'a>(&'a self, index: usize) -> Option<Entry<'a, K, V>> {
+        if index >= self.len() {
+            return None;
+        }
+
+        // The position of the entry, counting from 1 just past the head.
+        let target = index + 1;
+
+        let mut curr = self.head.as_ptr().cast::<Node<K, V>>();
+        let mut rank = 0;
+
+        // # Safety
+        //
+        // We borrow the list, so no node can be unlinked or freed while we walk over them. Once
+        // we reach the position of the entry, we are past the head.
+        unsafe {
+            for level in (0..self.state.max_height.load(Ordering::Relaxed)).rev() {
+                loop {
+                    let next = (*curr).levels[level].load_ptr();
+
+                    if next.is_null() || rank + (*curr).span(level) > target {
+                        break;
+                    }
+
+                    rank += (*curr).span(level);
+                    curr = next;
+                }
+
+                if rank == target {
+                    return Some(Entry {
+                        node: NonNull::new_unchecked(curr),
+                        _lt: PhantomData,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
     pub fn iter<'a>(&'a self) -> iter::Iter<'a, K, V> {
         iter::Iter::from_list(self)
     }
 
+    /// Returns an iterator over the entries whose keys lie within `range`. It searches for the
+    /// start of the range rather than walking up to it, and stops at its end.
+    pub fn range<'a, R>(&'a self, range: R) -> iter::Range<'a, K, V, R>
+    where
+        R: core::ops::RangeBounds<K>,
+    {
+        iter::Range::from_list(self, range)
+    }
+
+    /// Returns the first entry whose key lies after `bound`.
+    fn lower_bound<'a>(&'a self, bound: core::ops::Bound<&K>) -> Option<Entry<'a, K, V>> {
+        let key = match bound {
+            core::ops::Bound::Unbounded => return self.get_first(),
+            core::ops::Bound::Included(key) | core::ops::Bound::Excluded(key) => key,
+        };
+
+        if self.is_empty() {
+            return None;
+        }
+
+        // # Safety
+        //
+        // The node after the last one before `key` is never the head.
+        let next = unsafe { (*self.find(key).prev[0]).levels[0].load_ptr() };
+        let next = NonNull::new(next).map(|node| Entry {
+            node,
+            _lt: PhantomData,
+        })?;
+
+        match bound {
+            core::ops::Bound::Excluded(key) if next.key == *key => self.next_node(&next),
+            _ => Some(next),
+        }
+    }
+
+    /// Returns an iterator over the entries whose towers reach `level`, in ascending order. Level
+    /// 0 links every entry of the list.
+    ///
+    /// # Panics
+    ///
+    /// If `level` is not below [max_height](Self::max_height).
+    pub fn iter_level<'a>(&'a self, level: usize) -> iter::IterLevel<'a, K, V> {
+        iter::IterLevel::from_list(self, level)
+    }
+
+    /// Writes an ASCII diagram of the list to `w`, with one line per level showing the keys of the
+    /// nodes linked on it.
+    pub fn dump_levels(&self, w: impl std::io::Write) -> std::io::Result<()>
+    where
+        K: core::fmt::Debug,
+    {
+        crate::internal::utils::dump_levels(
+            w,
+            self.iter().map(|e| (format!("{:?}", e.key()), e.height())),
+        )
+    }
+
+    /// Returns a Graphviz description of the nodes and their links on every level.
+    pub fn to_dot(&self) -> String
+    where
+        K: core::fmt::Debug,
+    {
+        let mut dot = crate::internal::utils::Dot::new();
+
+        for level in 0..self.state.max_height.load(Ordering::Relaxed) {
+            let mut prev = None;
+            let mut curr = self.head.as_ptr().cast::<Node<K, V>>();
+
+            // # Safety
+            //
+            // We hold a shared reference to the list, so no node can be unlinked or freed while
+            // we walk over them.
+            unsafe {
+                loop {
+                    let (next, tag) = (*curr).levels[level].load_decomposed();
+
+                    if next.is_null() {
+                        break;
+                    }
+
+                    if level == 0 {
+                        dot.node(
+                            next.addr(),
+                            &(*next).key,
+                            (*next).height(),
+                            (*next).removed(),
+                        );
+                    }
+
+                    dot.link(prev, next.addr(), level, tag != 0);
+
+                    (prev, curr) = (Some(next.addr()), next);
+                }
+            }
+        }
+
+        dot.finish()
+    }
+
+    /// Reports how many nodes are linked on each level and how high their towers are.
+    pub fn stats(&self) -> crate::internal::utils::ListStats {
+        crate::internal::utils::ListStats::from_heights(
+            self.iter().map(|e| e.height()),
+            self.state.max_height.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Checks that the keys on the lowest level are ordered, that every node linked on a level is
+    /// linked on all the levels below, that towers are as high as the levels they are linked on,
+    /// that no linked node is flagged as removed and that the length matches the linked nodes.
+    /// With the `rank` feature, it also checks that every link skips as many nodes as it should.
+    pub fn validate(&self) -> Result<(), crate::internal::utils::InvariantError> {
+        let mut validator = crate::internal::utils::Validator::new();
+        let head = self.head.as_ptr().cast::<Node<K, V>>();
+
+        for level in 0..self.state.height_cap {
+            validator.level(level);
+
+            let mut curr = head;
+
+            // # Safety
+            //
+            // We hold a shared reference to the list, so no node can be unlinked or freed while
+            // we walk over them.
+            unsafe {
+                loop {
+                    let next = (*curr).levels[level].load_ptr();
+
+                    #[cfg(feature = "rank")]
+                    validator.span(next.as_ref(), (*curr).span(level))?;
+
+                    if next.is_null() {
+                        break;
+                    }
+
+                    let prev = (curr != head).then(|| &*curr);
+                    validator.visit(prev, &*next)?;
+
+                    curr = next;
+                }
+            }
+        }
+
+        validator.finish(self.len())
+    }
+
     pub fn iter_mut<'a: 'domain>(&'a mut self) -> iter::IterMut<'a, K, V> {
         iter::IterMut::from_list(self)
     }
@@ -350,6 +885,24 @@ impl<'a, K, V> Entry<'a, K, V> {
     pub fn key(&self) -> &'a K {
         unsafe { &self.node.as_ref().key }
     }
+
+    pub fn key_value(&self) -> (&'a K, &'a V) {
+        (self.key(), self.val())
+    }
+
+    /// The metadata word of the entry: the time its node was created, in nanoseconds since the
+    /// Unix epoch, unless [set_meta](Self::set_meta) stored a word of its own. Replacing the value
+    /// keeps the node, and so the word.
+    #[cfg(feature = "metadata")]
+    pub fn meta(&self) -> u64 {
+        unsafe { self.node.as_ref().meta() }
+    }
+
+    /// Stores `meta` as the metadata word of the entry, such as a tag of the caller's.
+    #[cfg(feature = "metadata")]
+    pub fn set_meta(&self, meta: u64) {
+        unsafe { self.node.as_ref().set_meta(meta) }
+    }
 }
 
 impl<'a, K, V> core::ops::Deref for Entry<'a, K, V> {
@@ -417,14 +970,69 @@ impl<'a, K: Ord, V> Removable<'a, K, V> {
     }
 }
 
-struct SearchResult<'a, K, V> {
-    prev: [&'a Levels<K, V>; HEIGHT],
+struct SearchResult<K, V> {
+    prev: [*mut Node<K, V>; HEIGHT],
+    /// The position of every node in `prev`, counting from 1 just past the head.
+    #[cfg(feature = "rank")]
+    ranks: [usize; HEIGHT],
     target: Option<NonNull<Node<K, V>>>,
 }
 
+/// A node as the walks shared with the concurrent list see it, see
+/// [skiplist_basics](crate::internal::utils::skiplist_basics). Borrowing the list keeps every node
+/// from being unlinked or freed, so there is nothing to protect, and no link leads astray.
+struct NodeRef<'a, K, V> {
+    node: NonNull<Node<K, V>>,
+    _lt: PhantomData<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> NodeRef<'a, K, V> {
+    fn from_raw(ptr: *mut Node<K, V>) -> Self {
+        NodeRef {
+            node: NonNull::new(ptr).expect("the head is never null"),
+            _lt: PhantomData,
+        }
+    }
+
+    fn from_live(link: &MaybeTagged<Node<K, V>>) -> Result<Option<Self>, ()> {
+        Ok(NonNull::new(link.load_ptr()).map(|node| NodeRef {
+            node,
+            _lt: PhantomData,
+        }))
+    }
+
+    fn as_ptr(&self) -> *mut Node<K, V> {
+        self.node.as_ptr()
+    }
+}
+
+impl<'a, K, V> core::ops::Deref for NodeRef<'a, K, V> {
+    type Target = Node<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.node.as_ref() }
+    }
+}
+
+impl<'a, K, V> Clone for NodeRef<'a, K, V> {
+    fn clone(&self) -> Self {
+        NodeRef {
+            node: self.node,
+            _lt: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V> PartialEq for NodeRef<'a, K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::eq(self.node.as_ptr(), other.node.as_ptr())
+    }
+}
+
 pub mod iter {
-    use super::{Entry, MutEntry, SkipList};
-    use core::iter::Iterator;
+    use super::{Entry, MutEntry, Node, SkipList};
+    use core::ops::{Bound, RangeBounds};
+    use core::{iter::Iterator, marker::PhantomData, ptr::NonNull};
 
     pub struct Iter<'a, K, V> {
         list: &'a SkipList<'a, K, V>,
@@ -441,6 +1049,14 @@ pub mod iter {
                 next: list.get_first(),
             }
         }
+
+        /// Advances the iterator to the first entry whose key is no less than `key`, without
+        /// starting over from the head. Does nothing if the next entry already is.
+        pub fn seek(&mut self, key: &K) {
+            if let Some(next) = self.next.take() {
+                self.next = self.list.seek_from(next, key);
+            }
+        }
     }
 
     impl<'a, K, V> Iterator for Iter<'a, K, V>
@@ -459,6 +1075,94 @@ pub mod iter {
         }
     }
 
+    /// An iterator over the entries linked on a single level, see
+    /// [iter_level](SkipList::iter_level).
+    pub struct IterLevel<'a, K, V> {
+        next: *mut Node<K, V>,
+        level: usize,
+        _lt: PhantomData<&'a SkipList<'a, K, V>>,
+    }
+
+    impl<'a, K, V> IterLevel<'a, K, V> {
+        pub fn from_list(list: &'a SkipList<'a, K, V>, level: usize) -> Self {
+            let height = list.max_height();
+            assert!(level < height, "level must be below {}", height);
+
+            IterLevel {
+                next: unsafe { (*list.head.as_ptr()).levels[level].load_ptr() },
+                level,
+                _lt: PhantomData,
+            }
+        }
+    }
+
+    impl<'a, K, V> Iterator for IterLevel<'a, K, V> {
+        type Item = Entry<'a, K, V>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let next = NonNull::new(self.next)?;
+
+            // # Safety
+            //
+            // We borrow the list, so no node can be unlinked or freed while we walk over them.
+            // Every node linked on a level is high enough to have that level.
+            self.next = unsafe { next.as_ref().levels[self.level].load_ptr() };
+
+            Some(Entry {
+                node: next,
+                _lt: PhantomData,
+            })
+        }
+    }
+
+    /// An iterator over the entries whose keys lie within a range, see
+    /// [range](SkipList::range).
+    pub struct Range<'a, K, V, R> {
+        list: &'a SkipList<'a, K, V>,
+        next: Option<Entry<'a, K, V>>,
+        range: R,
+    }
+
+    impl<'a, K, V, R> Range<'a, K, V, R>
+    where
+        K: Ord,
+        R: RangeBounds<K>,
+    {
+        pub fn from_list(list: &'a SkipList<'a, K, V>, range: R) -> Self {
+            Range {
+                list,
+                next: list.lower_bound(range.start_bound()),
+                range,
+            }
+        }
+    }
+
+    impl<'a, K, V, R> Iterator for Range<'a, K, V, R>
+    where
+        K: Ord,
+        R: RangeBounds<K>,
+    {
+        type Item = Entry<'a, K, V>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let next = self.next.take()?;
+
+            let in_range = match self.range.end_bound() {
+                Bound::Included(end) => next.key() <= end,
+                Bound::Excluded(end) => next.key() < end,
+                Bound::Unbounded => true,
+            };
+
+            if !in_range {
+                return None;
+            }
+
+            self.next = self.list.next_node(&next);
+
+            Some(next)
+        }
+    }
+
     pub struct IterMut<'a, K, V> {
         list: &'a SkipList<'a, K, V>,
         next: Option<Entry<'a, K, V>>,
@@ -526,6 +1230,24 @@ pub mod iter {
             IntoIter::from_list(self)
         }
     }
+
+    impl<'a, K, V> From<std::collections::BTreeMap<K, V>> for SkipList<'a, K, V>
+    where
+        K: Ord,
+    {
+        fn from(map: std::collections::BTreeMap<K, V>) -> Self {
+            SkipList::from_sorted_iter(map)
+        }
+    }
+
+    impl<'a, K, V> From<SkipList<'a, K, V>> for std::collections::BTreeMap<K, V>
+    where
+        K: Ord,
+    {
+        fn from(list: SkipList<'a, K, V>) -> Self {
+            list.into_iter().collect()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -568,6 +1290,548 @@ mod skiplist_test {
         }
     }
 
+    #[test]
+    fn test_with_capacity() {
+        let mut list = SkipList::with_capacity(1_000);
+
+        for i in 0..2_000 {
+            list.insert(i, i);
+        }
+
+        let arena = list.arena.as_ref().unwrap();
+        assert!(arena.contains(list.get(&0).unwrap().node.as_ptr()));
+        assert!(!arena.contains(list.get(&1_999).unwrap().node.as_ptr()));
+
+        for i in (0..2_000).step_by(2) {
+            assert_eq!(list.remove(&i), Some((i, i)));
+        }
+
+        assert_eq!(list.len(), 1_000);
+        assert!(list.iter().map(|e| *e.key()).eq((1..2_000).step_by(2)));
+    }
+
+    #[test]
+    fn test_from_sorted_iter() {
+        let list = SkipList::from_sorted_iter([
+            (1, 'a'),
+            (2, 'b'),
+            (4, 'd'),
+            (3, 'c'),
+            (4, 'e'),
+            (5, 'f'),
+        ]);
+
+        assert_eq!(list.len(), 5);
+        assert!(list.iter().map(|e| (*e.key(), *e.val())).eq([
+            (1, 'a'),
+            (2, 'b'),
+            (3, 'c'),
+            (4, 'e'),
+            (5, 'f')
+        ]));
+    }
+
+    #[test]
+    fn test_btreemap_round_trip() {
+        let map = (0..100)
+            .map(|i| (i, i * 2))
+            .collect::<std::collections::BTreeMap<_, _>>();
+
+        let list = SkipList::from(map.clone());
+
+        assert_eq!(list.len(), 100);
+        assert_eq!(std::collections::BTreeMap::from(list), map);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut list = SkipList::new();
+
+        for i in 0..10 {
+            list.insert(i, ());
+        }
+
+        let dot = list.to_dot();
+        let links = |level: usize| dot.matches(&format!("[label=\"{}\"]", level)).count();
+
+        assert_eq!(dot.matches("height").count(), 10);
+        assert_eq!(links(0), 10);
+        assert_eq!(links(1), list.iter().filter(|e| e.height() > 1).count());
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut list = SkipList::new();
+
+        assert_eq!(list.stats().levels, Vec::<usize>::new());
+        assert_eq!(list.stats().search_depth, 0.0);
+
+        for i in 0..1000 {
+            list.insert(i, ());
+        }
+
+        let stats = list.stats();
+
+        assert_eq!(stats.levels[0], 1000);
+        assert_eq!(stats.heights.iter().sum::<usize>(), 1000);
+        assert!(stats.levels.windows(2).all(|w| w[0] >= w[1]));
+        assert!(stats.heights.len() <= stats.max_height);
+        assert!(stats.search_depth > stats.levels.len() as f64);
+        assert!(stats.search_depth < 100.0);
+    }
+
+    #[test]
+    fn test_promotion_probability() {
+        let mut list = SkipList::new();
+
+        assert_eq!(list.max_height(), HEIGHT);
+        assert_eq!(list.promotion_probability(), 0.5);
+
+        list.set_promotion_probability(0.0);
+
+        for i in 0..100 {
+            list.insert(i, ());
+        }
+
+        assert_eq!(list.current_levels_in_use(), 1);
+
+        list.set_promotion_probability(0.9);
+
+        for i in 100..200 {
+            list.insert(i, ());
+        }
+
+        assert!(list.current_levels_in_use() > 4);
+        assert!(list.iter().map(|e| *e.key()).eq(0..200));
+    }
+
+    #[test]
+    fn test_auto_tuning() {
+        let mut list = SkipList::builder().max_height(4).auto_tuning().build();
+
+        assert!(list.auto_tuning());
+
+        // With towers capped at 4 levels, a growing list is better off promoting fewer of them.
+        for i in 0..100_000 {
+            list.insert(i, ());
+        }
+
+        assert_eq!(list.promotion_probability(), 0.0625);
+        assert!(list.needs_optimize());
+
+        list.optimize();
+
+        assert!(!list.needs_optimize());
+        assert_eq!(list.len(), 100_000);
+        assert_eq!(list.validate(), Ok(()));
+        assert!(list.iter().map(|e| *e.key()).eq(0..100_000));
+
+        // The rebuilt towers live up to the expectations.
+        for i in 0..crate::internal::utils::tuning::TUNE_INTERVAL {
+            assert!(list.get(&i).is_some());
+        }
+
+        assert!(!list.needs_optimize());
+    }
+
+    #[test]
+    fn test_builder() {
+        let build = || {
+            let mut list = SkipList::builder()
+                .max_height(6)
+                .probability(0.75)
+                .seed(42)
+                .capacity(100)
+                .build();
+
+            for i in 0..100 {
+                list.insert(i, ());
+            }
+
+            list
+        };
+
+        let (a, b) = (build(), build());
+
+        assert_eq!(a.max_height(), 6);
+        assert_eq!(a.promotion_probability(), 0.75);
+        assert!(a.current_levels_in_use() <= 6);
+        assert!(a
+            .iter()
+            .map(|e| e.height())
+            .eq(b.iter().map(|e| e.height())));
+        assert_eq!(a.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_branching_factor() {
+        let mut list = SkipList::builder().branching_factor(4).seed(3).build();
+
+        for i in 0..10_000 {
+            list.insert(i, ());
+        }
+
+        assert_eq!(list.promotion_probability(), 0.25);
+
+        // About a quarter of the towers reach the second level, a sixteenth the third.
+        let second = list.iter_level(1).count();
+        let third = list.iter_level(2).count();
+
+        assert!(
+            (2_000..3_000).contains(&second),
+            "{} towers on level 1",
+            second
+        );
+        assert!((400..850).contains(&third), "{} towers on level 2", third);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_builder_max_height_out_of_range() {
+        SkipList::<usize, ()>::builder().max_height(33);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_promotion_probability_out_of_range() {
+        SkipList::<usize, ()>::new().set_promotion_probability(1.0);
+    }
+
+    #[test]
+    #[cfg(feature = "stats")]
+    fn test_alloc_stats() {
+        let mut list = SkipList::new();
+
+        for i in 0..10 {
+            list.insert(i, ());
+        }
+
+        list.remove(&3);
+
+        let stats = list.alloc_stats();
+
+        assert_eq!((stats.allocated, stats.retired, stats.freed), (10, 0, 1));
+        assert_eq!(stats.live(), list.len());
+    }
+
+    #[test]
+    #[cfg(feature = "debug-leak-check")]
+    #[should_panic(expected = "the list leaked 1 nodes")]
+    fn test_leak_check() {
+        let mut list = SkipList::new();
+        list.insert(1, ());
+
+        // A node that was allocated, yet never made it into the list.
+        list.state.allocations.allocated();
+    }
+
+    #[test]
+    fn test_insert_with_height() {
+        let mut list = SkipList::new();
+
+        for (key, height) in [(0, 1), (1, 3), (2, 1), (3, 2)] {
+            list.insert_with_height(key, (), height);
+        }
+
+        let mut dump = Vec::new();
+        list.dump_levels(&mut dump).unwrap();
+
+        assert_eq!(
+            String::from_utf8(dump).unwrap(),
+            "L3 | head ------> 1\n\
+             L2 | head ------> 1 ------> 3\n\
+             L1 | head -> 0 -> 1 -> 2 -> 3\n"
+        );
+        assert_eq!(list.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_estimate_range_count() {
+        let mut list = SkipList::new();
+
+        for i in 0..10_000 {
+            list.insert(i, ());
+        }
+
+        // Too few towers reach up for anything but the lowest level.
+        assert_eq!(list.estimate_range_count(3..10), 7);
+        assert_eq!(list.estimate_range_count(20_000..), 0);
+
+        let estimate = list.estimate_range_count(2_500..7_500);
+        assert!((2_500..10_000).contains(&estimate), "{}", estimate);
+    }
+
+    #[test]
+    fn test_estimate_range_count_quarter() {
+        let mut list = SkipList::with_seed(7);
+        list.set_promotion_probability(0.25);
+
+        for i in 0..100_000 {
+            list.insert(i, ());
+        }
+
+        // Every level links about a quarter of the towers below, scaling by powers of 2 would
+        // leave the estimate far too low.
+        let estimate = list.estimate_range_count(25_000..75_000);
+        assert!((25_000..100_000).contains(&estimate), "{}", estimate);
+    }
+
+    #[test]
+    fn test_approx_quantile() {
+        let mut list = SkipList::new();
+
+        assert_eq!(list.approx_quantile(0.5), None);
+
+        for i in 0..10_000 {
+            list.insert(i, ());
+        }
+
+        assert_eq!(list.approx_quantile(0.0), Some(0));
+
+        let median = list.approx_quantile(0.5).unwrap();
+        assert!((2_500..7_500).contains(&median), "{}", median);
+        assert_eq!(list.approx_quantile(1.0), Some(9_999));
+    }
+
+    #[test]
+    fn test_iter_seek() {
+        let mut list = SkipList::new();
+
+        for i in (0..100).step_by(3) {
+            list.insert(i, ());
+        }
+
+        let mut iter = list.iter();
+
+        iter.seek(&10);
+        assert_eq!(iter.next().map(|e| *e.key()), Some(12));
+
+        // Seeking backwards leaves the iterator where it is.
+        iter.seek(&0);
+        assert_eq!(iter.next().map(|e| *e.key()), Some(15));
+
+        iter.seek(&99);
+        assert_eq!(iter.next().map(|e| *e.key()), Some(99));
+
+        iter.seek(&1000);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_range() {
+        let mut list = SkipList::new();
+
+        for i in (0..100).step_by(2) {
+            list.insert(i, ());
+        }
+
+        assert!(list.range(20..30).map(|e| *e.key()).eq((20..30).step_by(2)));
+        assert!(list
+            .range(21..=30)
+            .map(|e| *e.key())
+            .eq((22..=30).step_by(2)));
+        assert!(list.range(..6).map(|e| *e.key()).eq([0, 2, 4]));
+        assert!(list.range(90..).map(|e| *e.key()).eq((90..100).step_by(2)));
+        assert_eq!(list.range(200..).count(), 0);
+
+        let excluded = (
+            core::ops::Bound::Excluded(10),
+            core::ops::Bound::Included(14),
+        );
+        assert!(list.range(excluded).map(|e| *e.key()).eq([12, 14]));
+    }
+
+    #[test]
+    fn test_iter_level() {
+        let mut list = SkipList::new();
+
+        for (key, height) in [(0, 1), (1, 3), (2, 1), (3, 2)] {
+            list.insert_with_height(key, (), height);
+        }
+
+        assert!(list.iter_level(0).map(|e| *e.key()).eq([0, 1, 2, 3]));
+        assert!(list.iter_level(1).map(|e| *e.key()).eq([1, 3]));
+        assert!(list.iter_level(2).map(|e| *e.key()).eq([1]));
+        assert_eq!(list.iter_level(31).count(), 0);
+    }
+
+    #[test]
+    fn test_validate() {
+        use crate::internal::utils::InvariantError;
+
+        let mut list = SkipList::new();
+
+        for i in (0..100).rev() {
+            list.insert(i, ());
+        }
+
+        assert_eq!(list.validate(), Ok(()));
+
+        list.state.len.store(99, Ordering::Relaxed);
+        assert_eq!(
+            list.validate(),
+            Err(InvariantError::Len {
+                len: 99,
+                found: 100
+            })
+        );
+
+        list.state.len.store(100, Ordering::Relaxed);
+        list.get_mut(&50).unwrap().key = 10;
+        assert_eq!(
+            list.validate(),
+            Err(InvariantError::Unordered {
+                level: 0,
+                index: 50
+            })
+        );
+    }
+
+    #[cfg(feature = "rank")]
+    #[test]
+    fn test_rank() {
+        fn check(list: &SkipList<'_, i32, ()>, keys: &std::collections::BTreeSet<i32>) {
+            assert_eq!(list.validate(), Ok(()));
+
+            for (index, key) in keys.iter().enumerate() {
+                assert_eq!(list.rank(key), index);
+                assert_eq!(list.get_by_rank(index).map(|e| *e.key()), Some(*key));
+                // Absent keys rank where they would be inserted.
+                assert_eq!(list.rank(&(key + 1)), keys.range(..key + 1).count());
+            }
+
+            assert!(list.get_by_rank(keys.len()).is_none());
+        }
+
+        let mut list = SkipList::with_seed(7);
+        let mut keys = std::collections::BTreeSet::new();
+
+        for i in 0..500 {
+            let key = (i * 37) % 1_009 * 2;
+            list.insert(key, ());
+            keys.insert(key);
+        }
+
+        check(&list, &keys);
+
+        for key in keys.iter().copied().step_by(3).collect::<Vec<_>>() {
+            list.remove(&key);
+            keys.remove(&key);
+        }
+
+        list.remove_first();
+        keys.pop_first();
+        check(&list, &keys);
+
+        let other = SkipList::from_sorted_iter((0..300).map(|i| (i * 7, ())));
+        check(&other, &(0..300).map(|i| i * 7).collect());
+
+        list.merge_from(&other, |_, _, _| ());
+        keys.extend((0..300).map(|i| i * 7));
+        check(&list, &keys);
+
+        list.set_promotion_probability(0.25);
+        list.optimize();
+        check(&list, &keys);
+    }
+
+    #[test]
+    fn test_compact() {
+        let newer = SkipList::from_sorted_iter([(1, 10), (3, 30)]);
+        let older = SkipList::from_sorted_iter([(0, 0), (1, 1), (2, 2), (3, 3)]);
+
+        let list = SkipList::compact(vec![newer, older], |_, vals| vals[0]);
+
+        assert_eq!(list.len(), 4);
+        assert!(list
+            .iter()
+            .map(|e| (*e.key(), *e.val()))
+            .eq([(0, 0), (1, 10), (2, 2), (3, 30)]));
+    }
+
+    #[test]
+    fn test_merge_from() {
+        let mut list = SkipList::new();
+        let mut other = SkipList::new();
+
+        for i in (0..1_000).step_by(2) {
+            list.insert(i, 1);
+        }
+
+        for i in (0..1_000).step_by(3) {
+            other.insert(i, 2);
+        }
+
+        list.merge_from(&other, |_, ours, theirs| ours + theirs);
+
+        assert!(list.validate().is_ok());
+        assert_eq!(list.len(), 500 + 334 - 167);
+        assert!(list.iter().all(|e| {
+            let key = *e.key();
+            let expected = [(key % 2 == 0, 1), (key % 3 == 0, 2)];
+
+            *e.val()
+                == expected
+                    .iter()
+                    .filter(|(has, _)| *has)
+                    .map(|(_, v)| v)
+                    .sum::<i32>()
+        }));
+
+        // Merging into an empty list copies the other one.
+        let mut empty = SkipList::new();
+        empty.merge_from(&other, |_, _, _| unreachable!());
+        assert!(empty.iter().map(|e| *e.key()).eq((0..1_000).step_by(3)));
+    }
+
+    #[test]
+    fn test_drop_parallel() {
+        let counter = std::sync::Arc::new(());
+        let mut list = SkipList::new();
+
+        for i in 0..100_000 {
+            list.insert(i, counter.clone());
+        }
+
+        list.drop_parallel();
+
+        assert_eq!(std::sync::Arc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_drop_arena() {
+        use crate::internal::utils::freed_at_once_count;
+
+        let mut list = SkipList::with_capacity(1_000);
+
+        for i in 0..500 {
+            list.insert(i, i);
+        }
+
+        assert!(!list.arena.as_ref().unwrap().overflowed());
+
+        let dropped = freed_at_once_count();
+        drop(list);
+        assert_eq!(freed_at_once_count(), dropped + 1);
+
+        // Once the arena overflowed, some nodes live on the heap and are freed one by one.
+        let mut list = SkipList::with_capacity(10);
+
+        for i in 0..500 {
+            list.insert(i, i);
+        }
+
+        drop(list);
+        assert_eq!(freed_at_once_count(), dropped + 1);
+
+        // Just as those that need dropping.
+        let mut list = SkipList::with_capacity(1_000);
+        list.insert(0, String::from("zero"));
+
+        drop(list);
+        assert_eq!(freed_at_once_count(), dropped + 1);
+    }
+
     #[test]
     fn test_rand_height() {
         let mut list: SkipList<'_, i32, i32> = SkipList::new();
@@ -593,15 +1857,15 @@ mod skiplist_test {
 
         list.insert(1, 1);
 
-        list.iter().for_each(|n| println!("k: {}", n.key));
+        list.dump_levels(std::io::stdout()).unwrap();
 
         list.insert(2, 2);
 
-        list.iter().for_each(|n| println!("k: {}", n.key));
+        list.dump_levels(std::io::stdout()).unwrap();
 
         list.insert(5, 3);
 
-        list.iter().for_each(|n| println!("k: {}", n.key));
+        list.dump_levels(std::io::stdout()).unwrap();
     }
 
     #[test]
@@ -632,11 +1896,11 @@ mod skiplist_test {
         list.insert(2, 2);
         list.insert(5, 3);
 
-        list.iter().for_each(|n| println!("k: {}", n.key));
+        list.dump_levels(std::io::stdout()).unwrap();
 
         assert!(list.remove(&1).is_some());
 
-        list.iter().for_each(|n| println!("k: {}", n.key));
+        list.dump_levels(std::io::stdout()).unwrap();
 
         println!("removing 6");
         assert!(list.remove(&6).is_none());
@@ -648,7 +1912,7 @@ mod skiplist_test {
         assert!(list.remove(&2).is_some());
         //list.remove(&2);
 
-        list.iter().for_each(|n| println!("k: {}", n.key));
+        list.dump_levels(std::io::stdout()).unwrap();
 
         assert_eq!(list.len(), 0);
     }
@@ -669,6 +1933,103 @@ mod skiplist_test {
         })
     }
 
+    #[test]
+    fn test_get_key_value() {
+        let mut list = SkipList::new();
+        list.insert(String::from("a"), 1);
+
+        let (key, val) = list.get_key_value(&String::from("a")).unwrap();
+        assert_eq!((key.as_str(), *val), ("a", 1));
+        assert_eq!(list.get_first().unwrap().key_value(), (key, val));
+        assert!(list.get_key_value(&String::from("b")).is_none());
+    }
+
+    #[test]
+    fn test_callbacks() {
+        use std::sync::{Arc, Mutex};
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let (inserts, removals) = (log.clone(), log.clone());
+
+        let mut list = SkipList::builder()
+            .on_insert(move |k: &u8, v: &&str| inserts.lock().unwrap().push(('+', *k, *v)))
+            .on_remove(move |k: &u8, v: &&str| removals.lock().unwrap().push(('-', *k, *v)))
+            .build();
+
+        list.insert(1, "a");
+        list.insert(1, "b");
+        list.insert_conditionally(1, "c");
+        list.remove(&1);
+        list.remove(&1);
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            [('+', 1, "a"), ('-', 1, "a"), ('+', 1, "b"), ('-', 1, "b")]
+        );
+    }
+
+    #[test]
+    fn test_high_water_mark() {
+        let mut list = SkipList::new();
+
+        for i in 0..10 {
+            list.insert(i, ());
+        }
+
+        for i in 0..5 {
+            list.remove(&i);
+        }
+
+        list.insert(0, ());
+        assert_eq!((list.len(), list.high_water_mark()), (6, 10));
+    }
+
+    #[cfg(feature = "occupancy")]
+    #[test]
+    fn test_occupancy() {
+        use crate::internal::utils::{OCCUPANCY_SAMPLES, SAMPLE_INTERVAL};
+
+        let mut list = SkipList::new();
+
+        for i in 0..SAMPLE_INTERVAL * (OCCUPANCY_SAMPLES + 2) {
+            list.insert(i, ());
+        }
+
+        for i in 0..SAMPLE_INTERVAL {
+            list.remove(&i);
+        }
+
+        let occupancy = list.occupancy();
+        assert_eq!(occupancy.len, list.len());
+        assert_eq!(occupancy.levels, list.stats().levels);
+
+        // The oldest samples made room for the newest.
+        let history = list.occupancy_history();
+        assert_eq!(history.len(), OCCUPANCY_SAMPLES);
+        assert_eq!(history.last().unwrap().len, list.len());
+        assert!(history
+            .windows(2)
+            .all(|w| w[0].version + SAMPLE_INTERVAL == w[1].version));
+    }
+
+    #[test]
+    fn test_version() {
+        let mut list = SkipList::new();
+        assert_eq!(list.version(), 0);
+
+        list.insert(1, "a");
+        list.insert(1, "b");
+        assert_eq!(list.version(), 2);
+
+        // Neither misses change the list.
+        list.insert_conditionally(1, "c");
+        list.remove(&2);
+        assert_eq!(list.version(), 2);
+
+        list.remove(&1);
+        assert_eq!(list.version(), 3);
+    }
+
     #[test]
     fn test_get_last() {
         let mut list = SkipList::new();
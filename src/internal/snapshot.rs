@@ -0,0 +1,199 @@
+//! Snapshots of a list, written to and read from any [Write](std::io::Write) or
+//! [Read](std::io::Read).
+//!
+//! A snapshot is the magic `b"SKPYSNP2"` and then every `(key, value)` pair encoded with
+//! [bincode](bincode) as `Some((key, value))`, in ascending key order, closed by a `None`. The
+//! entries are streamed, so a concurrent list is written without first collecting its entries to
+//! learn how many there are. Reading a snapshot back uses the bulk sorted loader, so no entry has
+//! to search for its position.
+
+use std::io::{self, Read, Write};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::skiplist::SkipList;
+use super::sync::SkipList as SyncSkipList;
+
+const MAGIC: &[u8; 8] = b"SKPYSNP2";
+
+pub(super) fn into_io(err: bincode::ErrorKind) -> io::Error {
+    match err {
+        bincode::ErrorKind::Io(err) => err,
+        err => io::Error::new(io::ErrorKind::InvalidData, err),
+    }
+}
+
+/// Writes `entries` to `w`, one at a time, reading the key and value of each with `pair`. Entries
+/// of the concurrent list hand out their key and value only for as long as they live.
+fn write_entries<W, K, V, I, F>(mut w: W, entries: I, pair: F) -> io::Result<()>
+where
+    W: Write,
+    K: Serialize,
+    V: Serialize,
+    I: Iterator,
+    F: Fn(&I::Item) -> (&K, &V),
+{
+    w.write_all(MAGIC)?;
+
+    for entry in entries {
+        bincode::serialize_into(&mut w, &Some(pair(&entry))).map_err(|e| into_io(*e))?;
+    }
+
+    bincode::serialize_into(&mut w, &None::<(&K, &V)>).map_err(|e| into_io(*e))?;
+
+    w.flush()
+}
+
+/// Reads the header of a snapshot and hands its entries to `load`. The first error ends the
+/// entries early and is returned instead of whatever `load` built.
+fn read_entries<R, K, V, L>(
+    mut r: R,
+    load: impl FnOnce(&mut dyn Iterator<Item = (K, V)>) -> L,
+) -> io::Result<L>
+where
+    R: Read,
+    K: DeserializeOwned,
+    V: DeserializeOwned,
+{
+    let mut magic = [0; 8];
+    r.read_exact(&mut magic)?;
+
+    if &magic != MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a skip list snapshot",
+        ));
+    }
+
+    let mut error = None;
+
+    let mut entries = core::iter::from_fn(|| match bincode::deserialize_from(&mut r) {
+        Ok(entry) => entry,
+        Err(err) => {
+            error = Some(into_io(*err));
+            None
+        }
+    });
+
+    let loaded = load(&mut entries);
+
+    match error {
+        Some(err) => Err(err),
+        None => Ok(loaded),
+    }
+}
+
+impl<'domain, K, V> SkipList<'domain, K, V>
+where
+    K: Ord,
+{
+    /// Writes a snapshot of the list to `w`.
+    pub fn write_to<W: Write>(&self, w: W) -> io::Result<()>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        write_entries(w, self.iter(), |e| (e.key(), e.val()))
+    }
+
+    /// Reads a list back from a snapshot written by [write_to](SkipList::write_to).
+    pub fn read_from<R: Read>(r: R) -> io::Result<Self>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        read_entries(r, |entries| SkipList::from_sorted_iter(entries))
+    }
+}
+
+impl<'domain, K, V> SyncSkipList<'domain, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Writes a snapshot of the list to `w`. Concurrent modifications may or may not be part of
+    /// the snapshot.
+    pub fn write_to<W: Write>(&self, w: W) -> io::Result<()>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        write_entries(w, self.iter(), |e| (e.key(), e.val()))
+    }
+
+    /// Reads a list back from a snapshot written by [write_to](SyncSkipList::write_to).
+    pub fn read_from<R: Read>(r: R) -> io::Result<Self>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        read_entries(r, |entries| SyncSkipList::from_sorted_iter(entries))
+    }
+}
+
+#[cfg(test)]
+mod snapshot_test {
+    use super::*;
+
+    #[test]
+    fn test_snapshot() {
+        let mut list = SkipList::new();
+
+        for i in (0..1_000u32).rev() {
+            list.insert(i, format!("{}", i));
+        }
+
+        let mut bytes = Vec::new();
+        list.write_to(&mut bytes).unwrap();
+
+        let read = SkipList::<u32, String>::read_from(bytes.as_slice()).unwrap();
+
+        assert_eq!(read.len(), 1_000);
+        assert!(read
+            .iter()
+            .map(|e| (*e.key(), e.val().clone()))
+            .eq((0..1_000).map(|i| (i, format!("{}", i)))));
+    }
+
+    #[test]
+    fn test_snapshot_sync() {
+        let list = SyncSkipList::new();
+
+        for i in (0..1_000u32).rev() {
+            list.insert(i, i as u64 * 3);
+        }
+
+        let mut bytes = Vec::new();
+        list.write_to(&mut bytes).unwrap();
+
+        let read = SyncSkipList::<u32, u64>::read_from(bytes.as_slice()).unwrap();
+
+        assert_eq!(read.len(), 1_000);
+        assert!(read
+            .iter()
+            .map(|e| (*e.key(), *e.val()))
+            .eq((0..1_000).map(|i| (i, i as u64 * 3))));
+    }
+
+    #[test]
+    fn test_snapshot_truncated() {
+        let list = SyncSkipList::new();
+
+        for i in 0..10u32 {
+            list.insert(i, ());
+        }
+
+        let mut bytes = Vec::new();
+        list.write_to(&mut bytes).unwrap();
+        bytes.truncate(bytes.len() - 2);
+
+        let err = SyncSkipList::<u32, ()>::read_from(bytes.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let err = SyncSkipList::<u32, ()>::read_from(&b"SKPY"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let err = SyncSkipList::<u32, ()>::read_from(&b"SKPYSST1"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
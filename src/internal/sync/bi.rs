@@ -0,0 +1,874 @@
+//! A concurrent skip list whose towers link in both directions. Every level of a node points to
+//! the node following it and to the node preceding it, so the predecessor of an entry is a single
+//! load away, and walking the list backwards costs no more than walking it forwards. The concurrent
+//! [SkipList](super::SkipList) has to search from the head for every step back instead.
+//!
+//! Keeping two links per level consistent without locks is a lot harder than keeping one, so
+//! inserts and removals take turns on a mutex, which suits lists with a single writer. Readers
+//! never take it. They protect the nodes they visit with hazard pointers, just like the entries of
+//! the concurrent list do, so a node the writer removes is only freed once no reader holds it.
+//!
+//! A removed node keeps its links, but the writer stops updating them, so they may point to nodes
+//! that were freed since. The writer marks a node as removed before it unlinks it, and readers
+//! only trust a link they followed once they saw its node still unmarked afterwards. Should it be
+//! marked, they search for their place again by key.
+
+use core::borrow::Borrow;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::ops::Bound;
+use core::ptr::{self, NonNull};
+use core::sync::atomic::Ordering;
+use std::sync::{Mutex, PoisonError};
+
+use haphazard::Domain;
+
+use crate::internal::utils::{
+    atomic::{AtomicBool, AtomicPtr, AtomicUsize},
+    draw_height, Hazard, DEBUG_ENTRIES, DEFAULT_PROMOTION, HEIGHT,
+};
+
+/// The links of a node on one level. A null `left` stands for the head, a null `right` for the
+/// end of the level.
+struct Level<K, V> {
+    left: AtomicPtr<BiNode<K, V>>,
+    right: AtomicPtr<BiNode<K, V>>,
+}
+
+impl<K, V> Level<K, V> {
+    fn new() -> Self {
+        Level {
+            left: AtomicPtr::new(ptr::null_mut()),
+            right: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+}
+
+struct BiNode<K, V> {
+    key: K,
+    val: V,
+    /// Set by the writer before it unlinks the node, from then on its links are left as they are.
+    removed: AtomicBool,
+    levels: Box<[Level<K, V>]>,
+}
+
+/// The state only the writer holding the mutex touches.
+struct Writer {
+    seed: usize,
+    /// The chance to promote a tower by another level, out of `1 << 32`.
+    promotion: usize,
+}
+
+impl Writer {
+    fn gen_height(&mut self) -> usize {
+        draw_height(&mut self.seed, self.promotion, HEIGHT)
+    }
+}
+
+/// A skip list linked in both directions, for a single writer alongside any number of readers.
+/// See the [module](self) documentation.
+pub struct BiSkipList<K, V> {
+    /// The links of the head. On every level, `right` points to the first node and `left` to the
+    /// last one.
+    head: Box<[Level<K, V>]>,
+    /// The number of levels any tower reached so far, where searches start.
+    height: AtomicUsize,
+    len: AtomicUsize,
+    writer: Mutex<Writer>,
+    _nodes: PhantomData<Box<BiNode<K, V>>>,
+}
+
+// # Safety
+//
+// Readers on any thread read keys and values through shared references, and the writer, which may
+// be any thread, drops them or hands them to the hazard pointer domain to drop later.
+unsafe impl<K: Send + Sync, V: Send + Sync> Send for BiSkipList<K, V> {}
+
+unsafe impl<K: Send + Sync, V: Send + Sync> Sync for BiSkipList<K, V> {}
+
+impl<K, V> BiSkipList<K, V> {
+    pub fn new() -> Self {
+        BiSkipList {
+            head: (0..HEIGHT).map(|_| Level::new()).collect(),
+            height: AtomicUsize::new(1),
+            len: AtomicUsize::new(0),
+            writer: Mutex::new(Writer {
+                seed: rand::random::<usize>() | 1,
+                promotion: DEFAULT_PROMOTION,
+            }),
+            _nodes: PhantomData,
+        }
+    }
+
+    /// Creates an empty list drawing the heights of its towers from `seed` rather than a random
+    /// seed, so the same inserts end up with the same towers every time.
+    pub fn with_seed(seed: usize) -> Self {
+        let list = Self::new();
+
+        // The generator never leaves a seed of 0.
+        list.lock_writer().seed = seed.max(1);
+
+        list
+    }
+
+    /// The chance of a new tower to reach one more level, given it reached the one below.
+    pub fn promotion_probability(&self) -> f64 {
+        self.lock_writer().promotion as f64 / (1u64 << 32) as f64
+    }
+
+    /// Sets the chance of a new tower to reach one more level, which is `0.5` by default, just as
+    /// the concurrent list does. Only towers built from now on are affected.
+    ///
+    /// # Panics
+    ///
+    /// If `p` does not lie within `0.0..1.0`.
+    pub fn set_promotion_probability(&self, p: f64) {
+        assert!((0.0..1.0).contains(&p), "p must lie within 0.0..1.0");
+
+        self.lock_writer().promotion = (p * (1u64 << 32) as f64) as usize;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The links of `node` on `level`, or of the head if `node` is null.
+    ///
+    /// # Safety
+    ///
+    /// `node` is null or points to a node no one freed yet.
+    unsafe fn level(&self, node: *const BiNode<K, V>, level: usize) -> &Level<K, V> {
+        match node.as_ref() {
+            Some(node) => &node.levels[level],
+            None => &self.head[level],
+        }
+    }
+
+    /// Protects the node `link` points to, which is a link of `from`, or of the head if `from` is
+    /// `None`. Fails if `from` was removed by the time the node is protected, as it may have been
+    /// freed before.
+    fn protect<'a>(
+        &'a self,
+        link: &AtomicPtr<BiNode<K, V>>,
+        from: Option<&Entry<'a, K, V>>,
+    ) -> Result<Option<Entry<'a, K, V>>, ()> {
+        let mut _hazard = Hazard::new();
+        let mut ptr = link.load(Ordering::SeqCst);
+
+        while !ptr.is_null() {
+            _hazard.protect_raw(ptr);
+
+            let again = link.load(Ordering::SeqCst);
+
+            if again == ptr {
+                break;
+            }
+
+            ptr = again;
+        }
+
+        // As long as `from` is not removed, the writer keeps its links pointing to nodes that are
+        // not removed either, so the node was still around when we protected it.
+        if from.is_some_and(|from| from.node().removed.load(Ordering::SeqCst)) {
+            return Err(());
+        }
+
+        Ok(NonNull::new(ptr).map(|node| Entry {
+            node,
+            _hazard,
+            list: self,
+        }))
+    }
+
+    /// The node following `from` on `level`, or the first one if `from` is `None`.
+    fn next_on<'a>(
+        &'a self,
+        from: Option<&Entry<'a, K, V>>,
+        level: usize,
+    ) -> Result<Option<Entry<'a, K, V>>, ()> {
+        let node = from.map_or(ptr::null(), |from| from.node.as_ptr());
+
+        // # Safety
+        //
+        // `from` protects its node.
+        self.protect(&unsafe { self.level(node, level) }.right, from)
+    }
+
+    /// The node preceding `from` on `level`, or the last one if `from` is `None`.
+    fn prev_on<'a>(
+        &'a self,
+        from: Option<&Entry<'a, K, V>>,
+        level: usize,
+    ) -> Result<Option<Entry<'a, K, V>>, ()> {
+        let node = from.map_or(ptr::null(), |from| from.node.as_ptr());
+
+        // # Safety
+        //
+        // `from` protects its node.
+        self.protect(&unsafe { self.level(node, level) }.left, from)
+    }
+
+    /// Returns the last entry whose key is `before` the one searched for, or `None` if there is
+    /// none. `before` has to hold for every key up to some point and for none after it.
+    fn search<'a, F>(&'a self, before: F) -> Option<Entry<'a, K, V>>
+    where
+        F: Fn(&K) -> bool,
+    {
+        'search: loop {
+            let mut curr = None;
+
+            for level in (0..self.height.load(Ordering::SeqCst)).rev() {
+                loop {
+                    let Ok(next) = self.next_on(curr.as_ref(), level) else {
+                        continue 'search;
+                    };
+
+                    match next {
+                        Some(next) if before(next.key()) => curr = Some(next),
+                        _ => break,
+                    }
+                }
+            }
+
+            return curr;
+        }
+    }
+
+    /// Returns the first entry whose key is not `before` the one searched for.
+    fn search_after<'a, F>(&'a self, before: F) -> Option<Entry<'a, K, V>>
+    where
+        F: Fn(&K) -> bool,
+    {
+        loop {
+            let prev = self.search(&before);
+
+            if let Ok(next) = self.next_on(prev.as_ref(), 0) {
+                return next;
+            }
+        }
+    }
+
+    /// Returns the entry of `key`, if it is in the list.
+    pub fn get<'a, Q>(&'a self, key: &Q) -> Option<Entry<'a, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.search(|k| k.borrow() <= key)
+            .filter(|entry| entry.key().borrow() == key)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    pub fn get_first<'a>(&'a self) -> Option<Entry<'a, K, V>> {
+        self.next_on(None, 0).unwrap_or_else(|()| unreachable!("the head is never removed"))
+    }
+
+    /// Returns the entry with the greatest key, which the head links to directly.
+    pub fn get_last<'a>(&'a self) -> Option<Entry<'a, K, V>> {
+        self.prev_on(None, 0).unwrap_or_else(|()| unreachable!("the head is never removed"))
+    }
+
+    /// Returns the first entry whose key lies above `bound`.
+    pub fn lower_bound<'a, Q>(&'a self, bound: Bound<&Q>) -> Option<Entry<'a, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match bound {
+            Bound::Included(key) => self.search_after(|k| k.borrow() < key),
+            Bound::Excluded(key) => self.search_after(|k| k.borrow() <= key),
+            Bound::Unbounded => self.get_first(),
+        }
+    }
+
+    /// Returns the last entry whose key lies below `bound`.
+    pub fn upper_bound<'a, Q>(&'a self, bound: Bound<&Q>) -> Option<Entry<'a, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match bound {
+            Bound::Included(key) => self.search(|k| k.borrow() <= key),
+            Bound::Excluded(key) => self.search(|k| k.borrow() < key),
+            Bound::Unbounded => self.get_last(),
+        }
+    }
+
+    /// Returns an iterator over the entries in ascending order of their keys, which walks just as
+    /// cheaply in descending order from the back.
+    pub fn iter<'a>(&'a self) -> Iter<'a, K, V> {
+        Iter {
+            list: self,
+            front: None,
+            back: None,
+            finished: false,
+        }
+    }
+
+    fn lock_writer(&self) -> std::sync::MutexGuard<'_, Writer> {
+        // A writer that panicked did so comparing keys, before it changed any links.
+        self.writer.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Marks `node` as removed and takes it out of every level, then retires it.
+    ///
+    /// # Safety
+    ///
+    /// The caller holds the writer lock, and `node` is linked.
+    unsafe fn unlink(&self, node: *mut BiNode<K, V>)
+    where
+        K: Send,
+        V: Send,
+    {
+        (*node).removed.store(true, Ordering::SeqCst);
+
+        for (i, level) in (*node).levels.iter().enumerate() {
+            let (left, right) = (
+                level.left.load(Ordering::SeqCst),
+                level.right.load(Ordering::SeqCst),
+            );
+
+            self.level(left, i).right.store(right, Ordering::SeqCst);
+            self.level(right, i).left.store(left, Ordering::SeqCst);
+        }
+
+        self.len.fetch_sub(1, Ordering::Relaxed);
+
+        Domain::global().retire_ptr::<BiNode<K, V>, Box<BiNode<K, V>>>(node);
+    }
+}
+
+impl<K, V> BiSkipList<K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Inserts a value in the list given a key. If the key is already in the list, a new node takes
+    /// the place of the old one on every level, so readers find one or the other throughout, and
+    /// the entry of the old value is returned.
+    pub fn insert<'a>(&'a self, key: K, val: V) -> Option<Entry<'a, K, V>> {
+        let mut writer = self.lock_writer();
+
+        // # Safety
+        //
+        // Only we unlink and free nodes, so every node we reach stays around while we hold the
+        // lock.
+        unsafe {
+            let mut prev = [ptr::null_mut::<BiNode<K, V>>(); HEIGHT];
+            let mut curr = ptr::null_mut::<BiNode<K, V>>();
+
+            for level in (0..self.height.load(Ordering::SeqCst)).rev() {
+                loop {
+                    let next = self.level(curr, level).right.load(Ordering::SeqCst);
+
+                    match next.as_ref() {
+                        Some(next) if next.key < key => curr = next as *const _ as *mut _,
+                        _ => break,
+                    }
+                }
+
+                prev[level] = curr;
+            }
+
+            let found = self.level(prev[0], 0).right.load(Ordering::SeqCst);
+
+            if let Some(old) = found.as_ref().filter(|old| old.key == key) {
+                return Some(self.replace(found, old.levels.len(), key, val));
+            }
+
+            let height = writer.gen_height();
+            let node = Self::alloc(key, val, height);
+
+            if height > self.height.load(Ordering::SeqCst) {
+                self.height.store(height, Ordering::SeqCst);
+            }
+
+            // The node is fully linked to its neighbours before they link to it, bottom up.
+            for (i, level) in (*node).levels.iter().enumerate() {
+                let right = self.level(prev[i], i).right.load(Ordering::SeqCst);
+
+                level.left.store(prev[i], Ordering::SeqCst);
+                level.right.store(right, Ordering::SeqCst);
+
+                self.level(right, i).left.store(node, Ordering::SeqCst);
+                self.level(prev[i], i).right.store(node, Ordering::SeqCst);
+            }
+
+            self.len.fetch_add(1, Ordering::Relaxed);
+
+            None
+        }
+    }
+
+    /// Links a new node in place of `old`, returning the entry of `old`.
+    ///
+    /// # Safety
+    ///
+    /// The caller holds the writer lock, and `old` is linked and `height` levels high.
+    unsafe fn replace<'a>(
+        &'a self,
+        old: *mut BiNode<K, V>,
+        height: usize,
+        key: K,
+        val: V,
+    ) -> Entry<'a, K, V> {
+        let node = Self::alloc(key, val, height);
+        let entry = Entry::protect(self, old);
+
+        (*old).removed.store(true, Ordering::SeqCst);
+
+        for (i, (level, old)) in (*node).levels.iter().zip((*old).levels.iter()).enumerate() {
+            let (left, right) = (
+                old.left.load(Ordering::SeqCst),
+                old.right.load(Ordering::SeqCst),
+            );
+
+            level.left.store(left, Ordering::SeqCst);
+            level.right.store(right, Ordering::SeqCst);
+
+            self.level(right, i).left.store(node, Ordering::SeqCst);
+            self.level(left, i).right.store(node, Ordering::SeqCst);
+        }
+
+        Domain::global().retire_ptr::<BiNode<K, V>, Box<BiNode<K, V>>>(old);
+
+        entry
+    }
+
+    fn alloc(key: K, val: V, height: usize) -> *mut BiNode<K, V> {
+        Box::into_raw(Box::new(BiNode {
+            key,
+            val,
+            removed: AtomicBool::new(false),
+            levels: (0..height).map(|_| Level::new()).collect(),
+        }))
+    }
+
+    /// Removes `key` from the list, returning its entry if it was in the list.
+    pub fn remove<'a, Q>(&'a self, key: &Q) -> Option<Entry<'a, K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let _writer = self.lock_writer();
+
+        // Only we remove nodes, so the entry we find stays linked until we unlink it.
+        let entry = self.get(key)?;
+
+        // # Safety
+        //
+        // We hold the lock and the node is linked.
+        unsafe { self.unlink(entry.node.as_ptr()) };
+
+        Some(entry)
+    }
+
+    pub fn pop_front<'a>(&'a self) -> Option<Entry<'a, K, V>> {
+        let _writer = self.lock_writer();
+        let entry = self.get_first()?;
+
+        unsafe { self.unlink(entry.node.as_ptr()) };
+
+        Some(entry)
+    }
+
+    pub fn pop_back<'a>(&'a self) -> Option<Entry<'a, K, V>> {
+        let _writer = self.lock_writer();
+        let entry = self.get_last()?;
+
+        unsafe { self.unlink(entry.node.as_ptr()) };
+
+        Some(entry)
+    }
+}
+
+impl<K, V> Default for BiSkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Frees every node still linked. Nodes removed before are freed by the hazard pointer domain.
+impl<K, V> Drop for BiSkipList<K, V> {
+    fn drop(&mut self) {
+        let mut node = self.head[0].right.load(Ordering::SeqCst);
+
+        while !node.is_null() {
+            // # Safety
+            //
+            // No entry outlives the list, so we own every node that is still linked.
+            let next = unsafe { Box::from_raw(node) }.levels[0]
+                .right
+                .load(Ordering::SeqCst);
+
+            node = next;
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for BiSkipList<K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let list = BiSkipList::new();
+
+        for (key, val) in iter {
+            list.insert(key, val);
+        }
+
+        list
+    }
+}
+
+/// Prints the entries of the list as a map, eliding all but the first
+/// [DEBUG_ENTRIES](crate::internal::utils::DEBUG_ENTRIES).
+impl<K: Ord + Debug, V: Debug> Debug for BiSkipList<K, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut map = f.debug_map();
+        let mut entries = self.iter();
+
+        for entry in entries.by_ref().take(DEBUG_ENTRIES) {
+            map.entry(entry.key(), entry.val());
+        }
+
+        if entries.next().is_some() {
+            map.finish_non_exhaustive()
+        } else {
+            map.finish()
+        }
+    }
+}
+
+/// An entry of a [BiSkipList], whose node it keeps protected. It stays readable even if the entry
+/// is removed in the meantime.
+pub struct Entry<'a, K, V> {
+    node: NonNull<BiNode<K, V>>,
+    _hazard: Hazard<'a>,
+    list: &'a BiSkipList<K, V>,
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    /// Protects `node`, which has to stay around until this returns.
+    fn protect(list: &'a BiSkipList<K, V>, node: *mut BiNode<K, V>) -> Self {
+        let mut _hazard = Hazard::new();
+        _hazard.protect_raw(node);
+
+        Entry {
+            // # Safety
+            //
+            // Only linked nodes are ever protected this way.
+            node: unsafe { NonNull::new_unchecked(node) },
+            _hazard,
+            list,
+        }
+    }
+
+    fn node(&self) -> &BiNode<K, V> {
+        // # Safety
+        //
+        // Our hazard pointer keeps the node from being freed.
+        unsafe { self.node.as_ref() }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.node().key
+    }
+
+    pub fn val(&self) -> &V {
+        &self.node().val
+    }
+
+    pub fn key_value(&self) -> (&K, &V) {
+        (self.key(), self.val())
+    }
+
+    /// Whether the entry was removed from the list since it was read.
+    pub fn is_removed(&self) -> bool {
+        self.node().removed.load(Ordering::SeqCst)
+    }
+
+    /// Returns the entry following this one. Unless this entry was removed, this follows a single
+    /// link.
+    pub fn next(&self) -> Option<Entry<'a, K, V>>
+    where
+        K: Ord,
+    {
+        self.list
+            .next_on(Some(self), 0)
+            .unwrap_or_else(|()| self.list.lower_bound(Bound::Excluded(self.key())))
+    }
+
+    /// Returns the entry preceding this one. Unless this entry was removed, this follows a single
+    /// link.
+    pub fn prev(&self) -> Option<Entry<'a, K, V>>
+    where
+        K: Ord,
+    {
+        self.list
+            .prev_on(Some(self), 0)
+            .unwrap_or_else(|()| self.list.upper_bound(Bound::Excluded(self.key())))
+    }
+
+    /// Removes the entry from the list without searching for it. Returns `None` if it was removed
+    /// already.
+    pub fn remove(self) -> Option<Entry<'a, K, V>>
+    where
+        K: Send,
+        V: Send,
+    {
+        let _writer = self.list.lock_writer();
+
+        if self.is_removed() {
+            return None;
+        }
+
+        // # Safety
+        //
+        // We hold the lock and the node is still linked.
+        unsafe { self.list.unlink(self.node.as_ptr()) };
+
+        Some(self)
+    }
+}
+
+// # Safety
+//
+// The hazard pointer of an entry lives in the global domain and protects the node no matter which
+// thread holds it, just as for the entries of the concurrent list.
+unsafe impl<'a, K: Send + Sync, V: Send + Sync> Send for Entry<'a, K, V> {}
+
+unsafe impl<'a, K: Send + Sync, V: Send + Sync> Sync for Entry<'a, K, V> {}
+
+impl<'a, K, V> Clone for Entry<'a, K, V> {
+    fn clone(&self) -> Self {
+        Entry::protect(self.list, self.node.as_ptr())
+    }
+}
+
+impl<'a, K: Debug, V: Debug> Debug for Entry<'a, K, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Entry")
+            .field("key", self.key())
+            .field("val", self.val())
+            .finish()
+    }
+}
+
+/// Iterates over the entries of a [BiSkipList] from both ends, see [iter](BiSkipList::iter). The
+/// two ends stop once they meet.
+pub struct Iter<'a, K, V> {
+    list: &'a BiSkipList<K, V>,
+    /// The entries each end yielded last.
+    front: Option<Entry<'a, K, V>>,
+    back: Option<Entry<'a, K, V>>,
+    finished: bool,
+}
+
+impl<'a, K: Ord, V> Iterator for Iter<'a, K, V> {
+    type Item = Entry<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let next = match &self.front {
+            Some(front) => front.next(),
+            None => self.list.get_first(),
+        };
+
+        let before_back = |next: &Entry<'a, K, V>| {
+            self.back.as_ref().is_none_or(|back| next.key() < back.key())
+        };
+
+        match next.filter(before_back) {
+            Some(next) => {
+                self.front = Some(next.clone());
+                Some(next)
+            }
+            None => {
+                self.finished = true;
+                None
+            }
+        }
+    }
+}
+
+impl<'a, K: Ord, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let prev = match &self.back {
+            Some(back) => back.prev(),
+            None => self.list.get_last(),
+        };
+
+        let after_front = |prev: &Entry<'a, K, V>| {
+            self.front.as_ref().is_none_or(|front| prev.key() > front.key())
+        };
+
+        match prev.filter(after_front) {
+            Some(prev) => {
+                self.back = Some(prev.clone());
+                Some(prev)
+            }
+            None => {
+                self.finished = true;
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod bi_test {
+    use super::*;
+
+    #[test]
+    fn test_insert_get() {
+        let list = BiSkipList::new();
+
+        for i in (0..100).rev() {
+            assert!(list.insert(i, i * 2).is_none());
+        }
+
+        assert_eq!(list.len(), 100);
+        assert_eq!(list.get(&42).map(|e| *e.val()), Some(84));
+        assert!(list.get(&100).is_none());
+
+        // Replacing keeps the length, and hands back the old value.
+        assert_eq!(list.insert(42, 0).map(|e| *e.val()), Some(84));
+        assert_eq!(list.get(&42).map(|e| *e.val()), Some(0));
+        assert_eq!(list.len(), 100);
+
+        assert!(list.iter().map(|e| *e.key()).eq(0..100));
+    }
+
+    #[test]
+    fn test_heights() {
+        let heights = |list: &BiSkipList<usize, ()>| {
+            (0..200).for_each(|i| drop(list.insert(i, ())));
+            list.height.load(Ordering::Relaxed)
+        };
+
+        assert_eq!(heights(&BiSkipList::with_seed(7)), heights(&BiSkipList::with_seed(7)));
+
+        let flat = BiSkipList::new();
+        flat.set_promotion_probability(0.0);
+        assert_eq!(heights(&flat), 1);
+
+        let tall = BiSkipList::new();
+        tall.set_promotion_probability(0.9);
+        assert!(heights(&tall) > 8);
+    }
+
+    #[test]
+    fn test_both_directions() {
+        let list = (0..100).map(|i| (i, ())).collect::<BiSkipList<_, _>>();
+
+        assert!(list.iter().rev().map(|e| *e.key()).eq((0..100).rev()));
+        assert_eq!(list.get_last().map(|e| *e.key()), Some(99));
+
+        let entry = list.get(&50).unwrap();
+        assert_eq!(entry.prev().map(|e| *e.key()), Some(49));
+        assert_eq!(entry.next().map(|e| *e.key()), Some(51));
+        assert!(list.get_first().unwrap().prev().is_none());
+        assert!(list.get_last().unwrap().next().is_none());
+
+        // The two ends meet in the middle.
+        let mut iter = list.iter();
+        let mut keys = Vec::new();
+
+        while let (Some(front), Some(back)) = (iter.next(), iter.next_back()) {
+            keys.push(*front.key());
+            keys.push(*back.key());
+        }
+
+        keys.sort();
+        assert!(keys.into_iter().eq(0..100));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let list = (0..10).map(|i| (i * 2, ())).collect::<BiSkipList<_, _>>();
+
+        assert_eq!(list.lower_bound(Bound::Included(&4)).map(|e| *e.key()), Some(4));
+        assert_eq!(list.lower_bound(Bound::Excluded(&4)).map(|e| *e.key()), Some(6));
+        assert_eq!(list.lower_bound(Bound::Included(&5)).map(|e| *e.key()), Some(6));
+        assert!(list.lower_bound(Bound::Excluded(&18)).is_none());
+        assert_eq!(list.upper_bound(Bound::Included(&4)).map(|e| *e.key()), Some(4));
+        assert_eq!(list.upper_bound(Bound::Excluded(&4)).map(|e| *e.key()), Some(2));
+        assert!(list.upper_bound(Bound::Excluded(&0)).is_none());
+    }
+
+    #[test]
+    fn test_remove() {
+        let list = (0..10).map(|i| (i, ())).collect::<BiSkipList<_, _>>();
+
+        assert_eq!(list.remove(&5).map(|e| *e.key()), Some(5));
+        assert!(list.remove(&5).is_none());
+        assert_eq!(list.pop_front().map(|e| *e.key()), Some(0));
+        assert_eq!(list.pop_back().map(|e| *e.key()), Some(9));
+        assert_eq!(list.len(), 7);
+
+        // A removed entry still finds its way back into the list.
+        let entry = list.get(&4).unwrap();
+        assert!(entry.clone().remove().is_some());
+        assert!(entry.is_removed());
+        assert!(entry.clone().remove().is_none());
+        assert_eq!(entry.prev().map(|e| *e.key()), Some(3));
+        assert_eq!(entry.next().map(|e| *e.key()), Some(6));
+
+        assert!(list.iter().map(|e| *e.key()).eq([1, 2, 3, 6, 7, 8]));
+        assert!(list.iter().rev().map(|e| *e.key()).eq([8, 7, 6, 3, 2, 1]));
+    }
+
+    #[test]
+    fn test_concurrent_readers() {
+        let list = BiSkipList::new();
+
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                for round in 0..20 {
+                    for i in 0..200 {
+                        list.insert(i, round);
+                    }
+
+                    for i in (0..200).step_by(3) {
+                        list.remove(&i);
+                    }
+                }
+            });
+
+            for _ in 0..3 {
+                s.spawn(|| {
+                    for _ in 0..50 {
+                        let forward = list.iter().map(|e| *e.key()).collect::<Vec<_>>();
+                        let backward = list.iter().rev().map(|e| *e.key()).collect::<Vec<_>>();
+
+                        assert!(forward.windows(2).all(|w| w[0] < w[1]));
+                        assert!(backward.windows(2).all(|w| w[0] > w[1]));
+                    }
+                });
+            }
+        });
+
+        assert_eq!(list.len(), 200 - (0..200).step_by(3).count());
+        assert!(list.iter().rev().map(|e| *e.key()).eq((0..200).rev().filter(|i| i % 3 != 0)));
+    }
+}
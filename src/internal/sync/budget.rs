@@ -0,0 +1,194 @@
+//! Iterating a list while holding on to a bounded number of hazard pointers, so a scanner that
+//! keeps its entries around cannot pin an unbounded amount of retired memory.
+
+use core::fmt::Debug;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::{Entry, Iter, SkipList};
+
+/// The hazard pointers an [Entry](Entry) holds, one for its node and one for its value.
+const ENTRY_HAZARDS: usize = 2;
+
+/// An iterator that never holds more than a budget of hazard pointers, counting those of the
+/// entries it yielded that are still alive, see [iter_budgeted](SkipList::iter_budgeted).
+pub struct BudgetedIter<'a, K, V> {
+    iter: Iter<'a, K, V>,
+    held: Arc<AtomicUsize>,
+    budget: usize,
+}
+
+/// An entry yielded by a [BudgetedIter](BudgetedIter). Once the iterator is out of budget, it
+/// copies the key and value out of the list instead of protecting them.
+pub struct Protected<'a, K, V>(Inner<'a, K, V>);
+
+enum Inner<'a, K, V> {
+    Entry { entry: Entry<'a, K, V>, _held: Held },
+    Copied(K, V),
+}
+
+/// Gives the hazard pointers of an entry back to the budget of its iterator.
+struct Held(Arc<AtomicUsize>);
+
+impl Drop for Held {
+    fn drop(&mut self) {
+        self.0.fetch_sub(ENTRY_HAZARDS, Ordering::Release);
+    }
+}
+
+impl<'domain, K, V> SkipList<'domain, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Iterates the list just as [iter](Self::iter) does, but holds on to at most `budget` hazard
+    /// pointers, its own cursor included. Every entry it yields protects two of them for as long
+    /// as it is alive, so once the entries kept around use up the budget, the iterator copies the
+    /// keys and values out and lets go of the nodes right away.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `budget` does not leave room for the cursor and a single entry.
+    pub fn iter_budgeted<'a>(&'a self, budget: usize) -> BudgetedIter<'a, K, V>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        assert!(
+            budget >= 2 * ENTRY_HAZARDS,
+            "budget must be at least {}",
+            2 * ENTRY_HAZARDS
+        );
+
+        BudgetedIter {
+            iter: self.iter(),
+            held: Arc::new(AtomicUsize::new(0)),
+            budget,
+        }
+    }
+}
+
+impl<'a, K, V> BudgetedIter<'a, K, V> {
+    /// The hazard pointers the entries yielded so far still hold, leaving out the cursor.
+    pub fn held(&self) -> usize {
+        self.held.load(Ordering::Acquire)
+    }
+
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+}
+
+impl<'a, K, V> Iterator for BudgetedIter<'a, K, V>
+where
+    K: Ord + Send + Clone,
+    V: Send + Clone,
+{
+    type Item = Protected<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.iter.next()?;
+
+        // The cursor protects the entry after this one.
+        if self.held() + 2 * ENTRY_HAZARDS > self.budget {
+            return Some(Protected(Inner::Copied(
+                entry.key().clone(),
+                entry.val().clone(),
+            )));
+        }
+
+        self.held.fetch_add(ENTRY_HAZARDS, Ordering::AcqRel);
+
+        Some(Protected(Inner::Entry {
+            entry,
+            _held: Held(self.held.clone()),
+        }))
+    }
+}
+
+impl<'a, K, V> Protected<'a, K, V> {
+    pub fn key(&self) -> &K {
+        match &self.0 {
+            Inner::Entry { entry, .. } => entry.key(),
+            Inner::Copied(key, _) => key,
+        }
+    }
+
+    pub fn val(&self) -> &V {
+        match &self.0 {
+            Inner::Entry { entry, .. } => entry.val(),
+            Inner::Copied(_, val) => val,
+        }
+    }
+
+    pub fn key_value(&self) -> (&K, &V) {
+        (self.key(), self.val())
+    }
+
+    /// The entry in the list, unless the key and value were copied out of it.
+    pub fn entry(&self) -> Option<&Entry<'a, K, V>> {
+        match &self.0 {
+            Inner::Entry { entry, .. } => Some(entry),
+            Inner::Copied(..) => None,
+        }
+    }
+
+    pub fn is_copied(&self) -> bool {
+        matches!(self.0, Inner::Copied(..))
+    }
+}
+
+impl<'a, K, V> Debug for Protected<'a, K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Protected")
+            .field("key", self.key())
+            .field("val", self.val())
+            .field("copied", &self.is_copied())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod budget_test {
+    use super::*;
+
+    #[test]
+    fn test_iter_budgeted() {
+        let list = SkipList::new();
+
+        for i in 0..10 {
+            list.insert(i, i * 10);
+        }
+
+        // Room for the cursor and two entries.
+        let mut iter = list.iter_budgeted(6);
+        let kept = iter.by_ref().take(5).collect::<Vec<_>>();
+
+        assert_eq!(
+            kept.iter().map(|p| p.is_copied()).collect::<Vec<_>>(),
+            [false, false, true, true, true]
+        );
+        assert!(kept.iter().map(|p| *p.key()).eq(0..5));
+        assert_eq!(kept[3].val(), &30);
+        assert_eq!(iter.held(), 4);
+
+        // Letting go of the entries frees up the budget again.
+        drop(kept);
+        assert_eq!(iter.held(), 0);
+
+        let next = iter.next().unwrap();
+        assert!(next.entry().is_some_and(|e| *e.key() == 5));
+        assert_eq!(iter.count(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_iter_budgeted_too_small() {
+        let list = SkipList::<usize, usize>::new();
+        list.iter_budgeted(3);
+    }
+}
@@ -0,0 +1,140 @@
+//! Moving through a list from entry to entry in both directions.
+
+use core::ops::Bound;
+
+use super::{Entry, SkipList};
+
+/// A position in a list, from which it can move to the entries around it, see
+/// [cursor](SkipList::cursor). A cursor either points at an entry, whose node it keeps protected,
+/// or at no entry at all, in front of the first and behind the last. Moving on from there wraps
+/// around to the other end, as the cursors of [LinkedList](std::collections::LinkedList) do.
+pub struct Cursor<'a, K, V> {
+    list: &'a SkipList<'a, K, V>,
+    curr: Option<Entry<'a, K, V>>,
+}
+
+impl<'domain, K, V> SkipList<'domain, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Returns a cursor pointing at no entry, so that [next](Cursor::next) moves it to the first
+    /// and [prev](Cursor::prev) to the last.
+    pub fn cursor<'a>(&'a self) -> Cursor<'a, K, V> {
+        Cursor {
+            list: self,
+            curr: None,
+        }
+    }
+}
+
+impl<'a, K, V> Cursor<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Returns a cursor pointing at `entry`, picking up from an entry a lookup returned without
+    /// searching for it again.
+    pub fn from_entry(list: &'a SkipList<'a, K, V>, entry: Entry<'a, K, V>) -> Self {
+        Cursor {
+            list,
+            curr: Some(entry),
+        }
+    }
+
+    /// The entry the cursor points at. It stays readable even if it is removed in the meantime.
+    pub fn current(&self) -> Option<&Entry<'a, K, V>> {
+        self.curr.as_ref()
+    }
+
+    /// Moves to the first entry whose key is no less than `key`. When moving forward, this goes
+    /// on from the current entry rather than starting over from the head.
+    pub fn seek(&mut self, key: &K) -> Option<&Entry<'a, K, V>> {
+        self.curr = match self.curr.take() {
+            Some(curr) if curr.key() < key => self.list.seek_from(curr, key),
+            _ => self.list.lower_bound(Bound::Included(key)),
+        };
+
+        self.curr.as_ref()
+    }
+
+    /// Moves to the entry following the current one, or to the first one if the cursor points at
+    /// no entry. Should the current entry have been removed, the cursor moves to the entry that
+    /// now follows its key.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&Entry<'a, K, V>> {
+        self.curr = match self.curr.take() {
+            Some(curr) => self.list.next_node(&curr),
+            None => self.list.get_first(),
+        };
+
+        self.curr.as_ref()
+    }
+
+    /// Moves to the entry preceding the current one, or to the last one if the cursor points at
+    /// no entry. Nodes link to their successors only, so this searches for the current key from
+    /// the head and takes logarithmic rather than constant time.
+    pub fn prev(&mut self) -> Option<&Entry<'a, K, V>> {
+        self.curr = match self.curr.take() {
+            Some(curr) => self.list.upper_bound(Bound::Excluded(curr.key())),
+            None => self.list.get_last(),
+        };
+
+        self.curr.as_ref()
+    }
+
+    /// Removes the current entry and moves on to the entry following it. Returns the removed
+    /// entry, or `None` if the cursor points at no entry or someone else removed it first.
+    pub fn remove(&mut self) -> Option<Entry<'a, K, V>> {
+        let curr = self.curr.take()?;
+        self.curr = self.list.next_node(&curr);
+
+        if curr.removed() {
+            return None;
+        }
+
+        self.list.remove(curr.key())
+    }
+}
+
+#[cfg(test)]
+mod cursor_test {
+    use super::*;
+
+    #[test]
+    fn test_cursor() {
+        let list = SkipList::new();
+
+        for i in 0..10 {
+            list.insert(i, ());
+        }
+
+        let mut cursor = list.cursor();
+        assert!(cursor.current().is_none());
+        assert_eq!(cursor.next().map(|e| *e.key()), Some(0));
+        assert_eq!(cursor.seek(&7).map(|e| *e.key()), Some(7));
+        assert_eq!(cursor.prev().map(|e| *e.key()), Some(6));
+        assert_eq!(cursor.seek(&3).map(|e| *e.key()), Some(3));
+
+        // Removing moves on to the next entry.
+        assert_eq!(cursor.remove().map(|e| *e.key()), Some(3));
+        assert_eq!(cursor.current().map(|e| *e.key()), Some(4));
+        assert_eq!(cursor.prev().map(|e| *e.key()), Some(2));
+        assert!(list.get(&3).is_none());
+
+        // Past either end, the cursor wraps around.
+        assert!(cursor.seek(&10).is_none());
+        assert_eq!(cursor.prev().map(|e| *e.key()), Some(9));
+        assert!(cursor.next().is_none());
+        assert_eq!(cursor.next().map(|e| *e.key()), Some(0));
+        assert!(cursor.prev().is_none());
+
+        // A cursor picks up from an entry, even one removed since.
+        let entry = list.get(&5).unwrap();
+        list.remove(&5);
+
+        let mut cursor = Cursor::from_entry(&list, entry);
+        assert!(cursor.remove().is_none());
+        assert_eq!(cursor.current().map(|e| *e.key()), Some(6));
+    }
+}
@@ -0,0 +1,150 @@
+//! Comparing two lists entry by entry.
+
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::iter::Peekable;
+
+use super::{Entry, Iter, SkipList};
+
+/// A difference between two lists, see [diff](SkipList::diff).
+pub enum Diff<'a, K, V> {
+    /// The entry is only in the other list.
+    Added(Entry<'a, K, V>),
+    /// The entry is only in this list.
+    Removed(Entry<'a, K, V>),
+    /// Both lists hold the key, with different values. The entry of this list comes first.
+    Changed(Entry<'a, K, V>, Entry<'a, K, V>),
+}
+
+impl<'a, K, V> Diff<'a, K, V> {
+    pub fn key(&self) -> &K {
+        match self {
+            Diff::Added(entry) | Diff::Removed(entry) | Diff::Changed(entry, _) => entry.key(),
+        }
+    }
+}
+
+impl<'a, K, V> Debug for Diff<'a, K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Diff::Added(entry) => f
+                .debug_tuple("Added")
+                .field(entry.key())
+                .field(entry.val())
+                .finish(),
+            Diff::Removed(entry) => f
+                .debug_tuple("Removed")
+                .field(entry.key())
+                .field(entry.val())
+                .finish(),
+            Diff::Changed(old, new) => f
+                .debug_tuple("Changed")
+                .field(old.key())
+                .field(old.val())
+                .field(new.val())
+                .finish(),
+        }
+    }
+}
+
+/// Yields the differences between two lists in ascending key order, see
+/// [diff](SkipList::diff).
+pub struct DiffIter<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    old: Peekable<Iter<'a, K, V>>,
+    new: Peekable<Iter<'a, K, V>>,
+}
+
+impl<'domain, K, V> SkipList<'domain, K, V>
+where
+    K: Ord + Send,
+    V: PartialEq + Send,
+{
+    /// Walks this list and `other` in lockstep, yielding what it takes to turn this list into
+    /// `other`. Applying the differences to a copy of this list makes it equal to `other`, which
+    /// keeps replicas and indices in step without rebuilding them. Concurrent modifications of
+    /// either list may or may not be part of the differences.
+    pub fn diff<'a>(&'a self, other: &'a SkipList<'_, K, V>) -> DiffIter<'a, K, V> {
+        DiffIter {
+            old: self.iter().peekable(),
+            new: other.iter().peekable(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for DiffIter<'a, K, V>
+where
+    K: Ord + Send,
+    V: PartialEq + Send,
+{
+    type Item = Diff<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let order = match (self.old.peek(), self.new.peek()) {
+                (None, None) => return None,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(old), Some(new)) => old.key().cmp(new.key()),
+            };
+
+            match order {
+                Ordering::Less => return self.old.next().map(Diff::Removed),
+                Ordering::Greater => return self.new.next().map(Diff::Added),
+                Ordering::Equal => {
+                    let (old, new) = (self.old.next()?, self.new.next()?);
+
+                    if old.val() != new.val() {
+                        return Some(Diff::Changed(old, new));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod diff_test {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let (old, new) = (SkipList::new(), SkipList::new());
+
+        for i in 0..10 {
+            old.insert(i, i);
+        }
+
+        for i in 5..15 {
+            new.insert(i, if i % 2 == 0 { i * 10 } else { i });
+        }
+
+        let diff = old
+            .diff(&new)
+            .map(|d| format!("{:?}", d))
+            .collect::<Vec<_>>();
+
+        assert_eq!(diff.iter().filter(|d| d.starts_with("Removed")).count(), 5);
+        assert_eq!(diff.iter().filter(|d| d.starts_with("Added")).count(), 5);
+        assert_eq!(diff[5..7], ["Changed(6, 6, 60)", "Changed(8, 8, 80)"]);
+        assert!(old.diff(&new).map(|d| *d.key()).is_sorted());
+
+        // Applying the differences turns one list into the other.
+        for d in old.diff(&new) {
+            match d {
+                Diff::Added(e) | Diff::Changed(_, e) => old.insert(*e.key(), *e.val()),
+                Diff::Removed(e) => old.remove(e.key()),
+            };
+        }
+
+        assert_eq!(old.diff(&new).count(), 0);
+        assert!(old == new);
+    }
+}
@@ -0,0 +1,270 @@
+//! A hash index over the nodes of the concurrent list, which lets a
+//! [HybridMap](crate::collections::hybrid_map::HybridMap) find a key without searching for it.
+//!
+//! Every key hashes to a bucket of [WAYS] slots, each pointing to a node. Slots only point to
+//! nodes marked as indexed, which the list does not retire until the index lets go of them, so a
+//! slot is read just like a level. Lookups compare the key and check whether the node was
+//! removed, a slot that went stale is never taken at its word.
+//!
+//! A bucket holds as many keys as it has slots, a key that does not fit evicts the one in the
+//! slot its hash picks. The list still holds every key, lookups missing the index search it.
+//!
+//! Once the list holds more keys than the index has slots, the index swaps its table for one
+//! twice the size of the list. The old table is sealed slot by slot, letting go of every node it
+//! points to, and retired, as lookups may still be reading it. The index is a cache of the list,
+//! so the keys of the old table are simply indexed again as they are looked up.
+
+use core::hash::{BuildHasher, Hash};
+use std::collections::hash_map::RandomState;
+
+use haphazard::Domain;
+
+use super::tagged::MaybeTagged;
+use super::{Entry, Node, NodeRef, SkipList};
+use crate::internal::utils::Hazard;
+
+/// The number of slots in a bucket.
+const WAYS: usize = 4;
+
+/// The tag of a slot in a table that was swapped out. No node is placed in it anymore.
+const SEALED: usize = 1;
+
+/// The slots of an index, swapped out as a whole once the list outgrows them.
+struct Table<K, V> {
+    slots: Box<[MaybeTagged<Node<K, V>>]>,
+}
+
+impl<K, V> Table<K, V> {
+    fn new(slots: usize) -> *mut Self {
+        Box::into_raw(Box::new(Table {
+            slots: (0..slots.max(WAYS).next_power_of_two())
+                .map(|_| MaybeTagged::new(core::ptr::null_mut()))
+                .collect(),
+        }))
+    }
+}
+
+// # Safety
+//
+// The slots only point to nodes, which the list owns, and which are `Send` along with their keys
+// and values.
+unsafe impl<K: Send, V: Send> Send for Table<K, V> {}
+
+pub(crate) struct Index<K, V> {
+    table: MaybeTagged<Table<K, V>>,
+    hasher: RandomState,
+}
+
+impl<K, V> Index<K, V> {
+    /// Creates an index with at least `capacity` slots.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Index {
+            table: MaybeTagged::new(Table::new(capacity)),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Protects the current table with `hazard` and returns it.
+    fn table<'a>(&self, hazard: &'a mut Hazard<'_>) -> &'a Table<K, V> {
+        let mut ptr = self.table.load_ptr();
+        hazard.protect_raw(ptr);
+
+        let mut v_ptr = self.table.load_ptr();
+
+        while !core::ptr::eq(ptr, v_ptr) {
+            ptr = v_ptr;
+            hazard.protect_raw(ptr);
+
+            v_ptr = self.table.load_ptr();
+        }
+
+        // # Safety
+        //
+        // The table was current once `hazard` protected it, so it is not reclaimed before the
+        // hazard lets go of it.
+        unsafe { &*ptr }
+    }
+}
+
+impl<K, V> Index<K, V>
+where
+    K: Hash + Ord + Send,
+    V: Send,
+{
+    /// Returns the slots of the bucket `key` hashes to in `table`, along with the one it evicts
+    /// when the bucket is full.
+    fn bucket<'t>(
+        &self,
+        table: &'t Table<K, V>,
+        key: &K,
+    ) -> (&'t [MaybeTagged<Node<K, V>>], usize) {
+        let hash = self.hasher.hash_one(key);
+        let start = hash as usize & (table.slots.len() - 1) & !(WAYS - 1);
+
+        (
+            &table.slots[start..start + WAYS],
+            (hash >> 32) as usize % WAYS,
+        )
+    }
+
+    /// Empties `slot` if it still points to `node`, letting go of the node.
+    fn evict(
+        &self,
+        list: &SkipList<'_, K, V>,
+        slot: &MaybeTagged<Node<K, V>>,
+        node: *mut Node<K, V>,
+    ) {
+        if slot.compare_exchange(node, core::ptr::null_mut()).is_ok() {
+            list.unindex(node);
+        }
+    }
+
+    /// Empties and seals every slot of `table`, letting go of the nodes.
+    fn seal(&self, list: &SkipList<'_, K, V>, table: &Table<K, V>) {
+        for slot in table.slots.iter() {
+            let mut node = slot.load_ptr();
+
+            // Only the thread that swapped the table out seals it, no other tags a slot.
+            while let Err((other, _)) =
+                slot.compare_exchange_with_tag(node, 0, core::ptr::null_mut(), SEALED)
+            {
+                node = other;
+            }
+
+            if !node.is_null() {
+                list.unindex(node);
+            }
+        }
+    }
+
+    /// Swaps `table` for one twice the size of the list, unless another thread swapped it first.
+    fn grow(&self, list: &SkipList<'_, K, V>, table: &Table<K, V>) {
+        let old = (table as *const Table<K, V>).cast_mut();
+        let new = Table::new(list.len().saturating_mul(2));
+
+        if self.table.compare_exchange(old, new).is_err() {
+            // # Safety
+            //
+            // No one else ever saw the new table.
+            unsafe { drop(Box::from_raw(new)) };
+            return;
+        }
+
+        self.seal(list, table);
+
+        // # Safety
+        //
+        // The table is no longer current, lookups that still read it protect it.
+        unsafe { Domain::global().retire_ptr::<Table<K, V>, Box<Table<K, V>>>(old) };
+    }
+
+    /// Lets go of every node, which has to happen before `list` is dropped.
+    pub(crate) fn clear(&mut self, list: &SkipList<'_, K, V>) {
+        // # Safety
+        //
+        // We borrow the index mutably, so the table is no one else's to read.
+        let table = unsafe { &*self.table.load_ptr() };
+
+        for slot in table.slots.iter() {
+            let node = slot.load_ptr();
+
+            if !node.is_null() {
+                self.evict(list, slot, node);
+            }
+        }
+    }
+
+    /// Returns the entry of `key`, if the index points to it.
+    pub(crate) fn get<'a>(&self, list: &'a SkipList<'_, K, V>, key: &K) -> Option<Entry<'a, K, V>> {
+        let mut hazard = Hazard::new();
+        let table = self.table(&mut hazard);
+        let (bucket, _) = self.bucket(table, key);
+
+        for slot in bucket {
+            let Some(node) = NodeRef::from_maybe_tagged(slot) else {
+                continue;
+            };
+
+            if node.key != *key {
+                continue;
+            }
+
+            // We may as well make room for the key's next node.
+            if node.removed() {
+                self.evict(list, slot, node.as_ptr());
+                continue;
+            }
+
+            return Some(node.into());
+        }
+
+        None
+    }
+
+    /// Points the bucket of the entry's key to its node. The node takes the slot of another node
+    /// of the same key or of a removed one, otherwise the free or evicted slot. Does nothing if
+    /// the node is indexed already or no longer linked. Grows the index first, should the list
+    /// have outgrown it.
+    pub(crate) fn insert(&self, list: &SkipList<'_, K, V>, entry: &Entry<'_, K, V>) {
+        let node = entry.as_node_ref();
+
+        let mut hazard = Hazard::new();
+        let mut table = self.table(&mut hazard);
+
+        if list.len() > table.slots.len() {
+            self.grow(list, table);
+            table = self.table(&mut hazard);
+        }
+
+        if !node.try_index() {
+            return;
+        }
+
+        let (bucket, evicted) = self.bucket(table, &node.key);
+
+        let slot = bucket
+            .iter()
+            .find(|slot| {
+                NodeRef::from_maybe_tagged(slot).is_none_or(|n| n.key == node.key || n.removed())
+            })
+            .unwrap_or(&bucket[evicted]);
+
+        let mut old = slot.load_ptr();
+
+        while let Err((other, tag)) = slot.compare_exchange(old, node.as_ptr()) {
+            // The table was swapped out meanwhile, the node is indexed again once looked up.
+            if tag == SEALED {
+                list.unindex(node.as_ptr());
+                return;
+            }
+
+            old = other;
+        }
+
+        if !old.is_null() {
+            list.unindex(old);
+        }
+    }
+
+    /// Lets go of a removed entry's node, if the index points to it.
+    pub(crate) fn remove(&self, list: &SkipList<'_, K, V>, entry: &Entry<'_, K, V>) {
+        let node = entry.as_node_ref();
+
+        let mut hazard = Hazard::new();
+        let table = self.table(&mut hazard);
+        let (bucket, _) = self.bucket(table, &node.key);
+
+        for slot in bucket {
+            self.evict(list, slot, node.as_ptr());
+        }
+    }
+}
+
+impl<K, V> Drop for Index<K, V> {
+    fn drop(&mut self) {
+        // # Safety
+        //
+        // The table is current, and so was never retired.
+        unsafe { drop(Box::from_raw(self.table.load_ptr())) };
+    }
+}
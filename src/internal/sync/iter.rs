@@ -1,36 +1,231 @@
-use crate::internal::utils::Node;
+use crate::internal::utils::Arena;
+use std::sync::Arc;
 
-use super::{Entry, SkipList};
+use super::{Entry, Node, NodeRef, RemovedEntry, SkipList};
+use core::borrow::Borrow;
 use core::iter::{FromIterator, IntoIterator, Iterator};
+use core::marker::PhantomData;
+use core::ops::{Bound, RangeBounds};
 
+/// An iterator over the entries of a list, see [iter](SkipList::iter). It can be walked from both
+/// ends, the two ends stop once they meet, so no entry is yielded twice.
 pub struct Iter<'a, K, V> {
     list: &'a SkipList<'a, K, V>,
     next: Option<Entry<'a, K, V>>,
+    back: Option<Entry<'a, K, V>>,
 }
 
 impl<'a, K, V> Iter<'a, K, V>
 where
-    K: Ord + Send + Sync,
-    V: Send + Sync,
+    K: Ord + Send,
+    V: Send,
 {
     pub fn from_list(list: &'a SkipList<'a, K, V>) -> Self {
         Self {
             list,
             next: list.get_first(),
+            back: None,
+        }
+    }
+
+    /// Advances the iterator to the first entry whose key is no less than `key`, without
+    /// starting over from the head. Does nothing if the next entry already is.
+    pub fn seek(&mut self, key: &K) {
+        if let Some(next) = self.next.take() {
+            self.next = self.list.seek_from(next, key);
         }
     }
 }
 
 impl<'a, K, V> core::iter::Iterator for Iter<'a, K, V>
 where
-    K: Ord + Send + Sync,
-    V: Send + Sync,
+    K: Ord + Send,
+    V: Send,
 {
     type Item = Entry<'a, K, V>;
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(next) = self.next.take() {
+        let next = self.next.take()?;
+
+        if self
+            .back
+            .as_ref()
+            .is_some_and(|back| next.key() >= back.key())
+        {
+            return None;
+        }
+
+        self.next = self.list.next_node(&next);
+
+        Some(next)
+    }
+}
+
+impl<'a, K, V> core::iter::DoubleEndedIterator for Iter<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // Once the front is exhausted, so is the back.
+        let front = self.next.as_ref()?;
+
+        let back = match self.back.as_ref() {
+            Some(back) => self.list.upper_bound(Bound::Excluded(back.key())),
+            None => self.list.get_last(),
+        };
+
+        match back {
+            Some(back) if back.key() >= front.key() => {
+                self.back = Some(back.clone());
+                Some(back)
+            }
+            _ => {
+                self.next = None;
+                None
+            }
+        }
+    }
+}
+
+/// An iterator over the entries linked on a single level, see
+/// [iter_level](SkipList::iter_level). Nodes that are being removed are still yielded until they
+/// are unlinked.
+pub struct IterLevel<'a, K, V> {
+    list: &'a SkipList<'a, K, V>,
+    curr: NodeRef<'a, K, V>,
+    level: usize,
+}
+
+impl<'a, K, V> IterLevel<'a, K, V> {
+    pub fn from_list(list: &'a SkipList<'a, K, V>, level: usize) -> Self {
+        let height = list.max_height();
+        assert!(level < height, "level must be below {}", height);
+
+        Self {
+            list,
+            curr: NodeRef::from_raw(list.head.as_ptr().cast::<Node<K, V>>()),
+            level,
+        }
+    }
+}
+
+impl<'a, K, V> core::iter::Iterator for IterLevel<'a, K, V>
+where
+    K: Ord + Send + 'a,
+    V: Send + 'a,
+{
+    type Item = Entry<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Every node linked on a level is high enough to have that level. Once the current node
+        // is removed, its link may lead to a node that is gone already, so we look for the node
+        // following its key on the level instead.
+        let next = match NodeRef::from_live(&self.curr.levels[self.level]) {
+            Ok(next) => next?,
+            Err(()) => self.list.find(&self.curr.key, true).prev[self.level].1.take()?,
+        };
+
+        self.curr = next.clone();
+
+        Some(next.into())
+    }
+}
+
+/// An iterator over the entries whose keys lie within a range, see
+/// [range](SkipList::range).
+pub struct Range<'a, K, V, R, Q: ?Sized = K> {
+    list: &'a SkipList<'a, K, V>,
+    next: Option<Entry<'a, K, V>>,
+    range: R,
+    _bounds: PhantomData<fn() -> Box<Q>>,
+}
+
+impl<'a, K, V, R, Q> Range<'a, K, V, R, Q>
+where
+    K: Ord + Send + Borrow<Q>,
+    V: Send,
+    R: RangeBounds<Q>,
+    Q: Ord + ?Sized,
+{
+    pub fn from_list(list: &'a SkipList<'a, K, V>, range: R) -> Self {
+        Self {
+            list,
+            next: list.lower_bound(range.start_bound()),
+            range,
+            _bounds: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V, R, Q> core::iter::Iterator for Range<'a, K, V, R, Q>
+where
+    K: Ord + Send + Borrow<Q>,
+    V: Send,
+    R: RangeBounds<Q>,
+    Q: Ord + ?Sized,
+{
+    type Item = Entry<'a, K, V>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.next.take()?;
+
+        let in_range = match self.range.end_bound() {
+            Bound::Included(end) => next.key().borrow() <= end,
+            Bound::Excluded(end) => next.key().borrow() < end,
+            Bound::Unbounded => true,
+        };
+
+        if !in_range {
+            return None;
+        }
+
+        self.next = self.list.next_node(&next);
+
+        Some(next)
+    }
+}
+
+/// An iterator removing the entries a predicate picks as it walks the list, see
+/// [extract_if](SkipList::extract_if).
+pub struct ExtractIf<'a, K, V, F> {
+    list: &'a SkipList<'a, K, V>,
+    next: Option<Entry<'a, K, V>>,
+    pred: F,
+}
+
+impl<'a, K, V, F> ExtractIf<'a, K, V, F>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    pub fn from_list(list: &'a SkipList<'a, K, V>, pred: F) -> Self {
+        Self {
+            list,
+            next: list.get_first(),
+            pred,
+        }
+    }
+}
+
+impl<'a, K, V, F> core::iter::Iterator for ExtractIf<'a, K, V, F>
+where
+    K: Ord + Send,
+    V: Send,
+    F: FnMut(&K, &V) -> bool,
+{
+    type Item = RemovedEntry<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(next) = self.next.take() {
             self.next = self.list.next_node(&next);
-            return Some(next);
+
+            if !(self.pred)(next.key(), next.val()) {
+                continue;
+            }
+
+            // Someone else may remove the entry before us, in which case we move on.
+            if let Some(removed) = self.list.remove(next.key()) {
+                return Some(RemovedEntry(removed));
+            }
         }
 
         None
@@ -39,8 +234,8 @@ where
 
 impl<'a, K, V> IntoIterator for SkipList<'a, K, V>
 where
-    K: Ord + Send + Sync,
-    V: Send + Sync,
+    K: Ord + Send,
+    V: Send,
 {
     type Item = (K, V);
     type IntoIter = IntoIter<K, V>;
@@ -49,10 +244,22 @@ where
     }
 }
 
+impl<'a, K, V> IntoIterator for &'a SkipList<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    type Item = Entry<'a, K, V>;
+    type IntoIter = Iter<'a, K, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<'a, K, V> FromIterator<(K, V)> for SkipList<'a, K, V>
 where
-    K: Ord + Send + Sync,
-    V: Send + Sync,
+    K: Ord + Send,
+    V: Send,
 {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
         let list = Self::new();
@@ -64,31 +271,59 @@ where
     }
 }
 
+impl<'a, K, V> From<std::collections::BTreeMap<K, V>> for SkipList<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    fn from(map: std::collections::BTreeMap<K, V>) -> Self {
+        SkipList::from_sorted_iter(map)
+    }
+}
+
+impl<'a, K, V> From<SkipList<'a, K, V>> for std::collections::BTreeMap<K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    fn from(list: SkipList<'a, K, V>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
 pub struct IntoIter<K, V> {
     next: *mut Node<K, V>,
+    // Keeps arena backed nodes alive until they have all been yielded.
+    _arena: Option<Arc<Arena>>,
 }
 
 impl<K, V> IntoIter<K, V>
 where
-    K: Ord + Send + Sync,
-    V: Send + Sync,
+    K: Ord + Send,
+    V: Send,
 {
     pub fn from_list<'a>(mut list: SkipList<'a, K, V>) -> Self {
+        // The nodes are ours from here on, the list must not count them as leaked.
+        list.state.allocations.freed(list.len());
+
         unsafe {
             let next = list.head.as_ref().levels[0].load_ptr();
             for level in list.head.as_mut().levels.pointers.iter_mut() {
                 level.store_ptr(core::ptr::null_mut());
             }
 
-            IntoIter { next }
+            IntoIter {
+                next,
+                _arena: list.arena.take(),
+            }
         }
     }
 }
 
 impl<K, V> core::iter::Iterator for IntoIter<K, V>
 where
-    K: Ord + Send + Sync,
-    V: Send + Sync,
+    K: Ord + Send,
+    V: Send,
 {
     type Item = (K, V);
     fn next(&mut self) -> Option<Self::Item> {
@@ -101,6 +336,7 @@ where
         self.next = unsafe { (*next).levels[0].load_ptr() };
 
         let (key, val) = unsafe { (core::ptr::read(&(*next).key), core::ptr::read(&(*next).val)) };
+        let val = val.into_inner();
 
         unsafe {
             Node::dealloc(next);
@@ -0,0 +1,177 @@
+//! A background thread doing the cleanup that operations otherwise pick up on their way, so
+//! inserts and removals spend less time on work that is not theirs. Only compiled with the
+//! `maintenance` feature.
+
+use core::sync::atomic::Ordering;
+use core::time::Duration;
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::thread::JoinHandle;
+
+use super::SkipList;
+
+/// Keeps the maintenance thread of a list running, see
+/// [spawn_maintenance](SkipList::spawn_maintenance). Dropping it stops the thread and waits for
+/// the pass it may be in the middle of.
+#[derive(Debug)]
+#[must_use = "the maintenance thread is stopped as soon as the handle is dropped"]
+pub struct Maintenance {
+    stopped: Arc<(Mutex<bool>, Condvar)>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for Maintenance {
+    fn drop(&mut self) {
+        let (stopped, wake) = &*self.stopped;
+
+        *stopped.lock().unwrap() = true;
+        wake.notify_all();
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl<'domain, K, V> SkipList<'domain, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Runs a single pass of the work a [maintenance thread](Self::spawn_maintenance) does:
+    /// finishes the towers that inserts left incomplete, lowers the level searches start on to
+    /// the highest one still in use, and then [trims](Self::trim) the list, unlinking the
+    /// removed nodes and reclaiming what no one protects anymore. Returns the number of retired
+    /// allocations freed.
+    pub fn maintain(&self) -> usize {
+        for entry in self.iter() {
+            // A search stopping in front of the node finishes its tower.
+            if entry.incomplete() && !entry.removed() {
+                self.find(entry.key(), false);
+            }
+        }
+
+        self.shrink_levels();
+
+        self.trim()
+    }
+
+    /// Lowers the level searches start on to the highest one that links any tower. Towers that
+    /// reached higher levels were all removed since.
+    fn shrink_levels(&self) {
+        let head = unsafe { &(*self.head.as_ptr()) };
+        let max_height = self.state.max_height.load(Ordering::Relaxed);
+
+        let in_use = (1..max_height)
+            .rev()
+            .find(|&level| !head.levels[level].load_ptr().is_null())
+            .map_or(1, |level| level + 1);
+
+        // Should an insert have raised the height since, it knows better.
+        let _ = self.state.max_height.compare_exchange(
+            max_height,
+            in_use,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        );
+    }
+}
+
+impl<K, V> SkipList<'static, K, V>
+where
+    K: Ord + Send + Sync + 'static,
+    V: Send + Sync + 'static,
+{
+    /// Spawns a thread calling [maintain](Self::maintain) on the list every `interval`, moving
+    /// the cleanup off the paths of the application's inserts and removals. The thread holds on
+    /// to the list weakly, and stops once the list is dropped or the returned handle is.
+    pub fn spawn_maintenance(self: &Arc<Self>, interval: Duration) -> Maintenance {
+        let stopped = Arc::new((Mutex::new(false), Condvar::new()));
+        let list = Arc::downgrade(self);
+
+        let thread = std::thread::spawn({
+            let stopped = stopped.clone();
+
+            move || run(&list, &stopped, interval)
+        });
+
+        Maintenance {
+            stopped,
+            thread: Some(thread),
+        }
+    }
+}
+
+fn run<K, V>(
+    list: &Weak<SkipList<'static, K, V>>,
+    stopped: &(Mutex<bool>, Condvar),
+    interval: Duration,
+) where
+    K: Ord + Send + Sync,
+    V: Send + Sync,
+{
+    let (stopped, wake) = stopped;
+    let mut guard = stopped.lock().unwrap();
+
+    while !*guard {
+        guard = wake.wait_timeout(guard, interval).unwrap().0;
+
+        if *guard {
+            break;
+        }
+
+        let Some(list) = list.upgrade() else {
+            break;
+        };
+
+        drop(guard);
+        list.maintain();
+        drop(list);
+
+        guard = stopped.lock().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod maintenance_test {
+    use super::*;
+
+    #[test]
+    fn test_maintain() {
+        let list = SkipList::new();
+
+        for i in 0..100 {
+            list.insert_with_height(i, (), if i == 50 { 20 } else { 1 });
+        }
+
+        assert_eq!(list.current_levels_in_use(), 20);
+
+        list.remove(&50);
+        list.maintain();
+
+        assert_eq!(list.current_levels_in_use(), 1);
+        assert_eq!(list.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_spawn_maintenance() {
+        let list = Arc::new(SkipList::new());
+        list.insert_with_height(0, (), 16);
+        list.insert_with_height(1, (), 1);
+
+        let maintenance = list.spawn_maintenance(Duration::from_millis(1));
+        list.remove(&0);
+
+        let start = std::time::Instant::now();
+
+        while list.current_levels_in_use() > 1 {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        drop(maintenance);
+
+        // The thread lets go of the list once it stopped.
+        assert_eq!(Arc::strong_count(&list), 1);
+        assert_eq!(Arc::weak_count(&list), 0);
+    }
+}
@@ -0,0 +1,227 @@
+//! Read-modify-write access to a single key, in the style of
+//! [BTreeMap::entry](std::collections::BTreeMap::entry).
+
+use super::{Entry, Insert, Retries, SkipList};
+
+/// A key of a list that may or may not be in it, see [entry](SkipList::entry).
+pub enum MapEntry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// A key found in the list, along with its entry at the time.
+pub struct OccupiedEntry<'a, K, V> {
+    list: &'a SkipList<'a, K, V>,
+    key: K,
+    entry: Entry<'a, K, V>,
+}
+
+/// A key that was not in the list.
+pub struct VacantEntry<'a, K, V> {
+    list: &'a SkipList<'a, K, V>,
+    key: K,
+}
+
+impl<'domain, K, V> SkipList<'domain, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Looks `key` up once, for the caller to read, insert or update its value from there on.
+    /// Other threads may insert or remove the key in the meantime, which the methods of the entry
+    /// account for: a vacant entry never replaces a value someone else inserted, and an update
+    /// starts over from whichever value someone else swapped in since.
+    pub fn entry<'a>(&'a self, key: K) -> MapEntry<'a, K, V> {
+        match self.find_settled(&key).target {
+            Some(target) => MapEntry::Occupied(OccupiedEntry {
+                list: self,
+                key,
+                entry: target.into(),
+            }),
+            None => MapEntry::Vacant(VacantEntry { list: self, key }),
+        }
+    }
+}
+
+impl<'a, K, V> MapEntry<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    pub fn key(&self) -> &K {
+        match self {
+            MapEntry::Occupied(occupied) => occupied.key(),
+            MapEntry::Vacant(vacant) => vacant.key(),
+        }
+    }
+
+    /// Returns the entry of the key, inserting `val` first if the key is vacant.
+    pub fn or_insert(self, val: V) -> Entry<'a, K, V> {
+        match self {
+            MapEntry::Occupied(occupied) => occupied.entry,
+            MapEntry::Vacant(vacant) => vacant.insert(val),
+        }
+    }
+
+    /// Returns the entry of the key, inserting the value `f` returns first if the key is vacant.
+    pub fn or_insert_with<F>(self, f: F) -> Entry<'a, K, V>
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            MapEntry::Occupied(occupied) => occupied.entry,
+            MapEntry::Vacant(vacant) => vacant.insert(f()),
+        }
+    }
+
+    pub fn or_default(self) -> Entry<'a, K, V>
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Swaps the value of an occupied key for the one `f` makes of it. Should another thread swap
+    /// in a value first, `f` is called again with theirs, so no update is lost. Should it remove
+    /// the key, the entry turns vacant.
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnMut(&V) -> V,
+    {
+        match self {
+            MapEntry::Occupied(occupied) => occupied.update(f),
+            vacant => vacant,
+        }
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// The entry of the key, holding its value as of the lookup or the last update.
+    pub fn get(&self) -> &Entry<'a, K, V> {
+        &self.entry
+    }
+
+    pub fn into_entry(self) -> Entry<'a, K, V> {
+        self.entry
+    }
+
+    /// Swaps the value for `val`, returning the entry of the value it replaced. Should someone
+    /// else have removed the key since, `val` is inserted anew and `None` returned.
+    pub fn insert(self, val: V) -> Option<Entry<'a, K, V>> {
+        self.list.insert(self.key, val)
+    }
+
+    /// Swaps the value for the one `f` makes of it, see [and_modify](MapEntry::and_modify).
+    pub fn update<F>(self, f: F) -> MapEntry<'a, K, V>
+    where
+        F: FnMut(&V) -> V,
+    {
+        match self.list.update_node(self.entry.as_node_ref().clone(), f) {
+            Some((entry, _)) => MapEntry::Occupied(OccupiedEntry { entry, ..self }),
+            None => MapEntry::Vacant(VacantEntry {
+                list: self.list,
+                key: self.key,
+            }),
+        }
+    }
+
+    /// Removes the key from the list, returning its entry, or `None` if someone else removed it
+    /// first.
+    pub fn remove(self) -> Option<Entry<'a, K, V>> {
+        if self.entry.removed() {
+            return None;
+        }
+
+        self.list.remove(&self.key)
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts `val` for the key and returns its entry. Should another thread insert the key
+    /// first, returns their entry instead and drops `val`.
+    pub fn insert(self, val: V) -> Entry<'a, K, V> {
+        let retries = Retries::unbounded();
+
+        super::unbounded(
+            self.list
+                .insert_node(self.key, val, None, Insert::KeepExisting, &retries),
+        )
+        .entry
+    }
+}
+
+#[cfg(test)]
+mod map_entry_test {
+    use super::*;
+
+    #[test]
+    fn test_entry() {
+        let list = SkipList::new();
+
+        assert_eq!(*list.entry(1).or_insert(10).val(), 10);
+        assert_eq!(*list.entry(1).or_insert(20).val(), 10);
+        assert_eq!(*list.entry(2).or_insert_with(|| 30).val(), 30);
+        assert_eq!(*list.entry(3).or_default().val(), 0);
+
+        let modified = list.entry(1).and_modify(|v| v + 1).or_insert(0);
+        assert_eq!(*modified.val(), 11);
+        assert_eq!(*list.entry(4).and_modify(|v| v + 1).or_insert(40).val(), 40);
+
+        match list.entry(2) {
+            MapEntry::Occupied(occupied) => {
+                assert_eq!(occupied.insert(31).map(|e| *e.val()), Some(30))
+            }
+            MapEntry::Vacant(_) => unreachable!(),
+        }
+
+        match list.entry(3) {
+            MapEntry::Occupied(occupied) => assert!(occupied.remove().is_some()),
+            MapEntry::Vacant(_) => unreachable!(),
+        }
+
+        assert!(list
+            .iter()
+            .map(|e| (*e.key(), *e.val()))
+            .eq([(1, 11), (2, 31), (4, 40)]));
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn test_and_modify_concurrent() {
+        let list = SkipList::new();
+        list.insert(0, 0);
+
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..1000 {
+                        list.entry(0).and_modify(|v| v + 1);
+                    }
+                });
+            }
+        });
+
+        // No increment got lost.
+        assert_eq!(*list.get(&0).unwrap().val(), 4000);
+    }
+}
@@ -0,0 +1,157 @@
+//! Iterating over several lists at once, as if they were one.
+
+use core::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use super::{Entry, SkipList};
+
+/// Yields the entries of several lists in ascending key order, see [merge_iter](merge_iter).
+pub struct MergeIter<'a, K, V> {
+    lists: Vec<&'a SkipList<'a, K, V>>,
+    heads: BinaryHeap<Head<'a, K, V>>,
+}
+
+/// The next entry of one of the lists. Each keeps its node protected by its own hazard pointer.
+struct Head<'a, K, V> {
+    entry: Entry<'a, K, V>,
+    source: usize,
+}
+
+impl<'a, K: Ord, V> Ord for Head<'a, K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `BinaryHeap` is a max-heap, so the smallest key of the newest list has to compare
+        // greatest.
+        other
+            .entry
+            .key()
+            .cmp(self.entry.key())
+            .then(other.source.cmp(&self.source))
+    }
+}
+
+impl<'a, K: Ord, V> PartialOrd for Head<'a, K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, K: Ord, V> PartialEq for Head<'a, K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl<'a, K: Ord, V> Eq for Head<'a, K, V> {}
+
+/// Merges `lists` into a single iterator over their entries in ascending key order. The lists are
+/// expected from newest to oldest: if a key is in more than one of them, only the entry of the
+/// first list containing it is yielded.
+pub fn merge_iter<'a, K, V>(lists: &[&'a SkipList<'a, K, V>]) -> MergeIter<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    let heads = lists
+        .iter()
+        .enumerate()
+        .filter_map(|(source, list)| {
+            Some(Head {
+                entry: list.get_first()?,
+                source,
+            })
+        })
+        .collect();
+
+    MergeIter {
+        lists: lists.to_vec(),
+        heads,
+    }
+}
+
+impl<'a, K, V> MergeIter<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    /// Moves the list of `head` on to its next entry.
+    fn advance(&mut self, head: &Head<'a, K, V>) {
+        if let Some(entry) = self.lists[head.source].next_node(&head.entry) {
+            self.heads.push(Head {
+                entry,
+                source: head.source,
+            });
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for MergeIter<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    type Item = Entry<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let head = self.heads.pop()?;
+
+        // Older lists with the same key are shadowed by the newest one.
+        while let Some(older) = self.heads.peek() {
+            if older.entry.key() != head.entry.key() {
+                break;
+            }
+
+            let older = self.heads.pop().unwrap();
+            self.advance(&older);
+        }
+
+        self.advance(&head);
+
+        Some(head.entry)
+    }
+}
+
+#[cfg(test)]
+mod merge_test {
+    use super::*;
+
+    #[test]
+    fn test_merge_iter() {
+        let (new, mid, old) = (SkipList::new(), SkipList::new(), SkipList::new());
+
+        for i in (0..30).step_by(3) {
+            new.insert(i, "new");
+        }
+
+        for i in (0..30).step_by(2) {
+            mid.insert(i, "mid");
+        }
+
+        for i in 0..30 {
+            old.insert(i, "old");
+        }
+
+        let merged = merge_iter(&[&new, &mid, &old])
+            .map(|e| (*e.key(), *e.val()))
+            .collect::<Vec<_>>();
+
+        let expected = (0..30)
+            .map(|i| match i {
+                i if i % 3 == 0 => (i, "new"),
+                i if i % 2 == 0 => (i, "mid"),
+                i => (i, "old"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn test_merge_iter_empty() {
+        let (a, b) = (SkipList::<u8, ()>::new(), SkipList::new());
+        b.insert(1, ());
+
+        assert_eq!(merge_iter::<u8, ()>(&[]).count(), 0);
+        assert_eq!(merge_iter(&[&a]).count(), 0);
+        assert!(merge_iter(&[&a, &b]).map(|e| *e.key()).eq([1]));
+    }
+}
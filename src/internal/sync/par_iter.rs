@@ -0,0 +1,131 @@
+//! [rayon](rayon) integration, so parallel pipelines can build and extend a list directly.
+
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelExtend, ParallelIterator};
+use rayon::slice::ParallelSliceMut;
+
+use super::{Entry, SkipList};
+
+impl<'domain, K, V> SkipList<'domain, K, V>
+where
+    K: Ord + Clone + Send + Sync,
+    V: Send + Sync,
+{
+    /// Calls `f` on every entry, spreading the work over the rayon thread pool. The list is split
+    /// into a range per thread with [par_split](SkipList::par_split), each of which a single job
+    /// walks in order.
+    pub fn par_for_each<F>(&self, f: F)
+    where
+        F: Fn(Entry<'_, K, V>) + Send + Sync,
+    {
+        self.par_split(rayon::current_num_threads())
+            .into_par_iter()
+            .for_each(|range| self.range(range).for_each(&f));
+    }
+}
+
+impl<'domain, K, V> FromParallelIterator<(K, V)> for SkipList<'domain, K, V>
+where
+    K: Ord + Send + Sync,
+    V: Send + Sync,
+{
+    /// Collects and sorts the pairs in parallel, then links them into a fresh list with the bulk
+    /// sorted loader, so no insertion ever has to search for its position.
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        let mut pairs: Vec<(K, V)> = par_iter.into_par_iter().collect();
+
+        // The sort is stable, so the last value of a duplicate key stays last.
+        pairs.par_sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        SkipList::from_sorted_iter(pairs)
+    }
+}
+
+impl<'domain, K, V> ParallelExtend<(K, V)> for SkipList<'domain, K, V>
+where
+    K: Ord + Send + Sync,
+    V: Send + Sync,
+{
+    /// Every rayon job gathers its pairs into a batch and sorts it, then inserts it in one pass.
+    /// Each search of a batch starts from the nodes the insert before it left preceding its key,
+    /// rather than from the head, so the thread walks down the towers only once per run of close
+    /// keys. Which value wins for a key that appears in several batches is unspecified.
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        let list = &*self;
+
+        par_iter
+            .into_par_iter()
+            .fold(Vec::new, |mut batch, pair| {
+                batch.push(pair);
+                batch
+            })
+            .for_each(|mut batch| {
+                // The sort is stable, so the last value of a duplicate key stays last.
+                batch.sort_by(|(a, _), (b, _)| a.cmp(b));
+                list.insert_sorted(batch);
+            });
+    }
+}
+
+#[cfg(test)]
+mod par_iter_test {
+    use rayon::iter::{
+        IndexedParallelIterator, IntoParallelIterator, ParallelExtend, ParallelIterator,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_from_par_iter() {
+        let list: SkipList<'_, _, _> = (0..10_000u32)
+            .into_par_iter()
+            .rev()
+            .map(|i| (i, i))
+            .collect();
+
+        assert_eq!(list.len(), 10_000);
+        assert!(list.iter().map(|e| *e.key()).eq(0..10_000));
+    }
+
+    #[test]
+    fn test_par_for_each() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let list: SkipList<'_, _, _> = (0..10_000usize).map(|i| (i, i)).collect();
+        let sum = AtomicUsize::new(0);
+
+        list.par_for_each(|e| {
+            sum.fetch_add(*e.val(), Ordering::Relaxed);
+        });
+
+        assert_eq!(sum.into_inner(), (0..10_000).sum::<usize>());
+    }
+
+    #[test]
+    fn test_par_extend() {
+        let mut list = SkipList::new();
+
+        list.insert(0u32, 0u32);
+        list.par_extend((1..1_000u32).into_par_iter().map(|i| (i, i)));
+
+        assert_eq!(list.len(), 1_000);
+        assert!(list.iter().map(|e| *e.key()).eq(0..1_000));
+    }
+
+    #[test]
+    fn test_par_extend_duplicates() {
+        let mut list = SkipList::new();
+
+        list.par_extend((0..10_000u32).into_par_iter().map(|i| (i * 7 % 500, i)));
+
+        assert_eq!(list.len(), 500);
+        assert!(list.iter().map(|e| *e.key()).eq(0..500));
+        assert!(list.iter().all(|e| *e.val() * 7 % 500 == *e.key()));
+        assert_eq!(list.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,86 @@
+//! Registering the threads that operate on a list, so the hazard pointers they need exist before
+//! their first operation.
+
+use core::sync::atomic::Ordering;
+
+use crate::internal::utils::atomic::AtomicUsize;
+use crate::internal::utils::{Hazard, HEIGHT};
+
+use super::SkipList;
+
+/// The hazard pointers a thread may hold at once: a search protects two nodes on every level, an
+/// entry its node and value, and a replaced value gets one of its own.
+const THREAD_HAZARDS: usize = 2 * HEIGHT + 3;
+
+/// Keeps a thread registered with a list, see [register_thread](SkipList::register_thread).
+/// Dropping it deregisters the thread.
+#[derive(Debug)]
+#[must_use = "the thread is deregistered again once the registration is dropped"]
+pub struct Registration<'a> {
+    registered: &'a AtomicUsize,
+}
+
+impl<'domain, K, V> SkipList<'domain, K, V> {
+    /// Registers the calling thread with the list, making sure the hazard pointer domain holds as
+    /// many slots as all registered threads may need at once. The domain otherwise allocates its
+    /// slots as operations first ask for them, which shows up as a latency spike in the first few
+    /// operations of a thread. Registering is worth it for latency critical threads, call it once
+    /// before they start and keep the [Registration](Registration) for as long as they run.
+    ///
+    /// The domain never frees its slots, so those of deregistered threads are left for others to
+    /// take.
+    pub fn register_thread(&self) -> Registration<'_> {
+        let registered = self.state.registered.fetch_add(1, Ordering::AcqRel) + 1;
+
+        // Holding the hazard pointers all at once makes the domain allocate a slot for each, which
+        // go back to the domain for later operations to take once we let go of them.
+        let hazards = (0..registered * THREAD_HAZARDS)
+            .map(|_| Hazard::new())
+            .collect::<Vec<_>>();
+
+        drop(hazards);
+
+        Registration {
+            registered: &self.state.registered,
+        }
+    }
+
+    /// The number of threads currently registered with the list.
+    pub fn registered_threads(&self) -> usize {
+        self.state.registered.load(Ordering::Acquire)
+    }
+}
+
+impl<'a> Drop for Registration<'a> {
+    fn drop(&mut self) {
+        self.registered.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod registration_test {
+    use super::*;
+
+    #[test]
+    fn test_register_thread() {
+        let list = SkipList::new();
+
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                let list = &list;
+
+                s.spawn(move || {
+                    let _registration = list.register_thread();
+                    assert!(list.registered_threads() >= 1);
+
+                    for i in 0..100 {
+                        list.insert(i * 4 + t, ());
+                    }
+                });
+            }
+        });
+
+        assert_eq!(list.registered_threads(), 0);
+        assert_eq!(list.len(), 400);
+    }
+}
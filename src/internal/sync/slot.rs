@@ -0,0 +1,309 @@
+//! The value of a node in the concurrent list. It lives in an allocation of its own behind an
+//! atomic pointer, so inserting a key that is already in the list swaps in the new value without
+//! replacing the node. Entries protect the value they read with a hazard pointer, just like their
+//! node, so a value that was swapped out is retired rather than freed.
+//!
+//! Removing a node seals its slot by tagging the pointer, which makes every later swap fail. An
+//! insert that loses this race links a new node instead, rather than handing its value to a node
+//! that is on its way out. A rename claims the slot with a tag of its own while it moves the value,
+//! which fails swaps just the same. Letting go of the claim leaves a seal placed meanwhile alone.
+//!
+//! Every value is retired exactly once, or dropped along with its slot:
+//!
+//! 1. The pointer only ever changes through a compare and swap from an untagged pointer to a value
+//!    no one else has seen yet, so a value is the current one of a slot at most once. Values are
+//!    never without a size, so no two of them that are alive share an address.
+//! 2. Only the thread whose swap took a value out of the slot retires it, which happens once, by 1.
+//! 3. A sealed slot fails every swap, so the value current when the slot is sealed is never
+//!    swapped out, and only dropping the slot drops it. Nothing ever lifts a seal. A claimed slot
+//!    fails every swap as well, and [release](Slot::release) returns it to 2 unless it was sealed.
+//!
+//! A swap racing a removal thus either swaps before the seal, retiring the value it took out, or
+//! fails and frees the value it never published.
+//!
+//! The value a node is linked with shares the node's arena, if the list has one. Such a value is
+//! only ever dropped in place, its memory goes with the arena. Values swapped in later always live
+//! on the heap.
+
+use core::ptr::{self, NonNull};
+use std::sync::Arc;
+
+use super::tagged::MaybeTagged;
+use crate::internal::utils::{Arena, Hazard, Stored};
+
+/// The tag bit of a sealed slot.
+const SEALED: usize = 1;
+/// The tag bit of a slot a rename claimed.
+const CLAIMED: usize = 2;
+
+/// A value, aligned to leave room for the tag of the slot pointing to it.
+#[repr(C, align(4))]
+pub(crate) struct Value<V> {
+    val: V,
+    /// The arena the value was carved out of, or null if it lives on the heap.
+    arena: *const Arena,
+}
+
+impl<V> Value<V> {
+    fn boxed(val: V) -> *mut Self {
+        Box::into_raw(Box::new(Value {
+            val,
+            arena: ptr::null(),
+        }))
+    }
+
+    /// The arena the value was carved out of, if it was.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` points to a value that was not dropped yet.
+    pub(crate) unsafe fn arena(ptr: *mut Self) -> Option<*const Arena> {
+        let arena = (*ptr).arena;
+        (!arena.is_null()).then_some(arena)
+    }
+
+    /// Drops the value and, unless it lives in an arena, frees it.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` points to a value no one else accesses anymore, which is dropped only once.
+    pub(crate) unsafe fn drop(ptr: *mut Self) {
+        match Self::arena(ptr) {
+            Some(_) => ptr::drop_in_place(ptr),
+            None => drop(Box::from_raw(ptr)),
+        }
+    }
+
+    /// Takes the value out and, unless it lives in an arena, frees what is left.
+    ///
+    /// # Safety
+    ///
+    /// Just as for [drop](Self::drop).
+    unsafe fn take(ptr: *mut Self) -> V {
+        match Self::arena(ptr) {
+            Some(_) => ptr::read(ptr).val,
+            None => Box::from_raw(ptr).val,
+        }
+    }
+}
+
+// # Safety
+//
+// The arena a value points to is only ever read, and outlives the value.
+unsafe impl<V: Send> Send for Value<V> {}
+
+/// A value that was swapped out of its slot, as handed to the hazard pointer domain. Reclaiming it
+/// drops the value and lets go of its arena, which [retain](Self::retain) held on to.
+pub(crate) struct Retired<V>(*mut Value<V>);
+
+impl<V> Retired<V> {
+    /// Keeps the arena of the value, if any, alive until the value is reclaimed, as the list may
+    /// be gone by then.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` points to a value that is about to be retired.
+    pub(crate) unsafe fn retain(ptr: *mut Value<V>) {
+        if let Some(arena) = Value::arena(ptr) {
+            Arena::retain(arena);
+        }
+    }
+}
+
+impl<V> Drop for Retired<V> {
+    fn drop(&mut self) {
+        // # Safety
+        //
+        // No one protects the value anymore, and its arena was retained when it was retired.
+        unsafe {
+            let arena = Value::arena(self.0);
+
+            Value::drop(self.0);
+
+            if let Some(arena) = arena {
+                Arena::release(arena);
+            }
+        }
+    }
+}
+
+unsafe impl<V> haphazard::raw::Pointer<Value<V>> for Retired<V> {
+    fn into_raw(self) -> *mut Value<V> {
+        core::mem::ManuallyDrop::new(self).0
+    }
+
+    unsafe fn from_raw(ptr: *mut Value<V>) -> Self {
+        Retired(ptr)
+    }
+}
+
+impl<V> core::ops::Deref for Retired<V> {
+    type Target = Value<V>;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.0 }
+    }
+}
+
+pub struct Slot<V>(MaybeTagged<Value<V>>);
+
+impl<V> Slot<V> {
+    /// Protects the current value with `hazard` and returns a pointer to it, which stays valid
+    /// for as long as `hazard` protects it.
+    pub(crate) fn protect(&self, hazard: &mut Hazard<'_>) -> NonNull<V> {
+        let mut ptr = self.0.load_ptr();
+        hazard.protect_raw(ptr);
+
+        let mut v_ptr = self.0.load_ptr();
+
+        while !core::ptr::eq(ptr, v_ptr) {
+            ptr = v_ptr;
+            hazard.protect_raw(ptr);
+
+            v_ptr = self.0.load_ptr();
+        }
+
+        // # Safety
+        //
+        // A slot always holds a value, and `Value` is `repr(C)`, so its value sits at its start.
+        unsafe { NonNull::new_unchecked(ptr.cast()) }
+    }
+
+    /// Swaps in `val`, returning the previous value protected by `hazard`. The caller has to retire
+    /// it once it swapped it out. Gives `val` back if the slot was sealed.
+    pub(crate) fn replace(&self, val: V, hazard: &mut Hazard<'_>) -> Result<*mut Value<V>, V> {
+        let new = Value::boxed(val);
+
+        loop {
+            let old = self.protect(hazard).as_ptr().cast::<Value<V>>();
+
+            match self.0.compare_exchange(old, new) {
+                Ok(_) => return Ok(old),
+                // # Safety
+                //
+                // Someone else swapped in their value first, we replace theirs instead.
+                Err((_, 0)) => continue,
+                // No one else ever saw the new value.
+                Err(_) => return Err(unsafe { Value::take(new) }),
+            }
+        }
+    }
+
+    /// Swaps in the value `f` makes of the current one, returning the previous value protected by
+    /// `hazard`, just as [replace](Self::replace) does. Should someone else swap in their value
+    /// first, `f` is called again with theirs. Returns `None` if the slot was sealed or claimed.
+    pub(crate) fn update<F>(&self, mut f: F, hazard: &mut Hazard<'_>) -> Option<*mut Value<V>>
+    where
+        F: FnMut(&V) -> V,
+    {
+        loop {
+            let old = self.protect(hazard);
+
+            // # Safety
+            //
+            // `hazard` keeps the current value alive while we read it.
+            let new = Value::boxed(f(unsafe { old.as_ref() }));
+            let old = old.as_ptr().cast::<Value<V>>();
+
+            match self.0.compare_exchange(old, new) {
+                Ok(_) => return Some(old),
+                Err((_, tag)) => {
+                    // # Safety
+                    //
+                    // No one else ever saw the new value.
+                    unsafe { Value::drop(new) };
+
+                    if tag != 0 {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Seals the slot, once its node is removed.
+    pub(crate) fn seal(&self) {
+        self.update_tag(|tag| tag | SEALED);
+    }
+
+    /// Claims the slot for a rename unless it is sealed or claimed already, returning whether it
+    /// was this call that did.
+    pub(crate) fn try_claim(&self) -> bool {
+        self.0.compare_exchange_tag(0, CLAIMED).is_ok()
+    }
+
+    /// Lets go of a claim of [try_claim](Self::try_claim) whose node ended up staying in the list.
+    /// Should the node have been removed meanwhile, the slot stays sealed.
+    pub(crate) fn release(&self) {
+        self.update_tag(|tag| tag & !CLAIMED);
+    }
+
+    /// Sets the tag to what `f` makes of the current one.
+    fn update_tag(&self, f: impl Fn(usize) -> usize) {
+        let mut tag = self.0.load_tag();
+
+        while let Err(other) = self.0.compare_exchange_tag(tag, f(tag)) {
+            tag = other;
+        }
+    }
+
+    /// Takes the value out of the slot, which must be the only reference to it.
+    pub(crate) fn into_inner(self) -> V {
+        let this = core::mem::ManuallyDrop::new(self);
+
+        // # Safety
+        //
+        // We own the slot and with it its value, which we make sure not to drop twice.
+        unsafe { Value::take(this.0.load_ptr()) }
+    }
+}
+
+// # Safety
+//
+// The slot owns its value, moving it to another thread moves the value along. Values that were
+// swapped out are dropped by whichever thread reclaims them.
+unsafe impl<V: Send> Send for Slot<V> {}
+
+impl<V> Stored<V> for Slot<V> {
+    const ARENA_BYTES: usize = core::mem::size_of::<Value<V>>();
+
+    fn store(val: V, arena: Option<&Arc<Arena>>) -> Self {
+        let layout = core::alloc::Layout::new::<Value<V>>();
+
+        let ptr = match arena.and_then(|arena| Some((arena, arena.alloc(layout)?))) {
+            Some((arena, ptr)) => {
+                let ptr = ptr.as_ptr().cast::<Value<V>>();
+
+                // # Safety
+                //
+                // The arena handed out memory fitting a value.
+                unsafe {
+                    ptr.write(Value {
+                        val,
+                        arena: Arc::as_ptr(arena),
+                    })
+                };
+
+                ptr
+            }
+            None => Value::boxed(val),
+        };
+
+        Slot(MaybeTagged::new(ptr))
+    }
+}
+
+impl<V> Drop for Slot<V> {
+    fn drop(&mut self) {
+        // # Safety
+        //
+        // The node owning the slot is being dropped, so no one protects its current value anymore.
+        unsafe { Value::drop(self.0.load_ptr()) }
+    }
+}
+
+impl<V> core::fmt::Debug for Slot<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Slot").field(&self.0).finish()
+    }
+}
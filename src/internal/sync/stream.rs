@@ -0,0 +1,183 @@
+//! An async [Stream](futures_core::Stream) over the entries of a list.
+
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::{Entry, NodeRef, SkipList};
+
+/// The number of entries a [Stream](Stream) yields before handing control back to the executor.
+pub const STREAM_BUDGET: usize = 128;
+
+/// Yields the entries of a list in ascending order, see [stream](SkipList::stream).
+pub struct Stream<'a, K, V> {
+    list: &'a SkipList<'a, K, V>,
+    last: Option<NodeRef<'a, K, V>>,
+    budget: usize,
+    since_yield: usize,
+    follow: bool,
+}
+
+impl<'a, K, V> Stream<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    pub fn from_list(list: &'a SkipList<'a, K, V>) -> Self {
+        Stream {
+            list,
+            last: None,
+            budget: STREAM_BUDGET,
+            since_yield: 0,
+            follow: false,
+        }
+    }
+
+    /// Sets how many entries are yielded before the stream returns `Pending` once, so that long
+    /// scans do not starve the other tasks of the executor.
+    pub fn budget(mut self, budget: usize) -> Self {
+        self.budget = budget.max(1);
+        self
+    }
+
+    /// Instead of ending at the end of the list, the stream waits for keys greater than the last
+    /// one it yielded to be inserted.
+    pub fn follow(mut self) -> Self {
+        self.follow = true;
+        self
+    }
+
+    fn advance(&mut self) -> Option<Entry<'a, K, V>> {
+        let next = match self.last.as_ref() {
+            None => self.list.get_first()?,
+            Some(last) => {
+                let mut next = self.list.next_node(&last.clone().into())?;
+
+                // If `last` was removed in the meantime, the list searches for its successor
+                // again, which may land on a newly inserted node with the very same key.
+                while next.key() <= &last.key {
+                    next = self.list.next_node(&next)?;
+                }
+
+                next
+            }
+        };
+
+        self.last = Some(NodeRef::from_raw(next.node.as_ptr()));
+        self.since_yield += 1;
+
+        Some(next)
+    }
+}
+
+impl<'a, K, V> futures_core::Stream for Stream<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    type Item = Entry<'a, K, V>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.since_yield >= this.budget {
+            this.since_yield = 0;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        if let Some(next) = this.advance() {
+            return Poll::Ready(Some(next));
+        }
+
+        if !this.follow {
+            return Poll::Ready(None);
+        }
+
+        this.list.wakers.register(cx.waker());
+
+        // An insert that finished before we registered did not wake us, so we look once more.
+        match this.advance() {
+            Some(next) => Poll::Ready(Some(next)),
+            None => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod stream_test {
+    use std::sync::Arc;
+
+    use futures::{executor::block_on, StreamExt};
+
+    use super::*;
+
+    #[test]
+    fn test_stream() {
+        let list = SkipList::new();
+
+        for i in (0..1_000).rev() {
+            list.insert(i, ());
+        }
+
+        let keys = block_on(
+            list.stream()
+                .budget(7)
+                .map(|e| *e.key())
+                .collect::<Vec<_>>(),
+        );
+
+        assert!(keys.into_iter().eq(0..1_000));
+    }
+
+    #[test]
+    fn test_stream_budget() {
+        let list = SkipList::new();
+
+        for i in 0..10 {
+            list.insert(i, ());
+        }
+
+        let mut stream = list.stream().budget(4);
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        let polls = core::iter::from_fn(|| match stream.poll_next_unpin(&mut cx) {
+            Poll::Ready(None) => None,
+            Poll::Ready(Some(_)) => Some(true),
+            Poll::Pending => Some(false),
+        })
+        .collect::<Vec<_>>();
+
+        assert_eq!(polls.iter().filter(|ready| !**ready).count(), 2);
+        assert_eq!(polls.iter().filter(|ready| **ready).count(), 10);
+    }
+
+    #[test]
+    fn test_stream_follow() {
+        let list = Arc::new(SkipList::new());
+        list.insert(0, ());
+
+        let inserter = {
+            let list = list.clone();
+            std::thread::spawn(move || {
+                for i in 1..5 {
+                    std::thread::sleep(std::time::Duration::from_millis(5));
+                    list.insert(i, ());
+                }
+            })
+        };
+
+        let keys = block_on(
+            list.stream()
+                .follow()
+                .map(|e| *e.key())
+                .take(5)
+                .collect::<Vec<_>>(),
+        );
+
+        inserter.join().unwrap();
+
+        assert_eq!(keys, [0, 1, 2, 3, 4]);
+    }
+}
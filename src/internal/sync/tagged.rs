@@ -1,49 +1,85 @@
 use super::NodeRef;
-use crate::internal::utils::Node;
-use haphazard::{AtomicPtr, HazardPointer};
+use crate::internal::utils::{atomic::AtomicPtr, Hazard, Node};
 
-pub(crate) struct MaybeTagged<T>(AtomicPtr<T>);
+/// An atomic pointer that stores a small tag in the low bits its pointee's alignment leaves
+/// unused. See the [tagged](crate::tagged) module for the contracts it relies on.
+#[cfg(not(any(loom, shuttle)))]
+pub struct MaybeTagged<T>(haphazard::AtomicPtr<T>);
+
+/// Under loom or shuttle the pointer has to be one of their atomics, otherwise the scheduler could
+/// not see the accesses to it.
+#[cfg(any(loom, shuttle))]
+pub struct MaybeTagged<T>(AtomicPtr<T>);
 
 impl<T> MaybeTagged<T> {
-    pub(crate) fn load_ptr(&self) -> *mut T {
+    /// The bits of an address that are free to hold a tag, given the alignment of `T`.
+    pub const TAG_MASK: usize = unused_bits::<T>();
+
+    /// Creates an untagged pointer to `ptr`.
+    #[cfg(any(loom, shuttle))]
+    pub fn new(ptr: *mut T) -> Self {
+        MaybeTagged(AtomicPtr::new(ptr))
+    }
+
+    /// Creates an untagged pointer to `ptr`.
+    #[cfg(not(any(loom, shuttle)))]
+    pub fn new(ptr: *mut T) -> Self {
+        // An all zero pattern is a null pointer without a tag, just as in `Node::init`.
+        let tagged: Self = unsafe { core::mem::zeroed() };
+        tagged.store_ptr(ptr);
+        tagged
+    }
+
+    /// Loads the pointer, without its tag.
+    pub fn load_ptr(&self) -> *mut T {
         self.load_decomposed().0
     }
-    pub(crate) fn load_decomposed(&self) -> (*mut T, usize) {
-        let raw = unsafe { self.0.as_std().load(std::sync::atomic::Ordering::Acquire) };
+
+    /// Loads the pointer and its tag.
+    pub fn load_decomposed(&self) -> (*mut T, usize) {
+        let raw = self.as_std().load(std::sync::atomic::Ordering::Acquire);
         Self::decompose_raw(raw)
     }
 
+    /// Splits `raw` into the pointer and the tag stored in its unused low bits. The pointer keeps
+    /// the provenance of `raw`, we only ever touch its address.
     #[inline]
     fn decompose_raw(raw: *mut T) -> (*mut T, usize) {
         (
-            usize_to_ptr_with_provenance(raw as usize & !unused_bits::<T>(), raw),
-            raw as usize & unused_bits::<T>(),
+            raw.map_addr(|addr| addr & !unused_bits::<T>()),
+            raw.addr() & unused_bits::<T>(),
         )
     }
 
-    pub(crate) fn store_composed(&self, ptr: *mut T, tag: usize) {
+    /// Stores `ptr` tagged with `tag`. Bits of `tag` outside of [TAG_MASK](Self::TAG_MASK) are
+    /// dropped.
+    pub fn store_composed(&self, ptr: *mut T, tag: usize) {
         let tagged = Self::compose_raw(ptr, tag);
 
-        unsafe {
-            self.0
-                .as_std()
-                .store(tagged, std::sync::atomic::Ordering::Release);
-        }
+        self.as_std()
+            .store(tagged, std::sync::atomic::Ordering::Release);
     }
 
     #[inline]
     fn compose_raw(ptr: *mut T, tag: usize) -> *mut T {
-        usize_to_ptr_with_provenance(
-            (ptr as usize & !unused_bits::<T>()) | (tag & unused_bits::<T>()),
-            ptr,
-        )
+        const {
+            assert!(
+                unused_bits::<T>() != 0,
+                "pointee is not aligned enough to be tagged"
+            )
+        };
+
+        ptr.map_addr(|addr| (addr & !unused_bits::<T>()) | (tag & unused_bits::<T>()))
     }
 
-    pub(crate) fn store_ptr(&self, ptr: *mut T) {
+    /// Stores `ptr` without a tag.
+    pub fn store_ptr(&self, ptr: *mut T) {
         self.store_composed(ptr, 0);
     }
 
-    pub(crate) fn compare_exchange(
+    /// Replaces an untagged `expected` with an untagged `new`. Returns the previous pointer and
+    /// tag, in `Err` if they were not the expected ones.
+    pub fn compare_exchange(
         &self,
         expected: *mut T,
         new: *mut T,
@@ -51,27 +87,28 @@ impl<T> MaybeTagged<T> {
         self.compare_exchange_with_tag(expected, 0, new, 0)
     }
 
-    pub(crate) fn compare_exchange_with_tag(
+    /// Replaces `expected` tagged with `e_tag` by `new` tagged with `n_tag`. Returns the previous
+    /// pointer and tag, in `Err` if they were not the expected ones.
+    pub fn compare_exchange_with_tag(
         &self,
         expected: *mut T,
         e_tag: usize,
         new: *mut T,
         n_tag: usize,
     ) -> Result<(*mut T, usize), (*mut T, usize)> {
-        unsafe {
-            match self.0.as_std().compare_exchange(
-                Self::compose_raw(expected, e_tag),
-                Self::compose_raw(new, n_tag),
-                std::sync::atomic::Ordering::SeqCst,
-                std::sync::atomic::Ordering::SeqCst,
-            ) {
-                Ok(new) => Ok(Self::decompose_raw(new)),
-                Err(other) => Err(Self::decompose_raw(other)),
-            }
+        match self.as_std().compare_exchange(
+            Self::compose_raw(expected, e_tag),
+            Self::compose_raw(new, n_tag),
+            std::sync::atomic::Ordering::SeqCst,
+            std::sync::atomic::Ordering::SeqCst,
+        ) {
+            Ok(new) => Ok(Self::decompose_raw(new)),
+            Err(other) => Err(Self::decompose_raw(other)),
         }
     }
 
-    pub(crate) fn tag(&self, tag: usize) {
+    /// Sets the tag to `tag`, whatever the pointer and the previous tag are.
+    pub fn tag(&self, tag: usize) {
         let (mut old_ptr, mut old_tag) = self.load_decomposed();
 
         while let Err((other_ptr, other_tag)) =
@@ -81,14 +118,17 @@ impl<T> MaybeTagged<T> {
         }
     }
 
-    pub(crate) fn try_tag(&self, expected: *mut T, tag: usize) -> Result<*mut T, *mut T> {
+    /// Sets the tag to `tag` if the pointer still is `expected`. Returns the pointer found.
+    pub fn try_tag(&self, expected: *mut T, tag: usize) -> Result<*mut T, *mut T> {
         let old_tag = self.load_tag();
         self.compare_exchange_with_tag(expected, old_tag, expected, tag)
             .map(|s| s.0)
             .map_err(|e| e.0)
     }
 
-    pub(crate) fn compare_exchange_tag(&self, e_tag: usize, tag: usize) -> Result<usize, usize> {
+    /// Replaces the tag `e_tag` with `tag`, whatever the pointer is. Returns the tag found in
+    /// `Err` if it was not `e_tag`.
+    pub fn compare_exchange_tag(&self, e_tag: usize, tag: usize) -> Result<usize, usize> {
         let mut ptr = self.load_ptr();
         while let Err((other_ptr, other_tag)) = self.compare_exchange_with_tag(ptr, e_tag, ptr, tag)
         {
@@ -102,17 +142,44 @@ impl<T> MaybeTagged<T> {
         Ok(tag)
     }
 
-    pub(crate) fn load_tag(&self) -> usize {
+    /// Loads the tag, without the pointer.
+    pub fn load_tag(&self) -> usize {
         self.load_decomposed().1
     }
 
-    pub(crate) fn as_std(&self) -> &core::sync::atomic::AtomicPtr<T> {
+    /// The underlying atomic, holding the pointer and the tag composed.
+    #[cfg(not(any(loom, shuttle)))]
+    pub fn as_std(&self) -> &AtomicPtr<T> {
         unsafe { self.0.as_std() }
     }
 
-    pub(crate) fn as_hpz(&self) -> &AtomicPtr<T> {
+    /// The underlying atomic, holding the pointer and the tag composed.
+    #[cfg(any(loom, shuttle))]
+    pub fn as_std(&self) -> &AtomicPtr<T> {
         &self.0
     }
+
+    #[cfg(not(any(loom, shuttle)))]
+    pub(crate) fn as_hpz(&self) -> &haphazard::AtomicPtr<T> {
+        &self.0
+    }
+}
+
+/// The number of tag bits a [MaybeTagged](MaybeTagged) pointer to a [Node](Node) provides on every
+/// target, as nodes are over-aligned to guarantee them.
+pub(crate) const MIN_TAG_BITS: u32 = 2;
+
+const _: () = assert!(unused_bits::<Node<(), ()>>().count_ones() >= MIN_TAG_BITS);
+
+impl<T> core::fmt::Debug for MaybeTagged<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (ptr, tag) = self.load_decomposed();
+
+        f.debug_struct("MaybeTagged")
+            .field("ptr", &ptr)
+            .field("tag", &tag)
+            .finish()
+    }
 }
 
 const fn align<T>() -> usize {
@@ -123,14 +190,9 @@ const fn unused_bits<T>() -> usize {
     (1 << align::<T>().trailing_zeros()) - 1
 }
 
-fn usize_to_ptr_with_provenance<T>(addr: usize, prov: *mut T) -> *mut T {
-    let ptr = prov.cast::<u8>();
-    ptr.wrapping_add(addr.wrapping_sub(ptr as usize)).cast()
-}
-
 impl<'a, K, V> NodeRef<'a, K, V> {
-    pub(crate) fn from_maybe_tagged(maybe_tagged: &MaybeTagged<Node<K, V>>) -> Option<Self> {
-        let mut _hazard = HazardPointer::new();
+    pub(crate) fn from_maybe_tagged(maybe_tagged: &MaybeTagged<super::Node<K, V>>) -> Option<Self> {
+        let mut _hazard = Hazard::new();
         let mut ptr = maybe_tagged.load_ptr();
 
         _hazard.protect_raw(ptr);
@@ -155,4 +217,71 @@ impl<'a, K, V> NodeRef<'a, K, V> {
             }
         }
     }
+
+    /// Reads the link just as [from_maybe_tagged](Self::from_maybe_tagged) does, yet fails if the
+    /// link is tagged once the node it leads to is protected. Only the links of removed nodes are
+    /// tagged, and they keep leading to their old successor, which may be unlinked from the list
+    /// and reclaimed meanwhile. The link of a node that is not removed though leads to a node that
+    /// is still linked, and so not retired yet, once we protected it.
+    pub(crate) fn from_live(
+        maybe_tagged: &MaybeTagged<super::Node<K, V>>,
+    ) -> Result<Option<Self>, ()> {
+        let mut _hazard = Hazard::new();
+        let (mut ptr, _) = maybe_tagged.load_decomposed();
+
+        _hazard.protect_raw(ptr);
+
+        let (mut v_ptr, mut tag) = maybe_tagged.load_decomposed();
+
+        while !core::ptr::eq(ptr, v_ptr) {
+            ptr = v_ptr;
+            _hazard.protect_raw(ptr);
+
+            (v_ptr, tag) = maybe_tagged.load_decomposed();
+        }
+
+        if tag != 0 {
+            return Err(());
+        }
+
+        Ok(core::ptr::NonNull::new(ptr).map(|node| NodeRef { node, _hazard }))
+    }
+}
+
+#[cfg(all(test, not(any(loom, shuttle))))]
+mod tagged_test {
+    use super::*;
+
+    #[test]
+    fn test_tag_keeps_provenance() {
+        let value = Box::into_raw(Box::new(42u64));
+        let tagged = MaybeTagged::<u64>::new(core::ptr::null_mut());
+
+        tagged.store_composed(value, 0b101);
+        assert_eq!(tagged.load_tag(), 0b101);
+
+        // Dereferencing the untagged pointer is only sound if it still carries the provenance of
+        // the allocation, which strict provenance checkers verify.
+        let (ptr, tag) = tagged.load_decomposed();
+        assert_eq!(tag, 0b101);
+        assert_eq!(unsafe { *ptr }, 42);
+
+        unsafe { drop(Box::from_raw(ptr)) };
+    }
+
+    #[test]
+    fn test_tag_bits_of_low_alignment_nodes() {
+        assert!(unused_bits::<Node<u8, ()>>().count_ones() >= MIN_TAG_BITS);
+        assert!(unused_bits::<Node<(), bool>>().count_ones() >= MIN_TAG_BITS);
+
+        let node = Node::new(1u8, (), 1);
+        let tagged = MaybeTagged::new(node);
+
+        for tag in 0..1 << MIN_TAG_BITS {
+            tagged.store_composed(node, tag);
+            assert_eq!(tagged.load_decomposed(), (node, tag));
+        }
+
+        unsafe { Node::drop(node) };
+    }
 }
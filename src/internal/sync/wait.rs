@@ -0,0 +1,143 @@
+//! [Futures](core::future::Future) that resolve once a key, or any key at all, shows up in a list.
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::{Entry, SkipList};
+
+/// Resolves to the [Entry](Entry) of `key` once it has been inserted, see
+/// [wait_for](SkipList::wait_for).
+pub struct WaitFor<'a, K, V> {
+    list: &'a SkipList<'a, K, V>,
+    key: K,
+}
+
+impl<'a, K, V> WaitFor<'a, K, V> {
+    pub fn new(list: &'a SkipList<'a, K, V>, key: K) -> Self {
+        WaitFor { list, key }
+    }
+}
+
+impl<'a, K, V> Future for WaitFor<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    type Output = Entry<'a, K, V>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &*self;
+
+        if let Some(entry) = this.list.get(&this.key) {
+            return Poll::Ready(entry);
+        }
+
+        this.list.wakers.register(cx.waker());
+
+        // An insert that finished before we registered did not wake us, so we look once more.
+        match this.list.get(&this.key) {
+            Some(entry) => Poll::Ready(entry),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// Resolves to the entry it popped off the front of a list once there is one, see
+/// [wait_pop_front](SkipList::wait_pop_front).
+pub struct WaitPopFront<'a, K, V> {
+    list: &'a SkipList<'a, K, V>,
+}
+
+impl<'a, K, V> WaitPopFront<'a, K, V> {
+    pub fn new(list: &'a SkipList<'a, K, V>) -> Self {
+        WaitPopFront { list }
+    }
+}
+
+impl<'a, K, V> Future for WaitPopFront<'a, K, V>
+where
+    K: Ord + Send,
+    V: Send,
+{
+    type Output = Entry<'a, K, V>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(entry) = self.list.pop_front() {
+            return Poll::Ready(entry);
+        }
+
+        self.list.wakers.register(cx.waker());
+
+        // An insert that finished before we registered did not wake us, so we look once more.
+        match self.list.pop_front() {
+            Some(entry) => Poll::Ready(entry),
+            None => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod wait_test {
+    use std::sync::Arc;
+
+    use futures::executor::block_on;
+
+    use super::*;
+
+    #[test]
+    fn test_wait_for_present() {
+        let list = SkipList::new();
+        list.insert(1, "one");
+
+        assert_eq!(*block_on(list.wait_for(1)).val(), "one");
+    }
+
+    #[test]
+    fn test_wait_for() {
+        let list = Arc::new(SkipList::new());
+
+        let inserter = {
+            let list = list.clone();
+            std::thread::spawn(move || {
+                for i in 0..10 {
+                    std::thread::sleep(std::time::Duration::from_millis(2));
+                    list.insert(i, i * 2);
+                }
+            })
+        };
+
+        let entry = block_on(list.wait_for(7));
+
+        assert_eq!((*entry.key(), *entry.val()), (7, 14));
+
+        drop(entry);
+        inserter.join().unwrap();
+    }
+
+    #[test]
+    fn test_wait_pop_front() {
+        let list = Arc::new(SkipList::new());
+        list.insert(0, ());
+
+        let inserter = {
+            let list = list.clone();
+            std::thread::spawn(move || {
+                for i in 1..10 {
+                    std::thread::sleep(std::time::Duration::from_millis(2));
+                    list.insert(i, ());
+                }
+            })
+        };
+
+        // Every insert is popped in turn, the consumer never gets ahead of the inserter.
+        for i in 0..10 {
+            assert_eq!(*block_on(list.wait_pop_front()).key(), i);
+        }
+
+        inserter.join().unwrap();
+        assert!(list.is_empty());
+    }
+}
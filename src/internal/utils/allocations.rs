@@ -0,0 +1,217 @@
+//! Counts the nodes a list allocates and how it lets go of them again, when the `stats` feature
+//! is enabled. Without it, the counters take no space and every function in here compiles down to
+//! nothing.
+//!
+//! A list either frees a node itself, or it retires the node to the hazard pointer domain, which
+//! frees it once no thread protects it anymore. The domain is shared by all lists, so the nodes it
+//! reclaims and the hazard pointers protecting them are only counted for the whole process. Nodes
+//! that stay retired while the same hazard pointers stay alive point to a stalled reclamation, such
+//! as a leaked entry.
+//!
+//! With the `debug-leak-check` feature, dropping a list asserts that every node it allocated was
+//! either freed or retired, which catches nodes that were unlinked and then forgotten.
+//!
+//! Tests also count the lists a thread dropped by freeing their arena at once, as that leaves no
+//! other trace to tell it apart from freeing the nodes one by one.
+
+#[cfg(feature = "stats")]
+use core::sync::atomic::Ordering;
+
+use haphazard::{Domain, Global, HazardPointer};
+
+#[cfg(feature = "stats")]
+use super::atomic::AtomicUsize;
+
+// The process wide counters are not part of any list, so they need not be visible to loom or
+// shuttle, yet they have to be constructible in a `static`.
+#[cfg(feature = "stats")]
+use core::sync::atomic::AtomicUsize as GlobalCounter;
+
+/// The nodes retired to the hazard pointer domain by any list.
+#[cfg(feature = "stats")]
+static RETIRED: GlobalCounter = GlobalCounter::new(0);
+
+/// The retired nodes the hazard pointer domain has freed so far.
+#[cfg(feature = "stats")]
+static RECLAIMED: GlobalCounter = GlobalCounter::new(0);
+
+/// The hazard pointers held by entries and searches right now.
+#[cfg(feature = "stats")]
+static PROTECTING: GlobalCounter = GlobalCounter::new(0);
+
+/// The nodes of a list, as returned by `alloc_stats`.
+#[cfg(feature = "stats")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    /// Nodes the list allocated.
+    pub allocated: usize,
+    /// Nodes the list retired to the hazard pointer domain.
+    pub retired: usize,
+    /// Nodes the list freed itself, or handed over to an owning iterator.
+    pub freed: usize,
+    /// Retired nodes the hazard pointer domain freed, across all lists.
+    pub reclaimed: usize,
+    /// Retired nodes the hazard pointer domain still holds on to, across all lists.
+    pub pending: usize,
+}
+
+#[cfg(feature = "stats")]
+impl AllocStats {
+    /// The nodes that are neither freed nor retired, which are the ones still linked.
+    pub fn live(&self) -> usize {
+        self.allocated - self.retired - self.freed
+    }
+}
+
+pub(crate) struct Allocations {
+    #[cfg(feature = "stats")]
+    allocated: AtomicUsize,
+    #[cfg(feature = "stats")]
+    retired: AtomicUsize,
+    #[cfg(feature = "stats")]
+    freed: AtomicUsize,
+}
+
+impl Allocations {
+    pub(crate) fn new() -> Self {
+        Allocations {
+            #[cfg(feature = "stats")]
+            allocated: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            retired: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            freed: AtomicUsize::new(0),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn allocated(&self) {
+        #[cfg(feature = "stats")]
+        self.allocated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn retired(&self) {
+        #[cfg(feature = "stats")]
+        {
+            self.retired.fetch_add(1, Ordering::Relaxed);
+            RETIRED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn freed(&self, _nodes: usize) {
+        #[cfg(feature = "stats")]
+        self.freed.fetch_add(_nodes, Ordering::Relaxed);
+    }
+
+    #[cfg(feature = "stats")]
+    pub(crate) fn snapshot(&self) -> AllocStats {
+        let (retired, reclaimed) = (
+            RETIRED.load(Ordering::Relaxed),
+            RECLAIMED.load(Ordering::Relaxed),
+        );
+
+        AllocStats {
+            allocated: self.allocated.load(Ordering::Relaxed),
+            retired: self.retired.load(Ordering::Relaxed),
+            freed: self.freed.load(Ordering::Relaxed),
+            reclaimed,
+            pending: retired.saturating_sub(reclaimed),
+        }
+    }
+
+    /// Asserts that every node the list allocated was freed or retired. Called once the list has
+    /// dropped its nodes.
+    #[inline]
+    pub(crate) fn check_leaks(&self) {
+        #[cfg(feature = "debug-leak-check")]
+        {
+            let stats = self.snapshot();
+
+            assert_eq!(
+                stats.allocated,
+                stats.retired + stats.freed,
+                "the list leaked {} nodes",
+                stats.live()
+            );
+        }
+    }
+}
+
+/// Counts a retired node that the hazard pointer domain freed.
+#[inline]
+pub(crate) fn reclaimed() {
+    #[cfg(feature = "stats")]
+    RECLAIMED.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+thread_local! {
+    /// The lists this thread dropped by freeing their arena at once.
+    static FREED_AT_ONCE: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+}
+
+/// Counts a list whose nodes were freed at once along with its arena.
+#[inline]
+pub(crate) fn freed_at_once() {
+    #[cfg(test)]
+    FREED_AT_ONCE.set(FREED_AT_ONCE.get() + 1);
+}
+
+/// The lists this thread dropped by freeing their arena at once.
+#[cfg(test)]
+pub(crate) fn freed_at_once_count() -> usize {
+    FREED_AT_ONCE.get()
+}
+
+/// The nodes retired to the hazard pointer domain by all lists.
+#[cfg(feature = "stats")]
+pub(crate) fn retired_count() -> usize {
+    RETIRED.load(Ordering::Relaxed)
+}
+
+/// The retired nodes the hazard pointer domain freed, for all lists.
+#[cfg(feature = "stats")]
+pub(crate) fn reclaimed_count() -> usize {
+    RECLAIMED.load(Ordering::Relaxed)
+}
+
+/// The hazard pointers currently held by entries and searches, of all lists.
+#[cfg(feature = "stats")]
+pub(crate) fn protected_count() -> usize {
+    PROTECTING.load(Ordering::Relaxed)
+}
+
+/// A hazard pointer of the global domain, counted while it is alive. It is laid out just as the
+/// hazard pointer it wraps, and without the `stats` feature does nothing on top of it.
+#[repr(transparent)]
+pub(crate) struct Hazard<'domain>(HazardPointer<'domain, Global>);
+
+impl<'domain> Hazard<'domain> {
+    #[inline]
+    pub(crate) fn new() -> Hazard<'static> {
+        Hazard::new_in_domain(Domain::global())
+    }
+
+    #[inline]
+    pub(crate) fn new_in_domain(domain: &'domain Domain<Global>) -> Self {
+        #[cfg(feature = "stats")]
+        PROTECTING.fetch_add(1, Ordering::Relaxed);
+
+        Hazard(HazardPointer::new_in_domain(domain))
+    }
+
+    #[inline]
+    pub(crate) fn protect_raw<T>(&mut self, ptr: *mut T) {
+        self.0.protect_raw(ptr);
+    }
+}
+
+#[cfg(feature = "stats")]
+impl<'domain> Drop for Hazard<'domain> {
+    #[inline]
+    fn drop(&mut self) {
+        PROTECTING.fetch_sub(1, Ordering::Relaxed);
+    }
+}
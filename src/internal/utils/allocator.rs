@@ -0,0 +1,112 @@
+//! Placing nodes in memory of the caller's choosing, such as an arena, a bump allocator or a
+//! shared memory segment, rather than handing them to the global allocator.
+
+use core::alloc::Layout;
+use core::ptr::NonNull;
+use std::sync::Arc;
+
+/// Hands out the memory the nodes of a list live in, see
+/// [Builder::allocator](super::Builder::allocator).
+///
+/// Every node keeps the allocator it came from alive, so nodes that are reclaimed after their
+/// list is dropped still find their way back to it. The head of a list always lives on the
+/// global heap.
+///
+/// # Safety
+///
+/// Memory returned by [allocate](Self::allocate) must fit `layout`, must not overlap memory handed
+/// out before and must stay valid until it is passed to [deallocate](Self::deallocate). Other
+/// threads may access it in the meantime.
+pub unsafe trait NodeAllocator: Send + Sync {
+    /// Allocates memory fitting `layout`, which never has a size of zero. Returns `None` if the
+    /// allocator is exhausted, which the list treats as an allocation error.
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+    /// Gives back the memory at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` was returned by [allocate](Self::allocate) of this very allocator for `layout`, and
+    /// is given back only once.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+/// The allocator a node came from, stored right in front of the node.
+pub(crate) struct Header {
+    pub(crate) allocator: Arc<dyn NodeAllocator>,
+}
+
+/// The layout of a node laid out as `node`, with a header `H`, such as a [Header](Header), in front
+/// of it, along with the offset of the node within that layout.
+pub(crate) fn with_header<H>(node: Layout) -> (Layout, usize) {
+    let (layout, offset) = Layout::new::<H>()
+        .extend(node)
+        .expect("the layout of a node with a header overflows");
+
+    (layout.pad_to_align(), offset)
+}
+
+#[cfg(test)]
+mod allocator_test {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{SkipList, SyncSkipList};
+
+    /// Hands memory out of the global allocator, keeping track of what is live.
+    #[derive(Default)]
+    struct Counting {
+        live: AtomicUsize,
+        total: AtomicUsize,
+    }
+
+    unsafe impl NodeAllocator for Counting {
+        fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+            self.live.fetch_add(1, Ordering::Relaxed);
+            self.total.fetch_add(1, Ordering::Relaxed);
+
+            NonNull::new(unsafe { std::alloc::alloc(layout) })
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.live.fetch_sub(1, Ordering::Relaxed);
+
+            std::alloc::dealloc(ptr.as_ptr(), layout)
+        }
+    }
+
+    #[test]
+    fn test_allocator() {
+        let counting = Arc::new(Counting::default());
+
+        let mut list = SkipList::builder().allocator(counting.clone()).build();
+        let sync_list = SyncSkipList::builder()
+            .allocator(counting.clone())
+            .capacity(100)
+            .build();
+
+        for i in 0..100 {
+            list.insert(i, i.to_string());
+            sync_list.insert(i, i.to_string());
+        }
+
+        // Replacing a value keeps the node.
+        sync_list.insert(0, String::new());
+
+        for i in 0..50 {
+            list.remove(&i);
+            sync_list.remove(&i);
+        }
+
+        assert_eq!(counting.total.load(Ordering::Relaxed), 200);
+        assert!(sync_list.iter().map(|e| *e.key()).eq(50..100));
+        assert_eq!(list.get(&75).map(|e| e.val().as_str()), Some("75"));
+
+        drop(list);
+        drop(sync_list);
+
+        // Every node went back to the allocator, the lists let go of it.
+        assert_eq!(counting.live.load(Ordering::Relaxed), 0);
+        assert_eq!(Arc::strong_count(&counting), 1);
+    }
+}
@@ -0,0 +1,158 @@
+//! A fixed size bump arena that nodes can be carved out of.
+//!
+//! The arena is sized once up front and never grows. Once it is exhausted, allocations fall back
+//! to the global allocator. Memory handed out by the arena is only returned when the arena itself
+//! is dropped, so freeing an arena backed [Node](super::Node) is a no-op.
+//!
+//! A list shares its arena through an `Arc`. Nodes the concurrent list retires may only be
+//! reclaimed after the list itself is gone, so every retired arena node holds a reference to the
+//! arena, found through the [ArenaHeader](ArenaHeader) in front of it, until it is reclaimed.
+
+extern crate alloc;
+
+use super::atomic::{AtomicBool, AtomicUsize};
+use alloc::alloc::{alloc, dealloc, Layout};
+use alloc::sync::Arc;
+use core::{ptr::NonNull, sync::atomic::Ordering};
+
+pub(crate) struct Arena {
+    chunk: NonNull<u8>,
+    layout: Layout,
+    offset: AtomicUsize,
+    overflowed: AtomicBool,
+}
+
+/// The arena a node was carved out of, stored right in front of the node.
+pub(crate) struct ArenaHeader {
+    pub(crate) arena: *const Arena,
+}
+
+impl Arena {
+    /// Reserves a chunk of `bytes` bytes. Returns `None` if nothing needs reserving or the
+    /// allocation failed, in which case the list should just use the global allocator.
+    pub(crate) fn with_bytes(bytes: usize, align: usize) -> Option<Self> {
+        if bytes == 0 {
+            return None;
+        }
+
+        let layout = Layout::from_size_align(bytes, align).ok()?;
+
+        // # Safety
+        //
+        // `layout` has a non-zero size.
+        let chunk = NonNull::new(unsafe { alloc(layout) })?;
+
+        Some(Arena {
+            chunk,
+            layout,
+            offset: AtomicUsize::new(0),
+            overflowed: AtomicBool::new(false),
+        })
+    }
+
+    /// Bumps the offset of the arena to fit `layout`. Returns `None` once the arena is exhausted.
+    pub(crate) fn alloc(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let base = self.chunk.as_ptr().addr();
+        let mut offset = self.offset.load(Ordering::Relaxed);
+
+        loop {
+            let start = (base + offset + layout.align() - 1) & !(layout.align() - 1);
+            let end = start - base + layout.size();
+
+            if end > self.layout.size() {
+                self.overflowed.store(true, Ordering::Relaxed);
+                return None;
+            }
+
+            match self.offset.compare_exchange_weak(
+                offset,
+                end,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                // # Safety
+                //
+                // `start - base` is in bounds of the chunk, as `end` is.
+                Ok(_) => {
+                    return unsafe {
+                        Some(NonNull::new_unchecked(
+                            self.chunk.as_ptr().add(start - base),
+                        ))
+                    }
+                }
+                Err(other) => offset = other,
+            }
+        }
+    }
+
+    /// Whether an allocation ever had to fall back to the global allocator.
+    pub(crate) fn overflowed(&self) -> bool {
+        self.overflowed.load(Ordering::Relaxed)
+    }
+
+    /// Whether `ptr` was handed out by this arena.
+    #[allow(dead_code)]
+    pub(crate) fn contains<T>(&self, ptr: *const T) -> bool {
+        let base = self.chunk.as_ptr().addr();
+        (base..base + self.layout.size()).contains(&ptr.addr())
+    }
+
+    /// Keeps the arena alive for a node that is about to be retired.
+    ///
+    /// # Safety
+    ///
+    /// `arena` comes from [Arc::as_ptr] of an arena that is still alive.
+    pub(crate) unsafe fn retain(arena: *const Arena) {
+        Arc::increment_strong_count(arena)
+    }
+
+    /// Lets go of the reference a reclaimed node held on to, freeing the arena if it was the last.
+    ///
+    /// # Safety
+    ///
+    /// `arena` was [retained](Self::retain) before and is released only once for that.
+    pub(crate) unsafe fn release(arena: *const Arena) {
+        Arc::decrement_strong_count(arena)
+    }
+}
+
+impl Drop for Arena {
+    fn drop(&mut self) {
+        // # Safety
+        //
+        // The chunk was allocated with exactly this layout.
+        unsafe { dealloc(self.chunk.as_ptr(), self.layout) }
+    }
+}
+
+unsafe impl Send for Arena {}
+
+unsafe impl Sync for Arena {}
+
+#[cfg(test)]
+mod arena_test {
+    use super::*;
+
+    #[test]
+    fn test_arena_bump() {
+        let arena = Arena::with_bytes(64, 8).unwrap();
+
+        let first = arena
+            .alloc(Layout::from_size_align(24, 8).unwrap())
+            .unwrap();
+        let second = arena
+            .alloc(Layout::from_size_align(24, 8).unwrap())
+            .unwrap();
+
+        assert!(arena.contains(first.as_ptr()));
+        assert!(arena.contains(second.as_ptr()));
+        assert_eq!(second.as_ptr() as usize - first.as_ptr() as usize, 24);
+
+        // Only 16 bytes are left.
+        assert!(!arena.overflowed());
+        assert!(arena
+            .alloc(Layout::from_size_align(24, 8).unwrap())
+            .is_none());
+        assert!(arena.overflowed());
+    }
+}
@@ -0,0 +1,38 @@
+//! The atomic types the lists are built from. Compiling with `--cfg loom` swaps them for
+//! [loom](loom)'s model checked versions, so the interleavings of concurrent operations can be
+//! explored exhaustively. `--cfg shuttle` does the same for [shuttle](shuttle)'s randomized
+//! schedules, see the `harness` module.
+//!
+//! There is no fallback for targets without compare and swap, such as `thumbv6m-none-eabi` or
+//! `riscv32imc-unknown-none-elf`. Emulating it on the crate's own atomics would not be enough: the
+//! hazard pointer domain that nodes are retired to needs native compare and swap, and the lists
+//! need `std`, which those targets lack. The crate refuses to build for them up front, see the
+//! crate root.
+
+#[cfg(not(any(loom, shuttle)))]
+pub(crate) use core::{
+    hint::spin_loop,
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize},
+};
+
+// Loom needs to know about busy waiting, otherwise it would keep scheduling the spinning thread.
+#[cfg(loom)]
+pub(crate) use loom::{
+    hint::spin_loop,
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize},
+};
+
+#[cfg(all(shuttle, not(loom)))]
+pub(crate) use shuttle::{
+    hint::spin_loop,
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize},
+};
+
+// The metadata word of a node takes no part in any operation, so the model checkers need not see
+// it.
+#[cfg(feature = "metadata")]
+pub(crate) use core::sync::atomic::AtomicU64;
+
+// Nor do the spans of a node, which only change while the list is borrowed mutably.
+#[cfg(feature = "rank")]
+pub(crate) use core::sync::atomic::AtomicUsize as Span;
@@ -0,0 +1,158 @@
+//! Collects the options a list is built with, so each of them need not get a constructor of its
+//! own. Every list type implements `build` on the builder of its type.
+
+use core::marker::PhantomData;
+use std::sync::Arc;
+
+use super::{Callbacks, NodeAllocator, HEIGHT};
+
+/// Implemented by the lists a [Builder](Builder) builds, naming the keys and values its
+/// callbacks are given.
+pub trait Buildable {
+    type Key;
+    type Val;
+}
+
+/// Configures a list before building it, see `SkipList::builder`. Options left unset keep the
+/// defaults of `SkipList::new`.
+///
+/// There is no option for the hazard pointer domain. Lists protect and retire their nodes in the
+/// global domain of haphazard, which is the only domain of its `Global` family there is. Any other
+/// domain needs a family of its own, and every entry, iterator and search of the list would have
+/// to carry that family as a type parameter, just to keep their protections in the same domain as
+/// the retired nodes.
+pub struct Builder<L: Buildable> {
+    pub(crate) max_height: usize,
+    pub(crate) probability: Option<f64>,
+    pub(crate) seed: Option<usize>,
+    pub(crate) capacity: Option<usize>,
+    pub(crate) auto_tuning: bool,
+    pub(crate) allocator: Option<Arc<dyn NodeAllocator>>,
+    pub(crate) callbacks: Callbacks<L::Key, L::Val>,
+    _list: PhantomData<L>,
+}
+
+impl<L: Buildable> Builder<L> {
+    pub(crate) fn new() -> Self {
+        Builder {
+            max_height: HEIGHT,
+            probability: None,
+            seed: None,
+            capacity: None,
+            auto_tuning: false,
+            allocator: None,
+            callbacks: Callbacks::default(),
+            _list: PhantomData,
+        }
+    }
+
+    /// Caps the towers of the list at `height` levels. The head gets just as many levels, so
+    /// small lists built with a low cap spend less memory on it. Searches still keep their way
+    /// down in arrays of 32 levels, only the levels below the cap are ever filled.
+    ///
+    /// No list goes beyond 32 levels, as the word a node keeps its height in leaves no room for
+    /// more, next to its reference count and flags. At the default probability, that suits lists
+    /// of up to about 2^32 entries, and with a lower probability even larger ones.
+    ///
+    /// # Panics
+    ///
+    /// If `height` is not within `1..=32`.
+    pub fn max_height(mut self, height: usize) -> Self {
+        assert!(
+            (1..=HEIGHT).contains(&height),
+            "height must lie within 1..={}",
+            HEIGHT
+        );
+
+        self.max_height = height;
+        self
+    }
+
+    /// Sets the chance of a new tower to reach one more level, as
+    /// `set_promotion_probability` does.
+    ///
+    /// # Panics
+    ///
+    /// If `p` does not lie within `0.0..1.0`.
+    pub fn probability(mut self, p: f64) -> Self {
+        assert!((0.0..1.0).contains(&p), "p must lie within 0.0..1.0");
+
+        self.probability = Some(p);
+        self
+    }
+
+    /// Gives every level about `factor` times fewer towers than the one below, which is a
+    /// probability of `1 / factor`. LevelDB goes with a factor of 4, trading a few more
+    /// comparisons per search for towers half as high as those of the default factor of 2.
+    ///
+    /// # Panics
+    ///
+    /// If `factor` is below 2.
+    pub fn branching_factor(self, factor: usize) -> Self {
+        assert!(factor >= 2, "factor must be at least 2");
+
+        self.probability(1.0 / factor as f64)
+    }
+
+    /// Seeds the generator of tower heights, so lists built with the same seed and fed the same
+    /// inserts from a single thread end up with the same towers.
+    pub fn seed(mut self, seed: usize) -> Self {
+        // The generator never leaves a seed of 0.
+        self.seed = Some(seed.max(1));
+        self
+    }
+
+    /// Reserves storage for roughly `capacity` nodes up front, as `with_capacity` does.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Lets the list pick its promotion probability by itself, as `set_auto_tuning` does.
+    pub fn auto_tuning(mut self) -> Self {
+        self.auto_tuning = true;
+        self
+    }
+
+    /// Places the nodes of the list with `allocator` instead of the global allocator. This takes
+    /// the place of the storage [capacity](Self::capacity) reserves.
+    pub fn allocator(mut self, allocator: Arc<dyn NodeAllocator>) -> Self {
+        self.allocator = Some(allocator);
+        self
+    }
+
+    /// Calls `f` with the key and value of every insert, right after linking its node, replacing
+    /// any earlier callback. Replacing the value of a key counts as removing the old value and
+    /// inserting the new one.
+    ///
+    /// The callback runs on the inserting thread, which it holds up until it returns, so it should
+    /// be fast.
+    pub fn on_insert(mut self, f: impl Fn(&L::Key, &L::Val) + Send + Sync + 'static) -> Self {
+        self.callbacks.on_insert = Some(Box::new(f));
+        self
+    }
+
+    /// Calls `f` with the key and value of every removal, right after unlinking its node,
+    /// replacing any earlier callback. Removing through an entry of the concurrent list bypasses
+    /// the list, and so the callback.
+    ///
+    /// The callback runs on the removing thread, which it holds up until it returns, so it should
+    /// be fast.
+    pub fn on_remove(mut self, f: impl Fn(&L::Key, &L::Val) + Send + Sync + 'static) -> Self {
+        self.callbacks.on_remove = Some(Box::new(f));
+        self
+    }
+}
+
+impl<L: Buildable> core::fmt::Debug for Builder<L> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Builder")
+            .field("max_height", &self.max_height)
+            .field("probability", &self.probability)
+            .field("seed", &self.seed)
+            .field("capacity", &self.capacity)
+            .field("auto_tuning", &self.auto_tuning)
+            .field("allocator", &self.allocator.is_some())
+            .finish_non_exhaustive()
+    }
+}
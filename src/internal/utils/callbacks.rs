@@ -0,0 +1,37 @@
+//! The callbacks a list is built with, see `Builder::on_insert` and `Builder::on_remove`. They let
+//! users keep derived state, such as counters or secondary indices, in step with the list.
+//!
+//! Callbacks run on the thread whose operation changed the list, right after the change took
+//! effect, and hold up that operation until they return. They should be fast and must not block
+//! on other threads operating on the same list.
+
+/// A callback run with the key and value an operation linked or unlinked.
+type Callback<K, V> = Box<dyn Fn(&K, &V) + Send + Sync>;
+
+pub(crate) struct Callbacks<K, V> {
+    pub(crate) on_insert: Option<Callback<K, V>>,
+    pub(crate) on_remove: Option<Callback<K, V>>,
+}
+
+impl<K, V> Callbacks<K, V> {
+    pub(crate) fn inserted(&self, key: &K, val: &V) {
+        if let Some(on_insert) = &self.on_insert {
+            on_insert(key, val);
+        }
+    }
+
+    pub(crate) fn removed(&self, key: &K, val: &V) {
+        if let Some(on_remove) = &self.on_remove {
+            on_remove(key, val);
+        }
+    }
+}
+
+impl<K, V> Default for Callbacks<K, V> {
+    fn default() -> Self {
+        Callbacks {
+            on_insert: None,
+            on_remove: None,
+        }
+    }
+}
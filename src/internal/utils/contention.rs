@@ -0,0 +1,110 @@
+//! Counts how often operations of a list get in each other's way, when the `stats` feature is
+//! enabled. Without it, the counters take no space and every function in here compiles down to
+//! nothing.
+
+#[cfg(feature = "stats")]
+use core::sync::atomic::Ordering;
+
+#[cfg(feature = "stats")]
+use super::atomic::AtomicUsize;
+
+/// The contention a list has seen since it was created, as returned by `contention_stats`.
+/// Dividing the counts by `operations` gives the contention each operation sees on average.
+#[cfg(feature = "stats")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ContentionStats {
+    /// Calls to `insert` and `remove`.
+    pub operations: usize,
+    /// Compare and swaps that failed while linking a new node.
+    pub link_failures: usize,
+    /// Compare and swaps that failed while unlinking a removed node.
+    pub unlink_failures: usize,
+    /// Searches that started over from the head after losing a race.
+    pub find_restarts: usize,
+    /// Levels of removed nodes that a search unlinked on behalf of their remover.
+    pub helped_unlinks: usize,
+    /// Towers of new nodes that a search finished linking on behalf of their inserter.
+    pub helped_links: usize,
+}
+
+pub(crate) struct Contention {
+    #[cfg(feature = "stats")]
+    operations: AtomicUsize,
+    #[cfg(feature = "stats")]
+    link_failures: AtomicUsize,
+    #[cfg(feature = "stats")]
+    unlink_failures: AtomicUsize,
+    #[cfg(feature = "stats")]
+    find_restarts: AtomicUsize,
+    #[cfg(feature = "stats")]
+    helped_unlinks: AtomicUsize,
+    #[cfg(feature = "stats")]
+    helped_links: AtomicUsize,
+}
+
+impl Contention {
+    pub(crate) fn new() -> Self {
+        Contention {
+            #[cfg(feature = "stats")]
+            operations: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            link_failures: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            unlink_failures: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            find_restarts: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            helped_unlinks: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            helped_links: AtomicUsize::new(0),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn operation(&self) {
+        #[cfg(feature = "stats")]
+        self.operations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn link_failed(&self) {
+        #[cfg(feature = "stats")]
+        self.link_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn unlink_failed(&self) {
+        #[cfg(feature = "stats")]
+        self.unlink_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn find_restarted(&self) {
+        #[cfg(feature = "stats")]
+        self.find_restarts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn helped_unlink(&self) {
+        #[cfg(feature = "stats")]
+        self.helped_unlinks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn helped_link(&self) {
+        #[cfg(feature = "stats")]
+        self.helped_links.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[cfg(feature = "stats")]
+    pub(crate) fn snapshot(&self) -> ContentionStats {
+        ContentionStats {
+            operations: self.operations.load(Ordering::Relaxed),
+            link_failures: self.link_failures.load(Ordering::Relaxed),
+            unlink_failures: self.unlink_failures.load(Ordering::Relaxed),
+            find_restarts: self.find_restarts.load(Ordering::Relaxed),
+            helped_unlinks: self.helped_unlinks.load(Ordering::Relaxed),
+            helped_links: self.helped_links.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Renders the towers of a list as an ASCII diagram or a Graphviz graph.
+
+use core::fmt::{Debug, Write as _};
+use std::io::{self, Write};
+
+/// Writes one line per level of the list, from the top down. Each line shows the keys of the
+/// nodes whose towers reach that level, given as `(key, height)` in list order:
+///
+/// ```text
+/// L2 | head ------> 1 ------> 3
+/// L1 | head -> 0 -> 1 -> 2 -> 3
+/// ```
+pub(crate) fn dump_levels<W>(
+    mut w: W,
+    towers: impl Iterator<Item = (String, usize)>,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let towers = towers.collect::<Vec<_>>();
+    let height = towers.iter().map(|(_, height)| *height).max().unwrap_or(1);
+    let label = height.to_string().len();
+
+    for level in (1..=height).rev() {
+        let mut line = format!("L{:<label$} | head ", level);
+        let mut skipped = String::new();
+
+        for (key, height) in towers.iter() {
+            if *height >= level {
+                line.push_str(&skipped);
+                line.push_str(&format!("-> {} ", key));
+                skipped.clear();
+            } else {
+                skipped.push_str(&"-".repeat(key.chars().count() + 4));
+            }
+        }
+
+        writeln!(w, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+/// Builds a Graphviz description of a list. Nodes are identified by their address, links are
+/// labeled with their level and tagged links are drawn dashed and red.
+pub(crate) struct Dot(String);
+
+impl Dot {
+    pub(crate) fn new() -> Self {
+        Dot(String::from(
+            "digraph SkipList {\n    rankdir=LR;\n    node [shape=box];\n    head;\n",
+        ))
+    }
+
+    pub(crate) fn node<K: Debug>(&mut self, id: usize, key: &K, height: usize, removed: bool) {
+        let key = format!("{:?}", key)
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"");
+        let style = if removed { ", style=dashed" } else { "" };
+
+        let _ = writeln!(
+            self.0,
+            "    n{:x} [label=\"{}\\nheight {}\"{}];",
+            id, key, height, style
+        );
+    }
+
+    /// Adds the link on `level` from the node `from`, or the head if it is `None`, to `to`.
+    pub(crate) fn link(&mut self, from: Option<usize>, to: usize, level: usize, tagged: bool) {
+        let from = from.map_or(String::from("head"), |from| format!("n{:x}", from));
+        let style = if tagged {
+            ", style=dashed, color=red"
+        } else {
+            ""
+        };
+
+        let _ = writeln!(
+            self.0,
+            "    {} -> n{:x} [label=\"{}\"{}];",
+            from, to, level, style
+        );
+    }
+
+    pub(crate) fn finish(mut self) -> String {
+        self.0.push_str("}\n");
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod dump_test {
+    use super::*;
+
+    #[test]
+    fn test_dump_levels() {
+        let towers = [(0, 1), (1, 2), (2, 1), (3, 2), (40, 1)];
+        let mut out = Vec::new();
+
+        dump_levels(&mut out, towers.iter().map(|(k, h)| (k.to_string(), *h))).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "L2 | head ------> 1 ------> 3\nL1 | head -> 0 -> 1 -> 2 -> 3 -> 40\n"
+        );
+    }
+
+    #[test]
+    fn test_dump_empty() {
+        let mut out = Vec::new();
+
+        dump_levels(&mut out, core::iter::empty()).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "L1 | head\n");
+    }
+
+    #[test]
+    fn test_dot() {
+        let mut dot = Dot::new();
+
+        dot.node(0x10, &"a\"b", 2, false);
+        dot.node(0x20, &7, 1, true);
+        dot.link(None, 0x10, 0, false);
+        dot.link(Some(0x10), 0x20, 0, true);
+
+        let dot = dot.finish();
+
+        assert!(dot.starts_with("digraph SkipList {"));
+        assert!(dot.contains("n10 [label=\"\\\"a\\\\\\\"b\\\"\\nheight 2\"];"));
+        assert!(dot.contains("n20 [label=\"7\\nheight 1\", style=dashed];"));
+        assert!(dot.contains("head -> n10 [label=\"0\"];"));
+        assert!(dot.contains("n10 -> n20 [label=\"0\", style=dashed, color=red];"));
+        assert!(dot.ends_with("}\n"));
+    }
+}
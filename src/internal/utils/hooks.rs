@@ -0,0 +1,66 @@
+//! Lets tests pause an operation at a chosen point, in order to force an interleaving with other
+//! threads. The hooks are only called with the `testing` feature, without it [yield_point](
+//! yield_point) compiles down to nothing.
+
+#[cfg(feature = "testing")]
+use std::cell::Cell;
+
+/// The points at which an operation calls the yield hook of its thread.
+#[cfg_attr(not(feature = "testing"), allow(unreachable_pub))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YieldPoint {
+    /// A search drops down to `level`.
+    Descend { level: usize },
+    /// An insert is about to link its new node on `level`.
+    Link { level: usize },
+    /// A remove tagged its node and is about to unlink it.
+    Tagged,
+    /// A remove is about to unlink its node on `level`.
+    Unlink { level: usize },
+}
+
+#[cfg(feature = "testing")]
+type Hook = Box<dyn FnMut(YieldPoint)>;
+
+#[cfg(feature = "testing")]
+thread_local! {
+    static HOOK: Cell<Option<Hook>> = const { Cell::new(None) };
+}
+
+/// Calls the yield hook of this thread, if it has one.
+#[inline]
+pub(crate) fn yield_point(_point: YieldPoint) {
+    #[cfg(feature = "testing")]
+    if let Some(mut hook) = HOOK.take() {
+        // The hook is taken out while it runs, so it may itself operate on lists.
+        hook(_point);
+
+        HOOK.with(|slot| {
+            if let Some(other) = slot.replace(Some(hook)) {
+                slot.set(Some(other));
+            }
+        });
+    }
+}
+
+/// Removes the yield hook of its thread once dropped.
+#[cfg(feature = "testing")]
+#[must_use = "the hook is removed as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct HookGuard(());
+
+#[cfg(feature = "testing")]
+impl Drop for HookGuard {
+    fn drop(&mut self) {
+        HOOK.set(None);
+    }
+}
+
+/// Installs `hook` for the current thread, replacing any previous one. Every operation of this
+/// thread calls it at each [YieldPoint](YieldPoint) it passes, until the guard is dropped.
+#[cfg(feature = "testing")]
+pub fn set_yield_hook(hook: impl FnMut(YieldPoint) + 'static) -> HookGuard {
+    HOOK.set(Some(Box::new(hook)));
+
+    HookGuard(())
+}
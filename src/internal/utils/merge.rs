@@ -0,0 +1,99 @@
+//! Merging several sorted sequences of `(key, value)` pairs into one.
+
+use core::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+struct Head<K, V> {
+    key: K,
+    val: V,
+    source: usize,
+}
+
+impl<K: Ord, V> Ord for Head<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key
+            .cmp(&other.key)
+            .then(self.source.cmp(&other.source))
+    }
+}
+
+impl<K: Ord, V> PartialOrd for Head<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, V> PartialEq for Head<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl<K: Ord, V> Eq for Head<K, V> {}
+
+/// Merges `sources`, each sorted by key without duplicates, into a single sorted sequence. If a
+/// key is in more than one source, `resolve` combines its values, in the order of the sources.
+pub(crate) fn merge_sorted<K, V, I, F>(sources: Vec<I>, resolve: F) -> impl Iterator<Item = (K, V)>
+where
+    K: Ord,
+    I: Iterator<Item = (K, V)>,
+    F: Fn(&K, Vec<V>) -> V,
+{
+    let mut sources = sources;
+    let count = sources.len();
+    let mut heads = BinaryHeap::with_capacity(count);
+
+    let mut refill = move |heads: &mut BinaryHeap<_>, source: usize| {
+        if let Some((key, val)) = sources[source].next() {
+            heads.push(Reverse(Head { key, val, source }));
+        }
+    };
+
+    for source in 0..count {
+        refill(&mut heads, source);
+    }
+
+    core::iter::from_fn(move || {
+        let Reverse(head) = heads.pop()?;
+        refill(&mut heads, head.source);
+
+        if heads.peek().is_none_or(|next| next.0.key != head.key) {
+            return Some((head.key, head.val));
+        }
+
+        let mut vals = vec![head.val];
+
+        while let Some(Reverse(next)) = heads.peek() {
+            if next.key != head.key {
+                break;
+            }
+
+            let Reverse(next) = heads.pop().unwrap();
+            refill(&mut heads, next.source);
+            vals.push(next.val);
+        }
+
+        let val = resolve(&head.key, vals);
+
+        Some((head.key, val))
+    })
+}
+
+#[cfg(test)]
+mod merge_test {
+    use super::*;
+
+    #[test]
+    fn test_merge_sorted() {
+        let sources = vec![
+            vec![(1, "a"), (4, "a")].into_iter(),
+            vec![].into_iter(),
+            vec![(0, "c"), (1, "c"), (5, "c")].into_iter(),
+            vec![(1, "d"), (4, "d")].into_iter(),
+        ];
+
+        let merged = merge_sorted(sources, |_, vals| vals.concat().leak()).collect::<Vec<_>>();
+
+        assert_eq!(merged, [(0, "c"), (1, "acd"), (4, "ad"), (5, "c")]);
+    }
+}
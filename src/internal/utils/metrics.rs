@@ -0,0 +1,116 @@
+//! Publishes the health of the lists through the [metrics](::metrics) facade when the `metrics`
+//! feature is enabled. Without it, every function in here compiles down to nothing.
+//!
+//! | name                     | kind      | description                                     |
+//! |--------------------------|-----------|-------------------------------------------------|
+//! | `skippy.inserts`         | counter   | calls to `insert`                               |
+//! | `skippy.removes`         | counter   | successful calls to `remove`                    |
+//! | `skippy.cas_retries`     | counter   | operations restarted after losing a CAS race    |
+//! | `skippy.nodes_retired`   | counter   | nodes handed to the hazard pointer domain       |
+//! | `skippy.reclaim_runs`    | counter   | eager reclamations of the hazard pointer domain |
+//! | `skippy.nodes_reclaimed` | histogram | nodes freed by a single reclamation             |
+
+#[inline]
+pub(crate) fn inserted() {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!("skippy.inserts").increment(1);
+}
+
+#[inline]
+pub(crate) fn removed() {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!("skippy.removes").increment(1);
+}
+
+#[inline]
+pub(crate) fn cas_retried() {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!("skippy.cas_retries").increment(1);
+}
+
+#[inline]
+pub(crate) fn retired() {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!("skippy.nodes_retired").increment(1);
+}
+
+#[inline]
+pub(crate) fn reclaimed(_nodes: usize) {
+    #[cfg(feature = "metrics")]
+    {
+        ::metrics::counter!("skippy.reclaim_runs").increment(1);
+        ::metrics::histogram!("skippy.nodes_reclaimed").record(_nodes as f64);
+    }
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod metrics_test {
+    use std::{
+        collections::BTreeMap,
+        sync::{atomic::AtomicU64, atomic::Ordering, Arc, Mutex},
+    };
+
+    use ::metrics::{
+        Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+
+    use crate::SyncSkipList;
+
+    #[derive(Default)]
+    struct Counts(Mutex<BTreeMap<String, Arc<AtomicU64>>>);
+
+    impl Recorder for Counts {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let mut counts = self.0.lock().unwrap();
+            let count = counts.entry(key.name().to_string()).or_default();
+
+            Counter::from_arc(count.clone())
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    impl Counts {
+        fn get(&self, name: &str) -> u64 {
+            self.0
+                .lock()
+                .unwrap()
+                .get(name)
+                .map_or(0, |c| c.load(Ordering::Relaxed))
+        }
+    }
+
+    #[test]
+    fn test_counters() {
+        let counts = Counts::default();
+
+        ::metrics::with_local_recorder(&counts, || {
+            let list = SyncSkipList::new();
+
+            for i in 0..10 {
+                list.insert(i, ());
+            }
+
+            for i in 0..4 {
+                list.remove(&i);
+            }
+
+            list.remove(&42);
+        });
+
+        assert_eq!(counts.get("skippy.inserts"), 10);
+        assert_eq!(counts.get("skippy.removes"), 4);
+        assert_eq!(counts.get("skippy.reclaim_runs"), 4);
+        assert!(counts.get("skippy.nodes_retired") <= 4);
+    }
+}
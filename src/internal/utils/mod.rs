@@ -1,21 +1,87 @@
 use haphazard::{Domain, Global, HazardPointer, HazardPointerArray};
 
-use core::{
-    ops::{Deref, DerefMut},
-    sync::atomic::AtomicUsize,
-};
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::Ordering;
 
+mod allocations;
+pub(crate) mod allocator;
+mod arena;
+pub(crate) mod atomic;
+mod builder;
+mod callbacks;
+mod contention;
+mod dump;
+pub(crate) mod hooks;
+pub(crate) mod merge;
+pub(crate) mod metrics;
 mod node;
+#[cfg(feature = "occupancy")]
+mod occupancy;
 mod padded;
+mod stats;
+pub(crate) mod tuning;
+mod validate;
+#[cfg(feature = "async")]
+mod wakers;
 
-pub(crate) use node::{Head, Levels, Node};
+#[cfg(feature = "stats")]
+pub use allocations::AllocStats;
+#[cfg(feature = "stats")]
+pub(crate) use allocations::{protected_count, reclaimed_count, retired_count};
+pub(crate) use allocations::{freed_at_once, reclaimed, Allocations, Hazard};
+#[cfg(test)]
+pub(crate) use allocations::freed_at_once_count;
+pub use allocator::NodeAllocator;
+pub(crate) use arena::{Arena, ArenaHeader};
+use atomic::{AtomicBool, AtomicUsize};
+pub use builder::{Buildable, Builder};
+pub(crate) use callbacks::Callbacks;
+pub(crate) use contention::Contention;
+#[cfg(feature = "stats")]
+pub use contention::ContentionStats;
+pub(crate) use dump::{dump_levels, Dot};
+pub(crate) use node::{Head, Node};
+#[cfg(feature = "occupancy")]
+pub(crate) use occupancy::Occupancy;
+#[cfg(feature = "occupancy")]
+pub use occupancy::{OccupancySample, OCCUPANCY_SAMPLES, SAMPLE_INTERVAL};
 pub(crate) use padded::Padded;
+pub use stats::ListStats;
+pub(crate) use tuning::Tuning;
+pub use validate::InvariantError;
+pub(crate) use validate::Validator;
+#[cfg(feature = "async")]
+pub(crate) use wakers::Wakers;
 
 pub(crate) const HEIGHT_BITS: usize = 5;
 
+/// The highest any tower can grow, and so the cap [Builder::max_height] may
+/// lower but never raise. Nodes keep their height in the low `HEIGHT_BITS + 1` bits of the word
+/// they share with their reference count and flags, and every search keeps the nodes it dropped
+/// down from in arrays of this many levels on the stack, whatever the cap of the list. At the
+/// default probability of `0.5`, 32 levels keep searches logarithmic up to about 2^32 entries.
 pub(crate) const HEIGHT: usize = 1 << HEIGHT_BITS;
 pub(crate) const HEIGHT_MASK: usize = (1 << (HEIGHT_BITS + 1)) - 1;
 
+/// The minimum number of nodes each thread is handed when dropping a list in parallel.
+pub(crate) const PAR_DROP_CHUNK: usize = 1 << 14;
+
+/// The number of towers a level has to link within a range before `estimate_range_count` scales
+/// their count up instead of dropping down to count more of them.
+pub(crate) const ESTIMATE_SAMPLE: usize = 64;
+
+/// The number of towers a level has to link for `approx_quantile` to start its descent there. The
+/// few towers on the levels above split the keys too unevenly to go by.
+pub(crate) const QUANTILE_SAMPLE: usize = 64;
+
+/// The number of entries a `Debug` implementation prints before eliding the rest with `..`.
+pub(crate) const DEBUG_ENTRIES: usize = 32;
+
+/// Lets us hand a batch of [Node](Node)s over to the thread that drops them.
+pub(crate) struct SendPtr<T>(pub(crate) *mut T);
+
+unsafe impl<T> Send for SendPtr<T> {}
+
 /// The garbage collection of the list
 /// Utilizes Hazard Pointers under the hood to prevent use-after-frees and
 /// the ABA problem.
@@ -59,45 +125,220 @@ pub(crate) trait GeneratesHeight {
     fn gen_height(&self) -> usize;
 }
 
+/// The chance to promote a tower by another level, out of `1 << 32`, that lists start out with.
+pub(crate) const DEFAULT_PROMOTION: usize = 1 << 31;
+
+/// Advances the xorshift `seed` and draws the height of a new tower from it, promoting the tower
+/// by another level with a chance of `promotion` out of `1 << 32`, up to `cap` levels. The seed
+/// must not be 0, which the generator never leaves.
+pub(crate) fn draw_height(seed: &mut usize, promotion: usize, cap: usize) -> usize {
+    let next = |mut seed: usize| {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        seed
+    };
+
+    *seed = next(*seed);
+
+    if promotion.is_power_of_two() && promotion <= DEFAULT_PROMOTION {
+        // Every trailing zero is a coin flip that came up heads, so for a chance of 1 in 2^k,
+        // every k of them promote the tower once.
+        let k = 32 - promotion.trailing_zeros() as usize;
+
+        return core::cmp::min(cap, seed.trailing_zeros() as usize / k + 1);
+    }
+
+    let mut height = 1;
+
+    while height < cap && (*seed & u32::MAX as usize) < promotion {
+        *seed = next(*seed);
+        height += 1;
+    }
+
+    height
+}
+
 pub(crate) struct ListState {
     pub(crate) len: AtomicUsize,
+    /// The greatest length the list reached so far.
+    pub(crate) peak_len: AtomicUsize,
     pub(crate) max_height: AtomicUsize,
+    /// The highest the list lets a tower grow, at most [HEIGHT](HEIGHT).
+    pub(crate) height_cap: usize,
     pub(crate) seed: AtomicUsize,
+    /// The chance to promote a tower by another level, out of `1 << 32`.
+    pub(crate) promotion: AtomicUsize,
+    /// How many unlinks pass between two attempts at reclaiming retired nodes.
+    pub(crate) reclaim_interval: AtomicUsize,
+    pub(crate) unlinks: AtomicUsize,
+    /// Counts the inserts and removals that changed the list, see `version`.
+    pub(crate) version: AtomicUsize,
+    /// The threads registered with the list, see `register_thread`.
+    pub(crate) registered: AtomicUsize,
+    /// Set once the concurrent list swapped a value into a node, which lives on the heap. From
+    /// then on, dropping the list has to drop every node on its own.
+    pub(crate) boxed: AtomicBool,
+    pub(crate) contention: Contention,
+    pub(crate) allocations: Allocations,
+    pub(crate) tuning: Tuning,
+    #[cfg(feature = "occupancy")]
+    pub(crate) occupancy: Occupancy,
 }
 
 impl ListState {
     pub(crate) fn new() -> Self {
         ListState {
             len: AtomicUsize::new(0),
+            peak_len: AtomicUsize::new(0),
             max_height: AtomicUsize::new(1),
+            height_cap: HEIGHT,
+            #[cfg(not(any(loom, shuttle)))]
             seed: AtomicUsize::new(rand::random()),
+            // Loom and shuttle replay executions, so each of them has to build the same towers.
+            #[cfg(any(loom, shuttle))]
+            seed: AtomicUsize::new(0x2545_f491),
+            promotion: AtomicUsize::new(DEFAULT_PROMOTION),
+            reclaim_interval: AtomicUsize::new(1),
+            unlinks: AtomicUsize::new(0),
+            version: AtomicUsize::new(0),
+            registered: AtomicUsize::new(0),
+            boxed: AtomicBool::new(false),
+            contention: Contention::new(),
+            allocations: Allocations::new(),
+            tuning: Tuning::new(),
+            #[cfg(feature = "occupancy")]
+            occupancy: Occupancy::new(),
         }
     }
+
+    /// Counts a node of `height` joining the list.
+    pub(crate) fn grown(&self, _height: usize) {
+        let len = self.len.fetch_add(1, Ordering::AcqRel) + 1;
+        self.peak_len.fetch_max(len, Ordering::Relaxed);
+
+        #[cfg(feature = "occupancy")]
+        self.occupancy.linked(_height);
+    }
+
+    /// Counts a node of `height` leaving the list.
+    pub(crate) fn shrunk(&self, _height: usize) {
+        self.len.fetch_sub(1, Ordering::AcqRel);
+
+        #[cfg(feature = "occupancy")]
+        self.occupancy.unlinked(_height);
+    }
+
+    /// Bumps the version, once an insert or removal took effect. Every
+    /// [SAMPLE_INTERVAL](SAMPLE_INTERVAL) bumps, the occupancy of the list is sampled.
+    pub(crate) fn modified(&self) {
+        let _version = self.version.fetch_add(1, Ordering::Release) + 1;
+
+        #[cfg(feature = "occupancy")]
+        if _version.is_multiple_of(SAMPLE_INTERVAL) {
+            self.occupancy
+                .sample(_version, self.len.load(Ordering::Relaxed));
+        }
+    }
+}
+
+/// How the nodes of a list store its values, see [skiplist_basics](skiplist_basics).
+pub(crate) trait Stored<V> {
+    /// The bytes an arena sets aside for every value, on top of the node holding it.
+    const ARENA_BYTES: usize = 0;
+
+    /// Makes what a node stores of `val`, placing anything it keeps outside of the node in
+    /// `arena`, if there is one and it has room.
+    fn store(val: V, arena: Option<&std::sync::Arc<Arena>>) -> Self;
+}
+
+impl<V> Stored<V> for V {
+    fn store(val: V, _arena: Option<&std::sync::Arc<Arena>>) -> Self {
+        val
+    }
 }
 
 /// This macro allows us to define a basic `SkipList`. We only implement the methods that should be
 /// the same for all variations (non-sync, sync, ...) and let the user implement all the other
-/// methods themselves.
+/// methods themselves. The nodes store values as `$val`, which is [Stored](Stored) from a `V`, so a
+/// list may keep its values in a different shape than it hands them out. The list's `insert` is
+/// available under the `$bounds` on `K` and `V`, and so are its `iter` and `iter_level`. The methods
+/// walking the towers do so through a `NodeRef` the list defines next to it, which protects the
+/// nodes it leads to, if need be.
 macro_rules! skiplist_basics {
     ($my_list: ident) => {
+        skiplist_basics!($my_list, V, K: Ord);
+    };
+    ($my_list: ident, $val: ty, $($bounds: tt)+) => {
         pub struct $my_list<'domain, K, V> {
-            pub(crate) head: core::ptr::NonNull<crate::internal::utils::Head<K, V>>,
+            pub(crate) head: core::ptr::NonNull<crate::internal::utils::Head<K, $val>>,
             pub(crate) state: crate::internal::utils::Padded<crate::internal::utils::ListState>,
             #[allow(dead_code)]
             pub(crate) garbage: crate::internal::utils::Can<'domain>,
+            pub(crate) arena: Option<std::sync::Arc<crate::internal::utils::Arena>>,
+            pub(crate) allocator: Option<std::sync::Arc<dyn crate::internal::utils::NodeAllocator>>,
+            pub(crate) callbacks: crate::internal::utils::Callbacks<K, V>,
+            #[cfg(feature = "async")]
+            #[allow(dead_code)]
+            pub(crate) wakers: crate::internal::utils::Wakers,
         }
 
         impl<'domain, K, V> $my_list<'domain, K, V> {
             pub fn new() -> Self {
                 $my_list {
-                    head: crate::internal::utils::Head::new(),
+                    head: crate::internal::utils::Head::new(crate::internal::utils::HEIGHT),
                     state: crate::internal::utils::Padded::new(
                         crate::internal::utils::ListState::new(),
                     ),
                     garbage: crate::internal::utils::Can::new(),
+                    arena: None,
+                    allocator: None,
+                    callbacks: crate::internal::utils::Callbacks::default(),
+                    #[cfg(feature = "async")]
+                    wakers: crate::internal::utils::Wakers::new(),
                 }
             }
 
+            /// Creates an empty list with storage for roughly `capacity` nodes reserved up front.
+            ///
+            /// Nodes are carved out of that storage until it runs out, after which the list falls
+            /// back to the global allocator. This avoids allocator pressure during an initial
+            /// bulk-load. The memory of arena nodes is only released once the list is dropped and
+            /// every node it retired has been reclaimed.
+            pub fn with_capacity(capacity: usize) -> Self {
+                let mut list = Self::new();
+
+                list.arena = crate::internal::utils::Arena::with_bytes(
+                    capacity.saturating_mul(
+                        crate::internal::utils::Node::<K, $val>::expected_size()
+                            + <$val as crate::internal::utils::Stored<V>>::ARENA_BYTES,
+                    ),
+                    core::mem::align_of::<crate::internal::utils::Node<K, $val>>(),
+                )
+                .map(std::sync::Arc::new);
+
+                list
+            }
+
+            /// Creates an empty list drawing the heights of its towers from `seed` rather than a
+            /// random seed, so a list fed the same inserts from a single thread ends up with the
+            /// same towers every time. Tests and fuzzers can reproduce the exact layout of a list
+            /// this way. Concurrent inserts draw in whichever order they get to it.
+            pub fn with_seed(seed: usize) -> Self {
+                let list = Self::new();
+
+                // The generator never leaves a seed of 0.
+                list.state.seed.store(seed.max(1), Ordering::Relaxed);
+
+                list
+            }
+
+            /// Returns a [Builder](crate::internal::utils::Builder) to configure the list with
+            /// before building it.
+            pub fn builder() -> crate::internal::utils::Builder<Self> {
+                crate::internal::utils::Builder::new()
+            }
+
             pub fn len(&self) -> usize {
                 self.state.len.load(Ordering::Relaxed)
             }
@@ -106,19 +347,122 @@ macro_rules! skiplist_basics {
                 self.state.len.load(Ordering::Relaxed) < 1
             }
 
+            /// The greatest length the list reached so far.
+            pub fn high_water_mark(&self) -> usize {
+                self.state.peak_len.load(Ordering::Relaxed)
+            }
+
+            /// The current length of the list along with the number of towers reaching each
+            /// level.
+            #[cfg(feature = "occupancy")]
+            pub fn occupancy(&self) -> crate::internal::utils::OccupancySample {
+                self.state.occupancy.now(
+                    self.state.version.load(Ordering::Acquire),
+                    self.state.len.load(Ordering::Relaxed),
+                )
+            }
+
+            /// The last [OCCUPANCY_SAMPLES](crate::internal::utils::OCCUPANCY_SAMPLES) samples of
+            /// [occupancy](Self::occupancy), oldest first. The list takes one every
+            /// [SAMPLE_INTERVAL](crate::internal::utils::SAMPLE_INTERVAL) inserts and removals.
+            #[cfg(feature = "occupancy")]
+            pub fn occupancy_history(&self) -> Vec<crate::internal::utils::OccupancySample> {
+                self.state.occupancy.history()
+            }
+
+            /// A counter bumped by every insert and removal that changed the list, replacing a
+            /// value included. Comparing it with an earlier reading tells whether anything changed
+            /// since, without looking at the entries.
+            pub fn version(&self) -> usize {
+                self.state.version.load(Ordering::Acquire)
+            }
+
+            /// Reports the nodes the list allocated, and whether it freed or retired them since.
+            #[cfg(feature = "stats")]
+            pub fn alloc_stats(&self) -> crate::internal::utils::AllocStats {
+                self.state.allocations.snapshot()
+            }
+
+            /// The highest any tower of the list can grow.
+            pub fn max_height(&self) -> usize {
+                self.state.height_cap
+            }
+
+            /// The number of levels the towers of the list reach so far.
+            pub fn current_levels_in_use(&self) -> usize {
+                self.state.max_height.load(Ordering::Relaxed)
+            }
+
+            /// The chance of a new tower to reach one more level, given it reached the one below.
+            pub fn promotion_probability(&self) -> f64 {
+                self.state.promotion.load(Ordering::Relaxed) as f64 / (1u64 << 32) as f64
+            }
+
+            /// Sets the chance of a new tower to reach one more level, which is `0.5` by default.
+            /// Lower chances make for shorter towers, which take less memory but longer searches.
+            /// Chances of one in a power of two, such as `0.25`, take the fewest random bits to
+            /// draw a height with. Only towers built from now on are affected.
+            ///
+            /// # Panics
+            ///
+            /// If `p` does not lie within `0.0..1.0`.
+            pub fn set_promotion_probability(&self, p: f64) {
+                assert!((0.0..1.0).contains(&p), "p must lie within 0.0..1.0");
+
+                self.state
+                    .promotion
+                    .store((p * (1u64 << 32) as f64) as usize, Ordering::Relaxed);
+            }
+
+            /// Lets the list pick its promotion probability by itself, which pays off for lists
+            /// whose size changes by orders of magnitude over their lifetime. While on, the list
+            /// keeps count of the steps its searches take, and every so often sets the
+            /// probability that suits its size and height cap best. Should searches take far
+            /// longer than that size and probability lead to expect, the towers drifted out of
+            /// shape, see [needs_optimize](Self::needs_optimize).
+            pub fn set_auto_tuning(&self, enabled: bool) {
+                self.state.tuning.set_enabled(enabled);
+            }
+
+            pub fn auto_tuning(&self) -> bool {
+                self.state.tuning.enabled()
+            }
+
+            /// Whether auto tuning found the searches to take far longer than expected last time
+            /// it checked, in which case rebuilding the towers shortens them again. The single
+            /// threaded list does so in place with `optimize`, the concurrent one is rebuilt with
+            /// `compact`.
+            pub fn needs_optimize(&self) -> bool {
+                self.state.tuning.drifted()
+            }
+
+            /// Counts a search that took `steps`, tuning the promotion probability every so
+            /// often while auto tuning is on.
+            #[inline]
+            fn searched(&self, steps: usize) {
+                let Some(depth) = self.state.tuning.searched(steps) else {
+                    return;
+                };
+
+                let len = self.state.len.load(Ordering::Relaxed);
+                let p = self.promotion_probability();
+
+                if let Some(p) = self.state.tuning.tune(depth, len, p, self.state.height_cap) {
+                    self.set_promotion_probability(p);
+                }
+            }
+
             fn gen_height(&self) -> usize {
                 let mut seed = self.state.seed.load(Ordering::Relaxed);
-                seed ^= seed << 13;
-                seed ^= seed >> 17;
-                seed ^= seed << 5;
-
-                self.state.seed.store(seed, Ordering::Relaxed);
 
-                let mut height = std::cmp::min(
-                    crate::internal::utils::HEIGHT,
-                    seed.trailing_zeros() as usize + 1,
+                let mut height = crate::internal::utils::draw_height(
+                    &mut seed,
+                    self.state.promotion.load(Ordering::Relaxed),
+                    self.state.height_cap,
                 );
 
+                self.state.seed.store(seed, Ordering::Relaxed);
+
                 let head = unsafe { &(*self.head.as_ptr()) };
 
                 while height >= 4 && head.levels[height - 2].load_ptr().is_null() {
@@ -131,6 +475,459 @@ macro_rules! skiplist_basics {
 
                 height
             }
+
+            /// Allocates a new [Node](crate::internal::utils::Node) of random height, preferring
+            /// the list's allocator, then its arena, if it has one.
+            fn new_node(&self, key: K, val: V) -> *mut crate::internal::utils::Node<K, $val> {
+                self.state.allocations.allocated();
+
+                let val =
+                    <$val as crate::internal::utils::Stored<V>>::store(val, self.arena.as_ref());
+
+                if let Some(allocator) = self.allocator.as_ref() {
+                    return crate::internal::utils::Node::new_with(
+                        key,
+                        val,
+                        self.gen_height(),
+                        allocator,
+                    );
+                }
+
+                match self.arena.as_ref() {
+                    Some(arena) => {
+                        crate::internal::utils::Node::new_in(key, val, self.gen_height(), arena)
+                    }
+                    None => crate::internal::utils::Node::new_rand_height(key, val, self),
+                }
+            }
+
+            /// Allocates a new [Node](crate::internal::utils::Node) of the given height, or of
+            /// random height if there is none.
+            fn new_node_of_height(
+                &self,
+                key: K,
+                val: V,
+                height: Option<usize>,
+            ) -> *mut crate::internal::utils::Node<K, $val> {
+                let Some(height) = height else {
+                    return self.new_node(key, val);
+                };
+
+                assert!(
+                    (1..=self.state.height_cap).contains(&height),
+                    "height must be within 1..={}",
+                    self.state.height_cap
+                );
+
+                self.state.allocations.allocated();
+                self.state.max_height.fetch_max(height, Ordering::Relaxed);
+
+                let val =
+                    <$val as crate::internal::utils::Stored<V>>::store(val, self.arena.as_ref());
+
+                if let Some(allocator) = self.allocator.as_ref() {
+                    return crate::internal::utils::Node::new_with(key, val, height, allocator);
+                }
+
+                match self.arena.as_ref() {
+                    Some(arena) => crate::internal::utils::Node::new_in(key, val, height, arena),
+                    None => crate::internal::utils::Node::new(key, val, height),
+                }
+            }
+
+            /// Collects the last node of every level, descending from the top. Levels that are
+            /// still empty end at the head.
+            ///
+            /// # Safety
+            ///
+            /// No other thread may be modifying the list.
+            unsafe fn tail(
+                &self,
+            ) -> [*mut crate::internal::utils::Node<K, $val>; crate::internal::utils::HEIGHT] {
+                let mut curr = self
+                    .head
+                    .as_ptr()
+                    .cast::<crate::internal::utils::Node<K, $val>>();
+                let mut tail = [curr; crate::internal::utils::HEIGHT];
+
+                // Levels above the cap are missing from the head, and no tower reaches them.
+                for level in (0..self.state.height_cap).rev() {
+                    let mut next = (*curr).levels[level].load_ptr();
+
+                    while !next.is_null() {
+                        curr = next;
+                        next = (*curr).levels[level].load_ptr();
+                    }
+
+                    tail[level] = curr;
+                }
+
+                tail
+            }
+
+            /// Links a new node behind the last node of every level it reaches, without
+            /// searching for its position.
+            ///
+            /// # Safety
+            ///
+            /// 1. `tail` holds the last node of every level, as returned by [tail](Self::tail).
+            /// 2. `key` is greater than any key in the list.
+            /// 3. No other thread may be accessing the list.
+            unsafe fn append(
+                &self,
+                tail: &mut [*mut crate::internal::utils::Node<K, $val>;
+                         crate::internal::utils::HEIGHT],
+                key: K,
+                val: V,
+            ) {
+                let node = self.new_node(key, val);
+                let height = (*node).height();
+
+                for (level, last) in tail.iter_mut().enumerate().take(height) {
+                    (**last).levels[level].store_ptr(node);
+                    (*node).add_ref();
+                    *last = node;
+                }
+
+                // The links to the end that now lead to the new node already skipped as far,
+                // and those of the new node skip one, as for every new node. Only the links to
+                // the end above it skip one more.
+                #[cfg(feature = "rank")]
+                for (level, last) in tail
+                    .iter()
+                    .enumerate()
+                    .take(self.state.height_cap)
+                    .skip(height)
+                {
+                    (**last).set_span(level, (**last).span(level) + 1);
+                }
+
+                self.state.grown((*node).height());
+                self.state.modified();
+            }
+
+            /// Whether dropping the list frees all of its nodes at once, along with its arena. This
+            /// takes every node to live in the arena and to hold nothing that needs dropping. The
+            /// concurrent list keeps the value a node is linked with in the arena as well, yet
+            /// those it swaps in later on the heap.
+            pub(crate) fn frees_at_once(&self) -> bool {
+                self.arena.as_ref().is_some_and(|arena| !arena.overflowed())
+                    && !core::mem::needs_drop::<K>()
+                    && !core::mem::needs_drop::<V>()
+                    && !self.state.boxed.load(Ordering::Relaxed)
+            }
+
+            /// Drops the list, spreading the work of dropping its entries over all available
+            /// cores. The nodes are still collected with a single walk over the lowest level, but
+            /// dropping the keys and values and freeing the nodes happens in parallel chunks.
+            /// This pays off for lists with millions of entries or expensive to drop entries.
+            pub fn drop_parallel(self)
+            where
+                K: Send,
+                V: Send,
+            {
+                use crate::internal::utils::{Node, SendPtr, PAR_DROP_CHUNK};
+
+                self.garbage.domain.eager_reclaim();
+
+                let mut nodes = Vec::with_capacity(self.len());
+
+                // # Safety
+                //
+                // We own the list, so no one else can be accessing its nodes.
+                unsafe {
+                    let head = &(*self.head.as_ptr());
+                    let mut node = head.levels[0].load_ptr();
+
+                    while !node.is_null() {
+                        nodes.push(SendPtr(node));
+                        node = (*node).levels[0].load_ptr();
+                    }
+
+                    // The nodes are now ours to drop, the list must not visit them again.
+                    head.levels[0].store_ptr(core::ptr::null_mut());
+                }
+
+                // Dropping the list afterwards must not count the nodes as freed once more.
+                self.state.allocations.freed(nodes.len());
+                self.state.len.store(0, Ordering::Relaxed);
+
+                let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+                let chunk = core::cmp::max(PAR_DROP_CHUNK, nodes.len() / threads + 1);
+
+                std::thread::scope(|scope| {
+                    while !nodes.is_empty() {
+                        let batch = nodes.split_off(nodes.len().saturating_sub(chunk));
+
+                        scope.spawn(move || {
+                            for node in batch {
+                                // # Safety
+                                //
+                                // Every node is part of exactly one chunk.
+                                unsafe { Node::<K, $val>::drop(node.0) }
+                            }
+                        });
+                    }
+                });
+            }
+        }
+
+        impl<'domain, K, V> $my_list<'domain, K, V>
+        where
+            $($bounds)+
+        {
+            /// Builds a list out of `(key, value)` pairs sorted by key. Every pair that is greater
+            /// than the previous one is linked at the end of the list directly, without searching
+            /// for its position. Pairs that are out of order are still inserted, just at the
+            /// regular cost. As with [insert](Self::insert), the last value of a duplicate key
+            /// wins.
+            pub fn from_sorted_iter<I>(iter: I) -> Self
+            where
+                I: IntoIterator<Item = (K, V)>,
+            {
+                // Only the single threaded list needs it to be mutable to insert.
+                #[allow(unused_mut)]
+                let mut list = Self::new();
+
+                // # Safety
+                //
+                // We own the list, and only append keys greater than the current last one.
+                unsafe {
+                    let mut tail = list.tail();
+
+                    for (key, val) in iter {
+                        if list.is_head(tail[0]) || (*tail[0]).key < key {
+                            list.append(&mut tail, key, val);
+                        } else {
+                            list.insert(key, val);
+                            tail = list.tail();
+                        }
+                    }
+                }
+
+                list
+            }
+
+            /// Feeds every key and value to `hasher` in ascending order, followed by the number
+            /// of entries as a `u64`, so 32 and 64 bit targets agree. Lists holding the same
+            /// entries feed the same, whatever their heights, history or kind, so comparing
+            /// digests verifies a replica without sending it the entries. Should the
+            /// [version](Self::version) change during the walk, `hasher` is reset and the list
+            /// walked again, so the digest always covers the list as of a single version.
+            /// Removing through an `Entry` of the concurrent list does not bump the version, so
+            /// the walk cannot see these.
+            pub fn content_hash<H>(&self, hasher: &mut H)
+            where
+                K: core::hash::Hash,
+                V: core::hash::Hash,
+                H: core::hash::Hasher + Clone,
+            {
+                let start = hasher.clone();
+
+                loop {
+                    let version = self.version();
+                    let mut len = 0;
+
+                    for entry in self.iter() {
+                        entry.key().hash(hasher);
+                        entry.val().hash(hasher);
+                        len += 1;
+                    }
+
+                    hasher.write_u64(len as u64);
+
+                    if self.version() == version {
+                        return;
+                    }
+
+                    *hasher = start.clone();
+                }
+            }
+
+            /// Estimates the number of keys within `range` in logarithmic time. Going down from
+            /// the top, this counts the towers within the range on each level, until one level
+            /// links enough of them. A tower reaches each level with the
+            /// [promotion probability](Self::promotion_probability) `p`, so that count divided by
+            /// `p` to the power of the level approximates the total. Small ranges end up counted
+            /// exactly on the lowest level. Removed nodes of the concurrent list count until they
+            /// are unlinked.
+            pub fn estimate_range_count<R>(&self, range: R) -> usize
+            where
+                R: core::ops::RangeBounds<K>,
+            {
+                use core::ops::Bound;
+
+                let before = |key: &K| match range.start_bound() {
+                    Bound::Included(start) => key < start,
+                    Bound::Excluded(start) => key <= start,
+                    Bound::Unbounded => false,
+                };
+
+                // The links of a node removed while we pass it may lead to nodes that are gone
+                // already, so we start over should we run into one.
+                'estimate: loop {
+                    let mut prev = NodeRef::from_raw(
+                        self.head
+                            .as_ptr()
+                            .cast::<crate::internal::utils::Node<K, $val>>(),
+                    );
+
+                    for level in (0..self.state.max_height.load(Ordering::Acquire)).rev() {
+                        loop {
+                            match NodeRef::from_live(&prev.levels[level]) {
+                                Ok(Some(next)) if before(&next.key) => prev = next,
+                                Ok(_) => break,
+                                Err(()) => continue 'estimate,
+                            }
+                        }
+
+                        let mut count = 0;
+                        let Ok(mut next) = NodeRef::from_live(&prev.levels[level]) else {
+                            continue 'estimate;
+                        };
+
+                        while let Some(n) = next.filter(|n| range.contains(&n.key)) {
+                            count += 1;
+                            let Ok(after) = NodeRef::from_live(&n.levels[level]) else {
+                                continue 'estimate;
+                            };
+                            next = after;
+                        }
+
+                        if level == 0 {
+                            return count;
+                        }
+
+                        if count >= crate::internal::utils::ESTIMATE_SAMPLE {
+                            let p = self.promotion_probability().powi(level as i32);
+
+                            // The towers may have been built with a greater probability than the
+                            // current one, and a probability of 0 would scale them to no end.
+                            return ((count as f64 / p).round() as usize).min(self.len());
+                        }
+                    }
+
+                    return 0;
+                }
+            }
+
+            /// Returns a key at about the `q`-th quantile of the keys, which makes for a split
+            /// point that divides the list at that ratio. Rather than scanning the list, this
+            /// descends the towers: On each level it counts the towers between the two it dropped
+            /// down between, assumes they split the keys evenly and picks the one covering the
+            /// share of keys still to pass.
+            ///
+            /// # Panics
+            ///
+            /// If `q` does not lie within `0.0..=1.0`.
+            pub fn approx_quantile(&self, q: f64) -> Option<K>
+            where
+                K: Clone,
+            {
+                use crate::internal::utils::QUANTILE_SAMPLE;
+
+                assert!((0.0..=1.0).contains(&q), "q must lie within 0.0..=1.0");
+
+                let head = NodeRef::from_raw(
+                    self.head
+                        .as_ptr()
+                        .cast::<crate::internal::utils::Node<K, $val>>(),
+                );
+                let mut top = self.state.max_height.load(Ordering::Acquire);
+
+                // The few towers on the highest levels split the keys too unevenly to go by.
+                while top > 1
+                    && self.iter_level(top - 1).take(QUANTILE_SAMPLE).count() < QUANTILE_SAMPLE
+                {
+                    top -= 1;
+                }
+
+                // The links of a node removed while we pass it may lead to nodes that are gone
+                // already, so we start over should we run into one.
+                'descend: loop {
+                    // The keys from `start` up to `end` hold the quantile, `frac` tells how far in.
+                    let (mut start, mut end, mut frac) = (head.clone(), core::ptr::null_mut(), q);
+
+                    for level in (0..top).rev() {
+                        // On the lowest level the head has no key of its own to pick.
+                        let skip_head = level == 0 && start == head;
+
+                        let mut towers = usize::from(!skip_head);
+                        let Ok(mut next) = NodeRef::from_live(&start.levels[level]) else {
+                            continue 'descend;
+                        };
+
+                        while let Some(n) = next.as_ref().filter(|n| n.as_ptr() != end) {
+                            let Ok(after) = NodeRef::from_live(&n.levels[level]) else {
+                                continue 'descend;
+                            };
+
+                            towers += 1;
+                            next = after;
+                        }
+
+                        // The towers above are not spread as evenly as we assumed, none made it
+                        // in here.
+                        if towers == 0 {
+                            return next.map(|n| n.key.clone());
+                        }
+
+                        let pos = frac * towers as f64;
+                        let picked = (pos as usize).min(towers - 1);
+                        frac = pos - picked as f64;
+
+                        for _ in 0..picked + usize::from(skip_head) {
+                            let Ok(next) = NodeRef::from_live(&start.levels[level]) else {
+                                continue 'descend;
+                            };
+
+                            start = next?;
+                        }
+
+                        end = start.levels[level].load_ptr();
+                    }
+
+                    return (start != head).then(|| start.key.clone());
+                }
+            }
+        }
+
+        impl<'domain, K, V> crate::internal::utils::Builder<$my_list<'domain, K, V>> {
+            pub fn build(self) -> $my_list<'domain, K, V> {
+                // Nodes placed by an allocator have no use for an arena.
+                let mut list = match (self.capacity, self.allocator.is_some()) {
+                    (Some(capacity), false) => $my_list::with_capacity(capacity),
+                    _ => $my_list::new(),
+                };
+
+                list.allocator = self.allocator;
+
+                // The head needs no more levels than any tower may reach.
+                if self.max_height < crate::internal::utils::HEIGHT {
+                    unsafe { crate::internal::utils::Head::<K, $val>::drop(list.head) };
+                    list.head = crate::internal::utils::Head::new(self.max_height);
+                }
+
+                list.state.height_cap = self.max_height;
+
+                if let Some(p) = self.probability {
+                    list.set_promotion_probability(p);
+                }
+
+                if let Some(seed) = self.seed {
+                    list.state.seed.store(seed, Ordering::Relaxed);
+                }
+
+                list.callbacks = self.callbacks;
+                list.set_auto_tuning(self.auto_tuning);
+
+                list
+            }
+        }
+
+        impl<'domain, K, V> crate::internal::utils::Buildable for $my_list<'domain, K, V> {
+            type Key = K;
+            type Val = V;
         }
 
         /// Need this trait for our [Node](Node)s to be generated with random heights.
@@ -147,6 +944,18 @@ macro_rules! skiplist_basics {
                 // To ensure this is safe, clear all `HazardPointer`s in the domain.
                 // We do not want to drop a node twice!
                 self.garbage.domain.eager_reclaim();
+
+                // If every node lives in the arena and there is nothing to drop in them, the arena
+                // can free them all at once without us walking the list.
+                if self.frees_at_once() {
+                    self.state.allocations.freed(self.len());
+                    self.state.allocations.check_leaks();
+                    crate::internal::utils::freed_at_once();
+
+                    unsafe { crate::internal::utils::Head::<K, $val>::drop(self.head) };
+                    return;
+                }
+
                 let mut node = unsafe { (*self.head.as_ptr()).levels[0].load_ptr() };
 
                 // # Safety
@@ -156,11 +965,14 @@ macro_rules! skiplist_basics {
                     while !node.is_null() {
                         let temp = node;
                         node = (*temp).levels[0].load_ptr();
-                        crate::internal::utils::Node::<K, V>::drop(temp);
+                        crate::internal::utils::Node::<K, $val>::drop(temp);
+                        self.state.allocations.freed(1);
                     }
 
-                    crate::internal::utils::Head::<K, V>::drop(self.head);
+                    crate::internal::utils::Head::<K, $val>::drop(self.head);
                 }
+
+                self.state.allocations.check_leaks();
             }
         }
     };
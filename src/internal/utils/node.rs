@@ -1,12 +1,36 @@
 extern crate alloc;
 
 use crate::internal::sync::tagged::MaybeTagged;
+use crate::internal::utils::allocator::{self, Header, NodeAllocator};
+#[cfg(feature = "metadata")]
+use crate::internal::utils::atomic::AtomicU64;
+use crate::internal::utils::atomic::AtomicUsize;
+#[cfg(feature = "rank")]
+use crate::internal::utils::atomic::Span;
+use crate::internal::utils::{Arena, ArenaHeader};
 use crate::internal::utils::HEIGHT;
 use crate::internal::utils::HEIGHT_BITS;
 use crate::internal::utils::HEIGHT_MASK;
 use alloc::alloc::{alloc, dealloc, handle_alloc_error, Layout};
+use alloc::sync::Arc;
 
 const REMOVED_MASK: usize = !(usize::MAX >> 1);
+/// Set for nodes that were carved out of an [Arena](super::Arena) and must not be handed back to
+/// the global allocator.
+const ARENA_MASK: usize = REMOVED_MASK >> 1;
+/// Set for nodes whose upper levels their insert left unlinked, as long as no one is linking them.
+const INCOMPLETE_MASK: usize = ARENA_MASK >> 1;
+/// Set for nodes a hash index points to. The index holds on to them like a level does, so they
+/// are only retired once the index lets go of them as well.
+const INDEXED_MASK: usize = INCOMPLETE_MASK >> 1;
+/// Set for nodes a rename linked under their new key, until the node of the old key is removed.
+/// Searches for the key wait for the mark to clear, so they never see both keys or neither.
+const PENDING_MASK: usize = INDEXED_MASK >> 1;
+/// Set for nodes placed by a [NodeAllocator](NodeAllocator), which sits in a
+/// [Header](Header) right in front of the node.
+const ALLOCATOR_MASK: usize = PENDING_MASK >> 1;
+const REFS_MASK: usize =
+    !(REMOVED_MASK | ARENA_MASK | INCOMPLETE_MASK | INDEXED_MASK | PENDING_MASK | ALLOCATOR_MASK);
 
 use core::{
     fmt::Debug,
@@ -14,22 +38,24 @@ use core::{
     mem,
     ops::Index,
     ptr::{self, NonNull},
-    sync::atomic::AtomicUsize,
     sync::atomic::Ordering,
 };
 
 /// Head stores the first pointer tower at the beginning of the list. It is always of maximum
-#[repr(C)]
+#[repr(C, align(4))]
 pub(crate) struct Head<K, V> {
     pub(crate) key: K,
     pub(crate) val: V,
     pub(crate) height_and_removed: AtomicUsize,
+    #[cfg(feature = "metadata")]
+    pub(crate) meta: AtomicU64,
     pub(crate) levels: Levels<K, V>,
 }
 
 impl<K, V> Head<K, V> {
-    pub(crate) fn new() -> NonNull<Self> {
-        let head_ptr = unsafe { Node::<K, V>::alloc(super::HEIGHT).cast() };
+    /// Allocates a head with room for towers of up to `height` levels.
+    pub(crate) fn new(height: usize) -> NonNull<Self> {
+        let head_ptr = unsafe { Node::<K, V>::alloc(height).cast() };
 
         if let Some(head) = NonNull::new(head_ptr) {
             head
@@ -60,15 +86,21 @@ impl<K, V> Index<usize> for Levels<K, V> {
     type Output = MaybeTagged<Node<K, V>>;
 
     fn index(&self, index: usize) -> &Self::Output {
-        unsafe { self.pointers.get_unchecked(index) }
+        // The levels run on past the array, into the rest of the node. Indexing the array itself
+        // would tell the optimizer that every index but 0 is out of bounds, and so unreachable.
+        unsafe { &*self.pointers.as_ptr().add(index) }
     }
 }
 
-#[repr(C)]
+/// Nodes are aligned to at least 4 bytes, no matter their key and value, so pointers to them
+/// always have some unused low bits to store tags in.
+#[repr(C, align(4))]
 pub struct Node<K, V> {
     pub key: K,
     pub val: V,
     pub(crate) height_and_removed: AtomicUsize,
+    #[cfg(feature = "metadata")]
+    pub(crate) meta: AtomicU64,
     pub(crate) levels: Levels<K, V>,
 }
 
@@ -82,6 +114,31 @@ impl<K, V> Node<K, V> {
         }
     }
 
+    /// Allocates the node in `arena`, falling back to the global allocator once it is full.
+    pub(crate) fn new_in(key: K, val: V, height: usize, arena: &Arc<Arena>) -> *mut Self {
+        unsafe {
+            let node = Self::alloc_in(height, arena);
+            ptr::write(&mut (*node).key, key);
+            ptr::write(&mut (*node).val, val);
+            node
+        }
+    }
+
+    /// Allocates the node with `allocator`.
+    pub(crate) fn new_with(
+        key: K,
+        val: V,
+        height: usize,
+        allocator: &Arc<dyn NodeAllocator>,
+    ) -> *mut Self {
+        unsafe {
+            let node = Self::alloc_with(height, allocator);
+            ptr::write(&mut (*node).key, key);
+            ptr::write(&mut (*node).val, val);
+            node
+        }
+    }
+
     pub(crate) fn new_rand_height(
         key: K,
         val: V,
@@ -100,29 +157,172 @@ impl<K, V> Node<K, V> {
             handle_alloc_error(layout);
         }
 
-        ptr::write(&mut (*ptr).height_and_removed, AtomicUsize::new(height));
+        Self::init(ptr, height);
+
+        ptr
+    }
+
+    pub(crate) unsafe fn alloc_in(height: usize, arena: &Arc<Arena>) -> *mut Self {
+        let (layout, offset) = allocator::with_header::<ArenaHeader>(Self::get_layout(height));
+
+        match arena.alloc(layout) {
+            Some(base) => {
+                ptr::write(
+                    base.as_ptr().cast::<ArenaHeader>(),
+                    ArenaHeader {
+                        arena: Arc::as_ptr(arena),
+                    },
+                );
+
+                let ptr = base.as_ptr().add(offset).cast::<Self>();
+                Self::init(ptr, height | ARENA_MASK);
+                ptr
+            }
+            None => Self::alloc(height),
+        }
+    }
+
+    /// The arena the node was carved out of, if it was.
+    pub(crate) unsafe fn arena(ptr: *mut Self) -> Option<*const Arena> {
+        if (*ptr).height_and_removed.load(Ordering::Relaxed) & ARENA_MASK == 0 {
+            return None;
+        }
+
+        let (_, offset) = allocator::with_header::<ArenaHeader>(Self::get_layout((*ptr).height()));
+
+        Some((*ptr.cast::<u8>().sub(offset).cast::<ArenaHeader>()).arena)
+    }
+
+    pub(crate) unsafe fn alloc_with(
+        height: usize,
+        allocator: &Arc<dyn NodeAllocator>,
+    ) -> *mut Self {
+        let (layout, offset) = allocator::with_header::<Header>(Self::get_layout(height));
+
+        let Some(base) = allocator.allocate(layout) else {
+            handle_alloc_error(layout);
+        };
+
+        ptr::write(
+            base.as_ptr().cast::<Header>(),
+            Header {
+                allocator: allocator.clone(),
+            },
+        );
 
-        ptr::write_bytes((*ptr).levels.pointers.as_mut_ptr(), 0, height);
+        let ptr = base.as_ptr().add(offset).cast::<Self>();
+        Self::init(ptr, height | ALLOCATOR_MASK);
 
         ptr
     }
 
+    unsafe fn init(ptr: *mut Self, height_and_flags: usize) {
+        ptr::write(
+            &mut (*ptr).height_and_removed,
+            AtomicUsize::new(height_and_flags),
+        );
+
+        #[cfg(feature = "metadata")]
+        ptr::write(&mut (*ptr).meta, AtomicU64::new(Self::now()));
+
+        #[cfg(not(any(loom, shuttle)))]
+        ptr::write_bytes(
+            (*ptr).levels.pointers.as_mut_ptr(),
+            0,
+            height_and_flags & HEIGHT_MASK,
+        );
+
+        // Loom's and shuttle's atomics carry scheduler state, an all zero pattern is not a valid one.
+        #[cfg(any(loom, shuttle))]
+        for level in 0..height_and_flags & HEIGHT_MASK {
+            ptr::write(
+                (*ptr).levels.pointers.as_mut_ptr().add(level),
+                MaybeTagged::new(ptr::null_mut()),
+            );
+        }
+
+        // A link to the end skips one past the last node, which for a node not yet linked, or the
+        // head of an empty list, is the next one.
+        #[cfg(feature = "rank")]
+        for level in 0..height_and_flags & HEIGHT_MASK {
+            ptr::write(Self::spans_ptr(ptr).add(level), Span::new(1));
+        }
+    }
+
     pub(crate) unsafe fn dealloc(ptr: *mut Self) {
+        let flags = (*ptr).height_and_removed.load(Ordering::Relaxed);
+
+        // Arena backed nodes are freed all at once, together with their arena.
+        if flags & ARENA_MASK != 0 {
+            return;
+        }
+
         let height = (*ptr).height();
 
         let layout = Self::get_layout(height);
 
+        if flags & ALLOCATOR_MASK != 0 {
+            let (layout, offset) = allocator::with_header::<Header>(layout);
+            let base = ptr.cast::<u8>().sub(offset);
+
+            // The header holds on to the allocator until it got its memory back.
+            let Header { allocator } = ptr::read(base.cast::<Header>());
+            allocator.deallocate(NonNull::new_unchecked(base), layout);
+
+            return;
+        }
+
         dealloc(ptr.cast(), layout);
     }
 
+    /// The expected footprint of a single arena node, given that the average node is two levels
+    /// high.
+    pub(crate) fn expected_size() -> usize {
+        unsafe { allocator::with_header::<ArenaHeader>(Self::get_layout(2)).0.size() }
+    }
+
     unsafe fn get_layout(height: usize) -> Layout {
         let size_self = mem::size_of::<Self>();
         let align = mem::align_of::<Self>();
         let size_levels = Levels::<K, V>::get_size(height);
 
+        // The spans trail the level pointers.
+        #[cfg(feature = "rank")]
+        let size_levels = size_levels + mem::size_of::<Span>() * height;
+
         Layout::from_size_align_unchecked(size_self + size_levels, align)
     }
 
+    /// The spans of the node, which trail its level pointers.
+    #[cfg(feature = "rank")]
+    unsafe fn spans_ptr(ptr: *mut Self) -> *mut Span {
+        let height = (*ptr).height();
+
+        (*ptr).levels.pointers.as_mut_ptr().add(height).cast()
+    }
+
+    /// The number of nodes the link on `level` skips, counting the one it leads to, or one past
+    /// the last node for a link to the end. Only the single threaded list keeps spans, see
+    /// [SkipList::rank](crate::SkipList::rank).
+    #[cfg(feature = "rank")]
+    pub(crate) fn span(&self, level: usize) -> usize {
+        debug_assert!(level < self.height());
+
+        unsafe {
+            (*Self::spans_ptr(self as *const Self as *mut Self).add(level)).load(Ordering::Relaxed)
+        }
+    }
+
+    #[cfg(feature = "rank")]
+    pub(crate) fn set_span(&self, level: usize, span: usize) {
+        debug_assert!(level < self.height());
+
+        unsafe {
+            (*Self::spans_ptr(self as *const Self as *mut Self).add(level))
+                .store(span, Ordering::Relaxed)
+        }
+    }
+
     pub(crate) unsafe fn drop(ptr: *mut Self) {
         ptr::drop_in_place(&mut (*ptr).key);
         ptr::drop_in_place(&mut (*ptr).val);
@@ -130,12 +330,30 @@ impl<K, V> Node<K, V> {
         Node::dealloc(ptr);
     }
 
+    /// The time of day in nanoseconds since the Unix epoch, which new nodes are stamped with.
+    #[cfg(feature = "metadata")]
+    fn now() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |since| since.as_nanos() as u64)
+    }
+
+    #[cfg(feature = "metadata")]
+    pub(crate) fn meta(&self) -> u64 {
+        self.meta.load(Ordering::Relaxed)
+    }
+
+    #[cfg(feature = "metadata")]
+    pub(crate) fn set_meta(&self, meta: u64) {
+        self.meta.store(meta, Ordering::Relaxed);
+    }
+
     pub(crate) fn height(&self) -> usize {
         (self.height_and_removed.load(Ordering::Relaxed) & HEIGHT_MASK) as usize
     }
 
     pub(crate) fn refs(&self) -> usize {
-        (self.height_and_removed.load(Ordering::SeqCst) & !REMOVED_MASK) >> (HEIGHT_BITS + 1)
+        (self.height_and_removed.load(Ordering::SeqCst) & REFS_MASK) >> (HEIGHT_BITS + 1)
     }
 
     pub(crate) fn add_ref(&self) -> usize {
@@ -149,13 +367,13 @@ impl<K, V> Node<K, V> {
     pub(crate) fn try_add_ref(&self) -> Result<usize, usize> {
         self.height_and_removed
             .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |o| {
-                if (o & !REMOVED_MASK) >> (HEIGHT_BITS + 1) == 0 {
+                if (o & REFS_MASK) >> (HEIGHT_BITS + 1) == 0 {
                     return None;
                 }
 
                 Some(o + (1 << (HEIGHT_BITS + 1)))
             })
-            .map(|now| ((now & !REMOVED_MASK) >> (HEIGHT_BITS + 1)) + 1)
+            .map(|now| ((now & REFS_MASK) >> (HEIGHT_BITS + 1)) + 1)
     }
 
     pub(crate) fn sub_ref(&self) -> usize {
@@ -163,15 +381,43 @@ impl<K, V> Node<K, V> {
             .fetch_sub(1 << (HEIGHT_BITS + 1), Ordering::SeqCst) as usize
     }
 
+    /// Drops a reference, returning how many are left. An index pointing to the node counts as one
+    /// more, see [try_index](Self::try_index).
     pub(crate) fn try_sub_ref(&self) -> Result<usize, usize> {
         self.height_and_removed
             .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |o| {
-                if (o & !REMOVED_MASK) >> (HEIGHT_BITS + 1) == 0 {
+                if (o & REFS_MASK) >> (HEIGHT_BITS + 1) == 0 {
                     panic!("Will underflow")
                 }
                 Some(o - (1 << (HEIGHT_BITS + 1)))
             })
-            .map(|now| ((now & !REMOVED_MASK) >> (HEIGHT_BITS + 1)) - 1)
+            .map(|now| {
+                ((now & REFS_MASK) >> (HEIGHT_BITS + 1)) - 1 + usize::from(now & INDEXED_MASK != 0)
+            })
+    }
+
+    /// Marks the node as indexed, unless it is already or no longer linked on any level. Only a
+    /// node marked this way may be stored in an index.
+    pub(crate) fn try_index(&self) -> bool {
+        self.height_and_removed
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |o| {
+                let linked = (o & REFS_MASK) >> (HEIGHT_BITS + 1) != 0;
+
+                (linked && o & INDEXED_MASK == 0).then_some(o | INDEXED_MASK)
+            })
+            .is_ok()
+    }
+
+    /// Clears the index mark, once the index no longer points to the node. Returns whether the
+    /// node is unlinked as well, in which case the caller has to retire it.
+    pub(crate) fn unindex(&self) -> bool {
+        let old = self
+            .height_and_removed
+            .fetch_and(!INDEXED_MASK, Ordering::SeqCst);
+
+        debug_assert!(old & INDEXED_MASK != 0, "node was not indexed");
+
+        (old & REFS_MASK) >> (HEIGHT_BITS + 1) == 0
     }
 
     pub(crate) fn removed(&self) -> bool {
@@ -181,30 +427,48 @@ impl<K, V> Node<K, V> {
             == 0
     }
 
-    pub(crate) fn set_removed(&self) -> Result<usize, ()> {
-        self.set_har_with(|old| old | REMOVED_MASK)
+    /// Marks the tower as incomplete, so that searches passing the node link its missing levels.
+    pub(crate) fn set_incomplete(&self) {
+        self.height_and_removed
+            .fetch_or(INCOMPLETE_MASK, Ordering::SeqCst);
     }
 
-    fn set_har_with<F>(&self, f: F) -> Result<usize, ()>
-    where
-        F: Fn(usize) -> usize,
-    {
-        let height_and_removed = self.height_and_removed.load(Ordering::SeqCst);
+    pub(crate) fn incomplete(&self) -> bool {
+        self.height_and_removed.load(Ordering::Acquire) & INCOMPLETE_MASK != 0
+    }
 
-        let new_height_and_removed = f(height_and_removed);
+    /// Clears the incomplete mark, returning whether it was set. Only one thread claims the tower
+    /// this way, which then links its missing levels or marks it as incomplete again.
+    pub(crate) fn claim_tower(&self) -> bool {
+        self.height_and_removed
+            .fetch_and(!INCOMPLETE_MASK, Ordering::SeqCst)
+            & INCOMPLETE_MASK
+            != 0
+    }
 
-        if new_height_and_removed == height_and_removed {
-            return Err(());
-        }
+    /// Marks the node as pending, before it is linked by a rename.
+    pub(crate) fn set_pending(&self) {
+        self.height_and_removed
+            .fetch_or(PENDING_MASK, Ordering::SeqCst);
+    }
 
-        // try to exchange
+    pub(crate) fn pending(&self) -> bool {
+        self.height_and_removed.load(Ordering::Acquire) & PENDING_MASK != 0
+    }
+
+    /// Clears the pending mark, once the rename that linked the node is settled.
+    pub(crate) fn clear_pending(&self) {
         self.height_and_removed
-            .compare_exchange(
-                height_and_removed,
-                new_height_and_removed,
-                Ordering::SeqCst,
-                Ordering::SeqCst,
-            )
+            .fetch_and(!PENDING_MASK, Ordering::SeqCst);
+    }
+
+    /// Marks the node as removed. Fails only if it already is, whatever happens to its references
+    /// meanwhile.
+    pub(crate) fn set_removed(&self) -> Result<usize, ()> {
+        self.height_and_removed
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |o| {
+                (o & REMOVED_MASK == 0).then_some(o | REMOVED_MASK)
+            })
             .map_err(|_| ())
     }
 
@@ -226,6 +490,12 @@ impl<K, V> Node<K, V> {
     }
 }
 
+// # Safety
+//
+// The levels only point at other nodes, they do not own them. Moving a node to another thread, as
+// reclaiming it from the hazard pointer domain does, only moves its key and value.
+unsafe impl<K: Send, V: Send> Send for Node<K, V> {}
+
 impl<K, V> PartialEq for Node<K, V>
 where
     K: PartialEq,
@@ -293,4 +563,23 @@ mod test {
             assert_eq!((*node).try_add_ref().unwrap(), 2);
         }
     }
+
+    #[test]
+    fn test_indexed() {
+        unsafe {
+            let node = Node::new(1, (), 3);
+
+            // Only linked nodes can be indexed.
+            assert!(!(*node).try_index());
+
+            (*node).add_ref();
+            assert!((*node).try_index());
+            assert!(!(*node).try_index());
+
+            // The index holds on to the node once it is unlinked.
+            assert_eq!((*node).try_sub_ref().unwrap(), 1);
+            assert_eq!((*node).refs(), 0);
+            assert!((*node).unindex());
+        }
+    }
 }
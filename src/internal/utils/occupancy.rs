@@ -0,0 +1,92 @@
+//! Keeps track of how many towers reach each level, and samples these counts as the list changes,
+//! so its growth can be followed without walking it. Only compiled with the `occupancy` feature.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use super::atomic::AtomicUsize;
+use super::HEIGHT;
+use core::sync::atomic::Ordering;
+
+/// The number of samples a list keeps, dropping the oldest for each new one.
+pub const OCCUPANCY_SAMPLES: usize = 64;
+
+/// A list samples its occupancy once every this many modifications.
+pub const SAMPLE_INTERVAL: usize = 256;
+
+/// The occupancy of a list at one point in time, see `occupancy`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OccupancySample {
+    pub at: Instant,
+    /// The version of the list when the sample was taken.
+    pub version: usize,
+    pub len: usize,
+    /// The number of towers reaching each level, starting with the lowest. Levels no tower
+    /// reaches are left out.
+    pub levels: Vec<usize>,
+}
+
+pub(crate) struct Occupancy {
+    levels: [AtomicUsize; HEIGHT],
+    samples: Mutex<VecDeque<OccupancySample>>,
+}
+
+impl Occupancy {
+    pub(crate) fn new() -> Self {
+        Occupancy {
+            levels: core::array::from_fn(|_| AtomicUsize::new(0)),
+            samples: Mutex::new(VecDeque::with_capacity(OCCUPANCY_SAMPLES)),
+        }
+    }
+
+    /// Counts a tower of `height` joining the list.
+    pub(crate) fn linked(&self, height: usize) {
+        for level in &self.levels[..height] {
+            level.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Counts a tower of `height` leaving the list.
+    pub(crate) fn unlinked(&self, height: usize) {
+        for level in &self.levels[..height] {
+            level.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn now(&self, version: usize, len: usize) -> OccupancySample {
+        let mut levels = self
+            .levels
+            .iter()
+            .map(|level| level.load(Ordering::Relaxed))
+            .collect::<Vec<_>>();
+
+        while levels.last() == Some(&0) {
+            levels.pop();
+        }
+
+        OccupancySample {
+            at: Instant::now(),
+            version,
+            len,
+            levels,
+        }
+    }
+
+    /// Stores a sample of the current occupancy.
+    pub(crate) fn sample(&self, version: usize, len: usize) {
+        let sample = self.now(version, len);
+        let mut samples = self.samples.lock().unwrap();
+
+        if samples.len() == OCCUPANCY_SAMPLES {
+            samples.pop_front();
+        }
+
+        samples.push_back(sample);
+    }
+
+    /// The samples taken so far, oldest first.
+    pub(crate) fn history(&self) -> Vec<OccupancySample> {
+        self.samples.lock().unwrap().iter().cloned().collect()
+    }
+}
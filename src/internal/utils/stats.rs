@@ -0,0 +1,70 @@
+//! Summarizes how the towers of a list are distributed over its levels.
+
+/// The shape of a list, as returned by `stats`. With each level holding about half the nodes of
+/// the one below, the list is balanced and searches take logarithmic time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListStats {
+    /// The number of nodes linked on each level, starting with the lowest.
+    pub levels: Vec<usize>,
+    /// The number of nodes of each height, starting with a height of 1.
+    pub heights: Vec<usize>,
+    /// The height the list considers its highest tower to be.
+    pub max_height: usize,
+    /// The average number of steps, both along and down the levels, a search takes to reach a key
+    /// in the list.
+    pub search_depth: f64,
+}
+
+impl ListStats {
+    /// Gathers the statistics from the heights of all nodes, given in list order.
+    pub(crate) fn from_heights(heights: impl Iterator<Item = usize>, max_height: usize) -> Self {
+        let heights = heights.collect::<Vec<_>>();
+        let top = heights.iter().copied().max().unwrap_or(0);
+
+        let mut stats = ListStats {
+            levels: vec![0; top],
+            heights: vec![0; top],
+            max_height,
+            search_depth: 0.0,
+        };
+
+        // The nodes a search passes on each level since it dropped down from the level above.
+        let mut passed = vec![0; top];
+        let mut steps = 0;
+
+        for &height in heights.iter() {
+            stats.heights[height - 1] += 1;
+
+            for level in 0..height {
+                stats.levels[level] += 1;
+            }
+
+            // Searches for any later key drop down to the lower levels at this node.
+            passed[..height - 1].fill(0);
+            passed[height - 1] += 1;
+
+            steps += passed.iter().sum::<usize>() + top;
+        }
+
+        if !heights.is_empty() {
+            stats.search_depth = steps as f64 / heights.len() as f64;
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod stats_test {
+    use super::*;
+
+    #[test]
+    fn test_from_heights() {
+        let stats = ListStats::from_heights([1, 2, 1, 3, 1].into_iter(), 4);
+
+        assert_eq!(stats.levels, vec![5, 2, 1]);
+        assert_eq!(stats.heights, vec![3, 1, 1]);
+        assert_eq!(stats.max_height, 4);
+        assert_eq!(stats.search_depth, 22.0 / 5.0);
+    }
+}
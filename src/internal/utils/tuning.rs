@@ -0,0 +1,127 @@
+//! Watches how many steps searches take, so a list can pick the promotion probability that suits
+//! its size, and tell when its towers drifted too far from the shape they should have. Lists only
+//! keep count while auto tuning is on, see `set_auto_tuning`.
+
+use super::atomic::{AtomicBool, AtomicUsize};
+use core::sync::atomic::Ordering;
+
+/// Auto tuning reconsiders the promotion probability once every this many searches.
+pub(crate) const TUNE_INTERVAL: usize = 1 << 12;
+
+/// How many times longer than expected searches may take before the list counts as drifted.
+const DRIFT: f64 = 2.0;
+
+/// The promotion probabilities auto tuning picks from.
+const CANDIDATES: [f64; 4] = [0.5, 0.25, 0.125, 0.0625];
+
+/// The steps a search is expected to take in a list of `len` entries, promoting towers with a
+/// chance of `p` and capping them at `height_cap` levels. Every level but the top one takes about
+/// `1 / p` steps along and down. The top level holds the towers the cap kept from growing
+/// further, of which a search passes half.
+pub(crate) fn expected_depth(len: usize, p: f64, height_cap: usize) -> f64 {
+    if len < 2 {
+        return 1.0;
+    }
+
+    let len = len as f64;
+    let levels = (len.ln() / (1.0 / p).ln())
+        .ceil()
+        .clamp(1.0, height_cap as f64);
+
+    (levels - 1.0) / p + len * p.powf(levels - 1.0) / 2.0 + 1.0
+}
+
+pub(crate) struct Tuning {
+    enabled: AtomicBool,
+    searches: AtomicUsize,
+    /// The steps taken by the searches since the last tune.
+    steps: AtomicUsize,
+    drifted: AtomicBool,
+}
+
+impl Tuning {
+    pub(crate) fn new() -> Self {
+        Tuning {
+            enabled: AtomicBool::new(false),
+            searches: AtomicUsize::new(0),
+            steps: AtomicUsize::new(0),
+            drifted: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Counts a search that took `steps`, if auto tuning is on. Every
+    /// [TUNE_INTERVAL](TUNE_INTERVAL) searches, returns the average steps they took.
+    #[inline]
+    pub(crate) fn searched(&self, steps: usize) -> Option<f64> {
+        if !self.enabled() {
+            return None;
+        }
+
+        self.steps.fetch_add(steps, Ordering::Relaxed);
+
+        if !(self.searches.fetch_add(1, Ordering::Relaxed) + 1).is_multiple_of(TUNE_INTERVAL) {
+            return None;
+        }
+
+        Some(self.steps.swap(0, Ordering::Relaxed) as f64 / TUNE_INTERVAL as f64)
+    }
+
+    /// Compares the average `depth` of recent searches with the depth expected of a list of `len`
+    /// entries built with a promotion probability of `p`, marking the list as drifted if they are
+    /// far apart. Returns the probability that makes for noticeably shorter searches, if any.
+    pub(crate) fn tune(&self, depth: f64, len: usize, p: f64, height_cap: usize) -> Option<f64> {
+        let expected = expected_depth(len, p, height_cap);
+
+        self.drifted
+            .store(depth > expected * DRIFT, Ordering::Relaxed);
+
+        let (best, best_depth) = CANDIDATES
+            .into_iter()
+            .map(|p| (p, expected_depth(len, p, height_cap)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+        (best_depth < expected * 0.9).then_some(best)
+    }
+
+    /// Whether searches took far longer than expected last time the list was tuned.
+    pub(crate) fn drifted(&self) -> bool {
+        self.drifted.load(Ordering::Relaxed)
+    }
+
+    /// Forgets about the drift, once the towers are rebuilt.
+    pub(crate) fn rebuilt(&self) {
+        self.drifted.store(false, Ordering::Relaxed);
+        self.steps.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tuning_test {
+    use super::*;
+
+    #[test]
+    fn test_tune() {
+        let tuning = Tuning::new();
+
+        // A list of a million entries whose towers are capped at 4 levels is better off with
+        // fewer towers reaching the top one.
+        let capped = tuning.tune(expected_depth(1_000_000, 0.5, 4), 1_000_000, 0.5, 4);
+        assert_eq!(capped, Some(0.0625));
+        assert!(!tuning.drifted());
+
+        // Uncapped towers do fine with the default.
+        assert_eq!(tuning.tune(40.0, 1_000_000, 0.5, 32), None);
+        assert!(tuning.tune(400.0, 1_000_000, 0.5, 32).is_none() && tuning.drifted());
+
+        tuning.rebuilt();
+        assert!(!tuning.drifted());
+    }
+}
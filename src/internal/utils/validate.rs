@@ -0,0 +1,190 @@
+//! Checks the structure of a list against the invariants every operation has to uphold.
+
+use core::fmt::{self, Display};
+use std::collections::HashMap;
+
+use super::{Node, HEIGHT};
+
+/// A broken invariant found by `validate`. Nodes are given by their position on the level they
+/// were found on, counting from 0 just past the head.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantError {
+    /// The node's key is not greater than the key of the node before it on the lowest level, or
+    /// it comes before its predecessor on a higher level.
+    Unordered { level: usize, index: usize },
+    /// The node is linked on `level`, yet it is not linked on the level below.
+    Unreachable { level: usize, index: usize },
+    /// The node is linked on `level`, which its tower does not reach, or its height is invalid.
+    Height {
+        level: usize,
+        index: usize,
+        height: usize,
+    },
+    /// The node is flagged as removed, yet still linked into the list.
+    Removed { level: usize, index: usize },
+    /// The length of the list does not match the number of nodes on the lowest level.
+    Len { len: usize, found: usize },
+    /// The link to the node on `level` skips `span` nodes, which is not how far it lies from the
+    /// node before it. A link to the end leads to the index past the last node.
+    #[cfg(feature = "rank")]
+    Span {
+        level: usize,
+        index: usize,
+        span: usize,
+    },
+}
+
+impl Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantError::Unordered { level, index } => {
+                write!(f, "node {} on level {} is out of order", index, level)
+            }
+            InvariantError::Unreachable { level, index } => write!(
+                f,
+                "node {} on level {} is not linked on the level below",
+                index, level
+            ),
+            InvariantError::Height {
+                level,
+                index,
+                height,
+            } => write!(
+                f,
+                "node {} on level {} has an invalid height of {}",
+                index, level, height
+            ),
+            InvariantError::Removed { level, index } => {
+                write!(f, "node {} on level {} is removed but linked", index, level)
+            }
+            InvariantError::Len { len, found } => {
+                write!(f, "the list claims {} entries but links {}", len, found)
+            }
+            #[cfg(feature = "rank")]
+            InvariantError::Span { level, index, span } => write!(
+                f,
+                "the link to node {} on level {} skips {} nodes",
+                index, level, span
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvariantError {}
+
+/// Collects what it needs to know about the nodes of a list as they are visited, one level after
+/// the other from the bottom up, and in list order within each level.
+pub(crate) struct Validator {
+    /// The position on the lowest level and the number of levels each node was linked on so far,
+    /// by the address of the node.
+    nodes: HashMap<usize, (usize, usize)>,
+    level: usize,
+    index: usize,
+    last: Option<usize>,
+}
+
+impl Validator {
+    pub(crate) fn new() -> Self {
+        Validator {
+            nodes: HashMap::new(),
+            level: 0,
+            index: 0,
+            last: None,
+        }
+    }
+
+    /// Starts visiting the nodes of `level`, which must be one above the previous.
+    pub(crate) fn level(&mut self, level: usize) {
+        (self.level, self.index, self.last) = (level, 0, None);
+    }
+
+    /// Visits the next `node` of the current level, `prev` being the one before it or `None` for
+    /// the head.
+    pub(crate) fn visit<K: Ord, V>(
+        &mut self,
+        prev: Option<&Node<K, V>>,
+        node: &Node<K, V>,
+    ) -> Result<(), InvariantError> {
+        let (level, index) = (self.level, self.index);
+        let height = node.height();
+
+        if height == 0 || height > HEIGHT || height <= level {
+            return Err(InvariantError::Height {
+                level,
+                index,
+                height,
+            });
+        }
+
+        if node.removed() {
+            return Err(InvariantError::Removed { level, index });
+        }
+
+        let addr = (node as *const Node<K, V>).addr();
+
+        let position = if level == 0 {
+            if prev.is_some_and(|prev| prev.key >= node.key) {
+                return Err(InvariantError::Unordered { level, index });
+            }
+
+            self.nodes.insert(addr, (index, 1));
+            index
+        } else {
+            match self.nodes.get_mut(&addr) {
+                Some((position, levels)) if *levels == level => {
+                    *levels += 1;
+                    *position
+                }
+                _ => return Err(InvariantError::Unreachable { level, index }),
+            }
+        };
+
+        if self.last.is_some_and(|last| last >= position) {
+            return Err(InvariantError::Unordered { level, index });
+        }
+
+        (self.index, self.last) = (index + 1, Some(position));
+
+        Ok(())
+    }
+
+    /// Checks the `span` of the link to `next` on the current level, or to the end if `None`,
+    /// before `next` is visited. Nodes out of order or not linked on the levels below are left to
+    /// [visit](Self::visit) to report.
+    #[cfg(feature = "rank")]
+    pub(crate) fn span<K, V>(
+        &self,
+        next: Option<&Node<K, V>>,
+        span: usize,
+    ) -> Result<(), InvariantError> {
+        // Positions count from 1 just past the head here, so the head is at 0.
+        let from = self.last.map_or(0, |last| last + 1);
+
+        let to = match next {
+            _ if self.level == 0 => self.index + 1,
+            Some(next) => match self.nodes.get(&(next as *const Node<K, V>).addr()) {
+                Some(&(position, _)) => position + 1,
+                None => return Ok(()),
+            },
+            None => self.nodes.len() + 1,
+        };
+
+        if to > from && from + span != to {
+            return Err(InvariantError::Span {
+                level: self.level,
+                index: self.index,
+                span,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Compares the number of nodes on the lowest level with the length of the list.
+    pub(crate) fn finish(self, len: usize) -> Result<(), InvariantError> {
+        match self.nodes.len() {
+            found if found == len => Ok(()),
+            found => Err(InvariantError::Len { len, found }),
+        }
+    }
+}
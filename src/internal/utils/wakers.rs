@@ -0,0 +1,49 @@
+//! Lets async tasks park until something is inserted into a list.
+
+use core::task::Waker;
+use std::sync::Mutex;
+
+use super::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+
+pub(crate) struct Wakers {
+    /// The number of parked tasks, so inserts can skip taking the lock when no one is waiting.
+    waiting: AtomicUsize,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl Wakers {
+    pub(crate) fn new() -> Self {
+        Wakers {
+            waiting: AtomicUsize::new(0),
+            wakers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Parks `waker` until the next call to [wake_all](Wakers::wake_all). Callers have to check
+    /// their condition again after registering, otherwise a wake up that raced with the
+    /// registration may be lost.
+    pub(crate) fn register(&self, waker: &Waker) {
+        let mut wakers = self.wakers.lock().unwrap();
+
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+
+        self.waiting.store(wakers.len(), Ordering::SeqCst);
+    }
+
+    pub(crate) fn wake_all(&self) {
+        if self.waiting.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+
+        let wakers = {
+            let mut wakers = self.wakers.lock().unwrap();
+            self.waiting.store(0, Ordering::SeqCst);
+            core::mem::take(&mut *wakers)
+        };
+
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
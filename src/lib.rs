@@ -6,10 +6,47 @@
     rust_2018_idioms,
     unreachable_pub
 )]
+
+// Every list retires its nodes to a hazard pointer domain, which is built on compare and swap.
+#[cfg(not(target_has_atomic = "ptr"))]
+compile_error!("skippy-rs needs pointer sized atomics with compare and swap, which this target lacks");
+
+#[cfg(feature = "bench-utils")]
+pub mod bench;
 pub mod collections;
+pub mod compat;
+#[cfg(feature = "arbitrary")]
+pub mod fuzz;
+#[cfg(shuttle)]
+pub mod harness;
 pub mod internal;
+#[cfg(any(feature = "arbitrary", feature = "testing"))]
+pub mod model;
 pub mod skiplist;
+pub mod tagged;
+#[cfg(feature = "testing")]
+pub mod testing;
 
+pub use collections::bounded::{BoundedSkipList, Overflow};
+pub use collections::cache::{Displaced, SkipCache};
+pub use collections::descending::DescendingSkipList;
+pub use collections::hybrid_map::HybridMap;
+pub use collections::interner::Interner;
+pub use collections::lww_map::{LwwMap, Op, Stamp};
+pub use collections::multi_map::MultiMap;
 pub use collections::priority_queue::PriorityQueue;
+pub use collections::skip_map::SkipMap;
+pub use collections::skip_set::SkipSet;
+#[cfg(feature = "persistence")]
+pub use internal::durable::DurableSkipList;
+pub use internal::intrusive::{Intrusive, IntrusiveList, Links};
+pub use internal::sync::bi::BiSkipList;
+#[cfg(feature = "mmap")]
+pub use internal::mmap::MmapSkipList;
 pub use internal::skiplist::SkipList;
 pub use internal::sync::SkipList as SyncSkipList;
+#[cfg(feature = "stats")]
+pub use internal::utils::{AllocStats, ContentionStats};
+pub use internal::utils::{Buildable, Builder, InvariantError, ListStats, NodeAllocator};
+#[cfg(feature = "occupancy")]
+pub use internal::utils::{OccupancySample, OCCUPANCY_SAMPLES, SAMPLE_INTERVAL};
@@ -0,0 +1,101 @@
+//! Model testing against a `BTreeMap`.
+//!
+//! A workload is a sequence of [Op](Op)s. Every operation is applied to a
+//! [SyncSkipList](crate::SyncSkipList) and a `BTreeMap` model, and the results of both have to
+//! agree. The workloads themselves are generated by [fuzz](crate::fuzz) or
+//! [testing](crate::testing).
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use crate::SyncSkipList;
+
+/// A single operation on a list.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Op<K, V> {
+    Insert(K, V),
+    Remove(K),
+    Get(K),
+    /// Walks the whole list.
+    Iter,
+    /// Walks all entries with keys in `[from, to]`.
+    Range(K, K),
+}
+
+/// Applies `ops` to a fresh list and checks every result against a `BTreeMap`.
+pub fn check_against_btreemap<K, V>(ops: impl IntoIterator<Item = Op<K, V>>)
+where
+    K: Ord + Clone + Debug + Send + Sync,
+    V: Clone + PartialEq + Debug + Send + Sync,
+{
+    let list = SyncSkipList::new();
+    let mut model = BTreeMap::new();
+
+    for op in ops {
+        apply(&list, &mut model, op);
+    }
+
+    assert_eq!(list.len(), model.len());
+}
+
+/// Applies a single operation to both `list` and `model`, panicking if their results differ.
+pub fn apply<K, V>(list: &SyncSkipList<'_, K, V>, model: &mut BTreeMap<K, V>, op: Op<K, V>)
+where
+    K: Ord + Clone + Debug + Send + Sync,
+    V: Clone + PartialEq + Debug + Send + Sync,
+{
+    match op {
+        Op::Insert(key, val) => {
+            let old = list
+                .insert(key.clone(), val.clone())
+                .map(|e| e.val().clone());
+            assert_eq!(old, model.insert(key, val));
+        }
+        Op::Remove(key) => {
+            let old = list.remove(&key).map(|e| e.val().clone());
+            assert_eq!(old, model.remove(&key));
+        }
+        Op::Get(key) => {
+            let found = list.get(&key).map(|e| e.val().clone());
+            assert_eq!(found.as_ref(), model.get(&key));
+        }
+        Op::Iter => {
+            assert!(list
+                .iter()
+                .map(|e| (e.key().clone(), e.val().clone()))
+                .eq(model.iter().map(|(k, v)| (k.clone(), v.clone()))));
+        }
+        Op::Range(from, to) => {
+            let (from, to) = if from <= to { (from, to) } else { (to, from) };
+
+            assert!(list
+                .iter()
+                .skip_while(|e| *e.key() < from)
+                .take_while(|e| *e.key() <= to)
+                .map(|e| (e.key().clone(), e.val().clone()))
+                .eq(model
+                    .range(&from..=&to)
+                    .map(|(k, v)| (k.clone(), v.clone()))));
+        }
+    }
+}
+
+#[cfg(test)]
+mod model_test {
+    use super::*;
+
+    #[test]
+    fn test_check_against_btreemap() {
+        check_against_btreemap([
+            Op::Insert(3, 'a'),
+            Op::Insert(1, 'b'),
+            Op::Insert(3, 'c'),
+            Op::Get(3),
+            Op::Range(4, 1),
+            Op::Remove(1),
+            Op::Remove(1),
+            Op::Iter,
+        ]);
+    }
+}
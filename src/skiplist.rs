@@ -1,20 +1,53 @@
+//! The interface shared by the lists, to write code that works with either of them.
+
+use core::ops::RangeBounds;
+
+/// A sorted map backed by a skip list. Every operation takes a shared reference, entries are
+/// handed out as [Entry](Entry)s that keep their node alive for as long as they are held.
 pub trait SkipList<K, V> {
     type Entry<'a>: Entry<'a, K, V>
     where
         Self: 'a;
 
+    type Iter<'a>: Iterator<Item = Self::Entry<'a>>
+    where
+        Self: 'a;
+
+    type Range<'a, R>: Iterator<Item = Self::Entry<'a>>
+    where
+        Self: 'a,
+        R: RangeBounds<K>;
+
     fn new() -> Self;
 
-    fn insert(&self, key: K, value: V) -> Option<V>;
+    /// Inserts `value` under `key`, returning the entry it replaced, if any.
+    fn insert<'a>(&'a self, key: K, value: V) -> Option<Self::Entry<'a>>;
 
     fn get<'a>(&'a self, key: &K) -> Option<Self::Entry<'a>>;
 
-    fn remove(&self, key: &K) -> Option<(K, V)>;
+    /// Removes `key`, returning its entry if it was in the list.
+    fn remove<'a>(&'a self, key: &K) -> Option<Self::Entry<'a>>;
 
+    /// Returns the entry with the smallest key.
     fn front<'a>(&'a self) -> Option<Self::Entry<'a>>;
 
+    /// Returns the entry with the largest key.
     fn last<'a>(&'a self) -> Option<Self::Entry<'a>>;
 
+    /// Removes and returns the entry with the smallest key.
+    fn pop_front<'a>(&'a self) -> Option<Self::Entry<'a>>;
+
+    /// Removes and returns the entry with the largest key.
+    fn pop_back<'a>(&'a self) -> Option<Self::Entry<'a>>;
+
+    /// Returns an iterator over all entries, in ascending order of their keys.
+    fn iter<'a>(&'a self) -> Self::Iter<'a>;
+
+    /// Returns an iterator over the entries whose keys lie within `range`, in ascending order.
+    fn range<'a, R>(&'a self, range: R) -> Self::Range<'a, R>
+    where
+        R: RangeBounds<K>;
+
     fn len(&self) -> usize;
 
     fn is_empty(&self) -> bool {
@@ -0,0 +1,43 @@
+//! A tagged atomic pointer, as the concurrent list uses it to mark nodes that are being removed.
+//!
+//! [MaybeTagged](MaybeTagged) stores a small integer, the tag, in the low bits of a pointer that the
+//! alignment of its pointee leaves unused. Loading, storing and compare-and-swapping always treat
+//! the pointer and its tag as one word, so a tag can guard a pointer against concurrent updates.
+//!
+//! # Contracts
+//!
+//! - Only pointers aligned for `T` may be stored. The low bits of a misaligned pointer are taken
+//!   for its tag, so it would be loaded as a different pointer.
+//! - Tags may only use the bits in [TAG_MASK](MaybeTagged::TAG_MASK), others are dropped. A
+//!   pointee aligned to a single byte leaves no room for tags, which fails to compile.
+//! - The pointer is not reference counted or reclaimed in any way. To dereference it while other
+//!   threads may free the pointee, protect it with a hazard pointer first and load it once more to
+//!   make sure it was not replaced in between, as in:
+//!
+//! ```
+//! use skippy_rs::tagged::MaybeTagged;
+//!
+//! let value = Box::into_raw(Box::new(7u64));
+//! let tagged = MaybeTagged::new(value);
+//!
+//! let mut hazard = haphazard::HazardPointer::new();
+//! let mut ptr = tagged.load_ptr();
+//!
+//! loop {
+//!     hazard.protect_raw(ptr);
+//!
+//!     match tagged.load_ptr() {
+//!         now if now == ptr => break,
+//!         now => ptr = now,
+//!     }
+//! }
+//!
+//! tagged.tag(1);
+//! assert_eq!(tagged.load_decomposed(), (value, 1));
+//! assert_eq!(unsafe { *ptr }, 7);
+//!
+//! drop(hazard);
+//! drop(unsafe { Box::from_raw(value) });
+//! ```
+
+pub use crate::internal::sync::tagged::MaybeTagged;
@@ -0,0 +1,134 @@
+//! [proptest](proptest) strategies for property testing the lists, or wrappers around them,
+//! against a `BTreeMap` model.
+//!
+//! ```ignore
+//! use proptest::prelude::*;
+//! use skippy_rs::testing::{check_against_btreemap, ops};
+//!
+//! proptest! {
+//!     #[test]
+//!     fn behaves_like_a_btreemap(ops in ops(0..64u8, any::<u32>(), 0..256)) {
+//!         check_against_btreemap(ops);
+//!     }
+//! }
+//! ```
+//!
+//! To force a specific interleaving instead of waiting for a stress test to stumble upon it,
+//! [set_yield_hook](set_yield_hook) pauses the operations of one thread at the
+//! [YieldPoint](YieldPoint)s of choice, while other threads operate on the list.
+
+use proptest::{collection::SizeRange, prelude::*, strategy::LazyJust};
+
+pub use crate::internal::utils::hooks::{set_yield_hook, HookGuard, YieldPoint};
+pub use crate::model::{check_against_btreemap, Op};
+
+/// Sequences of `(key, value)` pairs, in no particular order and possibly with duplicate keys.
+pub fn pairs<K, V>(
+    key: K,
+    val: V,
+    len: impl Into<SizeRange>,
+) -> impl Strategy<Value = Vec<(K::Value, V::Value)>>
+where
+    K: Strategy,
+    V: Strategy,
+{
+    proptest::collection::vec((key, val), len)
+}
+
+/// A single [Op](Op), with inserts, removes and gets being more likely than full walks.
+pub fn op<K, V>(key: K, val: V) -> impl Strategy<Value = Op<K::Value, V::Value>>
+where
+    K: Strategy + Clone,
+    V: Strategy,
+{
+    prop_oneof![
+        4 => (key.clone(), val).prop_map(|(k, v)| Op::Insert(k, v)),
+        2 => key.clone().prop_map(Op::Remove),
+        2 => key.clone().prop_map(Op::Get),
+        1 => LazyJust::new(|| Op::Iter),
+        1 => (key.clone(), key).prop_map(|(from, to)| Op::Range(from, to)),
+    ]
+}
+
+/// Sequences of [Op](Op)s, ready to be handed to
+/// [check_against_btreemap](check_against_btreemap).
+pub fn ops<K, V>(
+    key: K,
+    val: V,
+    len: impl Into<SizeRange>,
+) -> impl Strategy<Value = Vec<Op<K::Value, V::Value>>>
+where
+    K: Strategy + Clone,
+    V: Strategy,
+{
+    proptest::collection::vec(op(key, val), len)
+}
+
+#[cfg(test)]
+mod testing_test {
+    use super::*;
+
+    use crate::SyncSkipList;
+
+    #[test]
+    fn test_remove_races_link() {
+        use std::sync::mpsc::channel;
+
+        let list = SyncSkipList::new();
+        list.insert(4, ());
+
+        let (paused_tx, paused_rx) = channel();
+        let (resume_tx, resume_rx) = channel::<()>();
+
+        std::thread::scope(|s| {
+            let list = &list;
+
+            s.spawn(move || {
+                let mut links = 0;
+
+                let _guard = set_yield_hook(move |point| {
+                    if point == (YieldPoint::Link { level: 0 }) {
+                        links += 1;
+
+                        // Wait for the predecessor to be removed before linking behind it.
+                        if links == 1 {
+                            paused_tx.send(()).unwrap();
+                            resume_rx.recv().unwrap();
+                        } else {
+                            paused_tx.send(()).unwrap();
+                        }
+                    }
+                });
+
+                list.insert(5, ());
+            });
+
+            paused_rx.recv().unwrap();
+            assert!(list.remove(&4).is_some());
+            resume_tx.send(()).unwrap();
+
+            // The link behind the removed node fails, so the insert has to try again.
+            paused_rx.recv().unwrap();
+        });
+
+        assert!(list.get(&4).is_none());
+        assert!(list.get(&5).is_some());
+        assert_eq!(list.validate(), Ok(()));
+    }
+
+    proptest! {
+        #[test]
+        fn test_ops(ops in ops(0..32u8, any::<u16>(), 0..128)) {
+            check_against_btreemap(ops);
+        }
+
+        #[test]
+        fn test_pairs(pairs in pairs(any::<u16>(), any::<u8>(), 0..256)) {
+            let list = SyncSkipList::from_iter(pairs.iter().copied());
+            let model = pairs.into_iter().collect::<std::collections::BTreeMap<_, _>>();
+
+            prop_assert_eq!(list.len(), model.len());
+            prop_assert!(list.iter().map(|e| (*e.key(), *e.val())).eq(model.into_iter()));
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Inserts into the lists from outside the crate, as a dependent would. Optimized builds act on
+//! undefined behavior that debug builds let slip, so CI runs these in release mode as well.
+
+use skippy_rs::{SkipList, SyncSkipList};
+
+#[test]
+fn smoke_insert_sync() {
+    let list = SyncSkipList::new();
+    list.insert(0, 0);
+    assert_eq!(list.len(), 1);
+
+    for i in 1..1_000 {
+        list.insert(i, i);
+    }
+
+    assert!(list.iter().map(|e| *e.key()).eq(0..1_000));
+}
+
+#[test]
+fn smoke_insert() {
+    let mut list = SkipList::new();
+
+    for i in 0..1_000 {
+        list.insert(i, i);
+    }
+
+    assert!(list.iter().map(|e| *e.key()).eq(0..1_000));
+}